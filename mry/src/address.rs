@@ -0,0 +1,79 @@
+use std::{fmt::Debug, mem::MaybeUninit, ptr::NonNull};
+
+/// Address of an argument which cannot be recorded as is, such as a raw pointer.
+/// Arguments are recorded as this with `#[mry::mry(unsafe_args)]`, and matched by `Any` or a
+/// predicate.
+#[derive(Clone, Copy)]
+pub struct Address(usize);
+
+impl Address {
+    #[doc(hidden)]
+    pub fn of<T: UnsafeArg>(arg: &T) -> Self {
+        Self(arg.address())
+    }
+
+    /// The address as an integer
+    pub fn get(&self) -> usize {
+        self.0
+    }
+}
+
+impl Debug for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Address({:#x})", self.0)
+    }
+}
+
+/// Arguments recorded by address
+pub trait UnsafeArg {
+    fn address(&self) -> usize;
+}
+
+impl<T: ?Sized> UnsafeArg for *const T {
+    fn address(&self) -> usize {
+        self.cast::<()>() as usize
+    }
+}
+
+impl<T: ?Sized> UnsafeArg for *mut T {
+    fn address(&self) -> usize {
+        self.cast::<()>() as usize
+    }
+}
+
+impl<T: ?Sized> UnsafeArg for NonNull<T> {
+    fn address(&self) -> usize {
+        self.as_ptr().address()
+    }
+}
+
+/// The address of the value itself, since its content may be uninitialized
+impl<T> UnsafeArg for MaybeUninit<T> {
+    fn address(&self) -> usize {
+        self.as_ptr().address()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_of_pointers() {
+        let mut value = [1u8, 2];
+        let ptr = value.as_mut_ptr();
+
+        assert_eq!(Address::of(&ptr).get(), ptr as usize);
+        assert_eq!(Address::of(&(ptr as *const u8)).get(), ptr as usize);
+        assert_eq!(Address::of(&NonNull::new(ptr).unwrap()).get(), ptr as usize);
+        assert_eq!(
+            Address::of(&(&mut value[..] as *mut [u8])).get(),
+            ptr as usize
+        );
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Address(255)), "Address(0xff)");
+    }
+}
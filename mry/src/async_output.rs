@@ -0,0 +1,78 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+};
+
+/// Output of `returns_with_async`, which the mocked async method awaits
+pub type BoxFuture<O> = Pin<Box<dyn Future<Output = O> + Send>>;
+
+thread_local! {
+    /// Whether the mock being called is an async method, which can await the output
+    static ACCEPTING: Cell<bool> = const { Cell::new(false) };
+    /// Future found for the async method being called
+    static PENDING: RefCell<Option<Box<dyn Any>>> = const { RefCell::new(None) };
+}
+
+/// Keeps the future found by an async behavior for the async method being called. Returns false
+/// if the method being called is not async
+pub(crate) fn stash<O: 'static>(future: BoxFuture<O>) -> bool {
+    if !ACCEPTING.with(Cell::get) {
+        return false;
+    }
+    PENDING.with(|pending| *pending.borrow_mut() = Some(Box::new(future)));
+    true
+}
+
+struct Accepting {
+    previous: bool,
+}
+
+impl Drop for Accepting {
+    fn drop(&mut self) {
+        ACCEPTING.with(|accepting| accepting.set(self.previous));
+    }
+}
+
+/// Finds the output of a call of an async method, giving the future of an async behavior to
+/// await instead if there is one
+#[doc(hidden)]
+pub fn __find_async_output<T, O: 'static>(find: impl FnOnce() -> T) -> Result<T, BoxFuture<O>> {
+    let accepting = Accepting {
+        previous: ACCEPTING.with(|accepting| accepting.replace(true)),
+    };
+    let found = find();
+    drop(accepting);
+    match PENDING.with(|pending| pending.borrow_mut().take()) {
+        Some(future) => Err(*future
+            .downcast::<BoxFuture<O>>()
+            .expect("the future of returns_with_async has the output type of the method")),
+        None => Ok(found),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gives_stashed_future() {
+        let found = __find_async_output::<_, u8>(|| {
+            assert!(stash::<u8>(Box::pin(async { 1 })));
+            None::<u8>
+        });
+
+        assert!(found.is_err());
+    }
+
+    #[test]
+    fn gives_found_output() {
+        assert_eq!(__find_async_output::<_, u8>(|| Some(1)).ok(), Some(Some(1)));
+    }
+
+    #[test]
+    fn rejects_futures_outside_async_methods() {
+        assert!(!stash::<u8>(Box::pin(async { 1 })));
+    }
+}
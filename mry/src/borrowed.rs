@@ -0,0 +1,207 @@
+use std::{
+    borrow::Borrow,
+    fmt::Debug,
+    sync::{Arc, OnceLock},
+    thread::{self, ThreadId},
+};
+
+use crate::sync::{Condvar, Mutex};
+
+use crate::ArgMatcher;
+
+/// Argument borrowed by a call, which is only read on the thread making the call until it returns
+pub(crate) struct Loan<T: ?Sized + 'static> {
+    value: *const T,
+    thread: ThreadId,
+}
+
+// SAFETY: the value is only read on the thread lending it, checked by `Loan::get`
+unsafe impl<T: ?Sized> Send for Loan<T> {}
+
+impl<T: ?Sized> Loan<T> {
+    pub(crate) fn new(value: &T) -> Self {
+        Self {
+            value,
+            thread: thread::current().id(),
+        }
+    }
+
+    /// The value if the loan is read on the lending thread
+    pub(crate) fn get(&self) -> Option<&T> {
+        // SAFETY: the value lives until the lender drops the loan, which owns this borrow
+        (self.thread == thread::current().id()).then(|| unsafe { &*self.value })
+    }
+}
+
+struct Shared<T: ?Sized + ToOwned + 'static> {
+    loan: Mutex<Option<Loan<T>>>,
+    owned: OnceLock<T::Owned>,
+    settled: Condvar,
+}
+
+/// Argument borrowed by a method with `#[mry(move_args)]`, such as `&T` or `&str`.
+/// Patterns match it in place while the call runs, and it is copied into the log only when the
+/// call returns. Reading it on another thread before that waits for the copy.
+pub struct Borrowed<T: ?Sized + ToOwned + 'static>(Arc<Shared<T>>);
+
+impl<T: ?Sized + ToOwned> Clone for Borrowed<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + ToOwned + Debug> Debug for Borrowed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|value| value.fmt(f))
+    }
+}
+
+/// Copies the argument into the log when the call returns
+struct Return<T: ?Sized + ToOwned + 'static>(Arc<Shared<T>>);
+
+impl<T: ?Sized + ToOwned> Drop for Return<T> {
+    fn drop(&mut self) {
+        let mut loan = self.0.loan.lock();
+        // Nothing keeps the argument if the call was not recorded
+        if Arc::strong_count(&self.0) > 1 {
+            if let Some(value) = loan.as_ref().and_then(Loan::get) {
+                let _ = self.0.owned.set(value.to_owned());
+            }
+        }
+        loan.take();
+        self.0.settled.notify_all();
+    }
+}
+
+impl<T: ?Sized + ToOwned> Borrowed<T> {
+    /// Runs `f` on the argument
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        if let Some(value) = self.0.owned.get() {
+            return f(value.borrow());
+        }
+        let mut loan = self.0.loan.lock();
+        loop {
+            match loan.as_ref() {
+                Some(lent) => match lent.get() {
+                    Some(value) => {
+                        let value: *const T = value;
+                        drop(loan);
+                        // SAFETY: the loan only ends when `lend` returns on this thread, which
+                        // can't happen while `f` runs on it
+                        return f(unsafe { &*value });
+                    }
+                    None => self.0.settled.wait(&mut loan),
+                },
+                None => {
+                    let value = self.0.owned.get().expect("the recorded argument is copied");
+                    return f(value.borrow());
+                }
+            }
+        }
+    }
+
+    /// Copy of the argument
+    pub fn owned(&self) -> T::Owned {
+        self.with(ToOwned::to_owned)
+    }
+
+    /// Lends the argument to the `Borrowed` given to `f`, copying it if it is recorded
+    #[doc(hidden)]
+    pub fn lend<R>(value: &T, f: impl FnOnce(Self) -> R) -> R {
+        let shared = Arc::new(Shared {
+            loan: Mutex::new(Some(Loan::new(value))),
+            owned: OnceLock::new(),
+            settled: Condvar::new(),
+        });
+        let _return = Return(shared.clone());
+        f(Self(shared))
+    }
+}
+
+impl<T: Clone + Send + 'static> Borrowed<T> {
+    #[doc(hidden)]
+    pub fn matcher(matcher: ArgMatcher<T>) -> ArgMatcher<Self> {
+        match matcher {
+            ArgMatcher::Any => ArgMatcher::Any,
+            ArgMatcher::Never => ArgMatcher::Never,
+            ArgMatcher::Captures(capture) => {
+                ArgMatcher::Captures(Box::new(move |value: &Self| value.with(&capture)))
+            }
+            matcher => ArgMatcher::Fn(Box::new(move |value: &Self| {
+                value.with(|value| matcher.matches(value))
+            })),
+        }
+    }
+}
+
+impl<T: ?Sized + ToOwned + PartialEq> Borrowed<T>
+where
+    T::Owned: PartialEq + Send + 'static,
+{
+    /// Matcher of an unsized argument such as `&str`, which patterns match as its owned type.
+    /// Values are compared with the argument in place, and other patterns see a copy of it.
+    #[doc(hidden)]
+    pub fn owned_matcher(matcher: ArgMatcher<T::Owned>) -> ArgMatcher<Self> {
+        match matcher {
+            ArgMatcher::Any => ArgMatcher::Any,
+            ArgMatcher::Never => ArgMatcher::Never,
+            ArgMatcher::Captures(capture) => {
+                ArgMatcher::Captures(Box::new(move |value: &Self| capture(&value.owned())))
+            }
+            ArgMatcher::Eq { value, partial_eq } if compares_by_partial_eq(partial_eq) => {
+                ArgMatcher::Fn(Box::new(move |actual: &Self| {
+                    actual.with(|actual| value.borrow() == actual)
+                }))
+            }
+            matcher => ArgMatcher::Fn(Box::new(move |value: &Self| {
+                matcher.matches(&value.owned())
+            })),
+        }
+    }
+}
+
+/// Whether the comparator is `PartialEq::eq` of the owned type, which compares the same as the
+/// borrowed type by the contract of `Borrow`. Copies of the same function may have different
+/// addresses, which only makes the matcher compare a copy of the argument.
+#[allow(unpredictable_function_pointer_comparisons)]
+fn compares_by_partial_eq<O: PartialEq>(partial_eq: fn(&O, &O) -> bool) -> bool {
+    partial_eq == <O as PartialEq>::eq as fn(&O, &O) -> bool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_in_place() {
+        let matcher = Borrowed::owned_matcher(ArgMatcher::from("bucket"));
+
+        assert!(Borrowed::lend("bucket", |value| matcher.matches(&value)));
+        assert!(!Borrowed::lend("other", |value| matcher.matches(&value)));
+    }
+
+    #[test]
+    fn copies_recorded_argument_when_call_returns() {
+        let recorded = Borrowed::lend(&vec![1, 2], |value| value.clone());
+
+        assert_eq!(recorded.owned(), vec![1, 2]);
+        assert!(Borrowed::matcher(ArgMatcher::new_eq(vec![1, 2])).matches(&recorded));
+    }
+
+    #[test]
+    fn waits_for_copy_on_other_thread() {
+        let value = "bucket".to_string();
+        Borrowed::lend(value.as_str(), |borrowed| {
+            let reader = {
+                let borrowed = borrowed.clone();
+                thread::spawn(move || borrowed.owned())
+            };
+            drop(borrowed);
+            // The reader is joined after the call returns and copies the argument
+            reader
+        })
+        .join()
+        .map(|value| assert_eq!(value, "bucket"))
+        .unwrap();
+    }
+}
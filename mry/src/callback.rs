@@ -0,0 +1,168 @@
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    thread::{self, ThreadId},
+};
+
+use crate::sync::Mutex;
+
+/// Callback lent by a call, with the thread making the call
+struct Lent<T> {
+    callback: *mut (dyn FnMut(&mut T) + 'static),
+    thread: ThreadId,
+    running: bool,
+}
+
+// SAFETY: the callback is only run on the thread lending it, checked by `Callback::call`
+unsafe impl<T> Send for Lent<T> {}
+
+/// Argument of a callback parameter such as `impl FnOnce(&mut T)`, which behaviors and patterns
+/// run on a value of the test to observe the mutation the unit intended.
+/// The callback is lent for the call only, so it panics if run after the call returns.
+pub struct Callback<T: 'static>(Arc<Mutex<Option<Lent<T>>>>);
+
+impl<T> Clone for Callback<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Debug for Callback<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
+/// Takes the callback back from the `Callback` handed to the mock when the call ends
+struct Return<T: 'static>(Arc<Mutex<Option<Lent<T>>>>);
+
+impl<T> Drop for Return<T> {
+    fn drop(&mut self) {
+        self.0.lock().take();
+    }
+}
+
+impl<T> Callback<T> {
+    /// Runs the callback on the value.
+    /// Panics after the call returns, on another thread, or if a `FnOnce` callback already ran.
+    pub fn call(&self, value: &mut T) {
+        let callback = {
+            let mut lent = self.0.lock();
+            let Some(lent) = lent.as_mut() else {
+                panic!(
+                    "the callback is lent for the call only, but it is run after the call returns"
+                )
+            };
+            if lent.thread != thread::current().id() {
+                panic!("the callback is lent for the call only, but it is run on another thread");
+            }
+            if lent.running {
+                panic!("the callback is running, but it is run again from itself");
+            }
+            lent.running = true;
+            lent.callback
+        };
+        let _running = Running(&self.0);
+        // SAFETY: the callback lives until `lend` returns, which takes it back before that, and
+        // it is run on the lending thread and not from itself
+        unsafe { (*callback)(value) }
+    }
+
+    /// Runs the callback on the value and returns the value it leaves
+    pub fn applied_to(&self, mut value: T) -> T {
+        self.call(&mut value);
+        value
+    }
+
+    /// Lends the callback to the `Callback` given to `f`, taking it back when `f` returns
+    #[doc(hidden)]
+    pub fn lend<R>(callback: &mut dyn FnMut(&mut T), f: impl FnOnce(Self) -> R) -> R {
+        let callback: *mut (dyn FnMut(&mut T) + '_) = callback;
+        // SAFETY: only the lifetime is erased, and `Return` takes the callback back before it ends
+        let callback: *mut (dyn FnMut(&mut T) + 'static) = unsafe { std::mem::transmute(callback) };
+        let lent = Arc::new(Mutex::new(Some(Lent {
+            callback,
+            thread: thread::current().id(),
+            running: false,
+        })));
+        let _return = Return(lent.clone());
+        f(Self(lent))
+    }
+
+    /// Runs the `FnOnce` callback kept for the real implementation
+    #[doc(hidden)]
+    pub fn once(callback: &mut Option<impl FnOnce(&mut T)>, value: &mut T) {
+        match callback.take() {
+            Some(callback) => callback(value),
+            None => panic!("the callback is FnOnce, but it is run more than once"),
+        }
+    }
+
+    /// Runs the `FnMut` or `Fn` callback kept for the real implementation
+    #[doc(hidden)]
+    pub fn each(callback: &mut Option<impl FnMut(&mut T)>, value: &mut T) {
+        if let Some(callback) = callback {
+            callback(value)
+        }
+    }
+}
+
+/// Gives the callback kept while the mock looks for the output back to the real implementation
+#[doc(hidden)]
+pub fn returned_callback<F>(callback: Option<F>, name: &str) -> F {
+    callback.unwrap_or_else(|| {
+        panic!(
+            "{} cannot call the real implementation, because the mock ran its FnOnce callback",
+            name
+        )
+    })
+}
+
+/// Marks the callback as not running when it returns or unwinds
+struct Running<'a, T: 'static>(&'a Mutex<Option<Lent<T>>>);
+
+impl<T> Drop for Running<'_, T> {
+    fn drop(&mut self) {
+        if let Some(lent) = self.0.lock().as_mut() {
+            lent.running = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_lent_callback() {
+        let mut retries = 0;
+        let mut set = |value: &mut u8| {
+            retries += 1;
+            *value = 3;
+        };
+
+        let value = Callback::lend(&mut set, |callback| callback.applied_to(0));
+
+        assert_eq!(value, 3);
+        assert_eq!(retries, 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the callback is lent for the call only, but it is run after the call returns"
+    )]
+    fn panics_after_call() {
+        let callback = Callback::lend(&mut |value: &mut u8| *value = 3, |callback| callback);
+
+        callback.applied_to(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "the callback is FnOnce, but it is run more than once")]
+    fn runs_fn_once_once() {
+        let mut callback = Some(|value: &mut u8| *value = 3);
+
+        Callback::once(&mut callback, &mut 0);
+        Callback::once(&mut callback, &mut 0);
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::sync::Mutex;
+
+use crate::{ArgMatcher, MockableArg};
+
+/// Matcher of an argument which matches any value and records it, for custom assertions on the
+/// arguments after the calls.
+///
+/// ```
+/// #[mry::mry]
+/// #[derive(Default)]
+/// struct Cat {}
+///
+/// #[mry::mry]
+/// impl Cat {
+///     fn meow(&self, count: usize) -> String {
+///         "meow".repeat(count)
+///     }
+/// }
+///
+/// let mut cat = Cat::default();
+/// let counts = mry::Captor::new();
+/// cat.mock_meow(counts.clone()).returns("Called".into());
+///
+/// cat.meow(2);
+/// cat.meow(3);
+///
+/// assert_eq!(counts.values(), vec![2, 3]);
+/// ```
+///
+/// Values are recorded only for the calls served by the rule of the captor, not for calls the
+/// rule rejects on another argument nor for the calls scanned by `assert_called`.
+pub struct Captor<T>(Arc<Mutex<Vec<T>>>);
+
+impl<T> Clone for Captor<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Default for Captor<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Captor<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captured values in the order of the calls
+    pub fn values(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.0.lock().clone()
+    }
+
+    /// The latest captured value
+    pub fn last(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.0.lock().last().cloned()
+    }
+}
+
+impl<T: Clone + MockableArg> From<Captor<T>> for ArgMatcher<T> {
+    fn from(captor: Captor<T>) -> Self {
+        ArgMatcher::Captures(Box::new(move |value: &T| {
+            captor.0.lock().push(value.clone());
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_served_values() {
+        let captor = Captor::new();
+        let matcher: ArgMatcher<u8> = captor.clone().into();
+        assert!(matcher.matches(&1));
+        assert!(captor.values().is_empty());
+
+        matcher.capture(&1);
+        matcher.capture(&2);
+
+        assert_eq!(captor.values(), vec![1, 2]);
+        assert_eq!(captor.last(), Some(2));
+    }
+}
@@ -0,0 +1,52 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
+
+use crate::{sync::RwLock, BoxFuture};
+
+/// Source of the time mry reads, for the durations of `measure_durations` and the waits of
+/// `assert_called_eventually` and `returns_after`.
+///
+/// Replace it by `set_clock` to control the time under deterministic simulators such as madsim.
+pub trait ClockSource: Send + Sync + 'static {
+    fn now(&self) -> Instant;
+
+    /// Sleep of the waits for the duration, such as `madsim::time::sleep`. `None` leaves them to
+    /// the timer of tokio or to the timer thread of mry, which the `sim` feature removes.
+    fn sleep(&self, _duration: Duration) -> Option<BoxFuture<()>> {
+        None
+    }
+}
+
+/// Clock of the operating system, used by default
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl ClockSource for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+fn clock() -> &'static RwLock<Arc<dyn ClockSource>> {
+    static CLOCK: OnceLock<RwLock<Arc<dyn ClockSource>>> = OnceLock::new();
+    CLOCK.get_or_init(|| RwLock::new(Arc::new(SystemClock)))
+}
+
+/// Replaces the source of the time mry reads. The clock is global to the test process, as the
+/// timer thread of mry reads it too, so set it once at the start of a simulation.
+pub fn set_clock(source: impl ClockSource) {
+    *clock().write() = Arc::new(source);
+}
+
+/// Current time of the clock set by `set_clock`
+pub(crate) fn now() -> Instant {
+    clock().read().now()
+}
+
+/// Sleep of the clock set by `set_clock`, if it has one
+#[cfg(feature = "async")]
+pub(crate) fn sleep(duration: Duration) -> Option<BoxFuture<()>> {
+    clock().read().sleep(duration)
+}
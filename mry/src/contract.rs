@@ -0,0 +1,199 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Stubs of methods serialized to a versioned JSON format, which one team publishes from its
+/// service and another loads into its mocks by `returns_contract`, for consumer-driven contract
+/// tests.
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "stubs": [
+///     { "method": "Payments::charge", "args": [{ "eq": "alice" }, "any"], "returns": { "Ok": 100 } }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Contract {
+    stubs: Vec<Stub>,
+}
+
+/// Canned response of a method to the arguments matching the specs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stub {
+    /// Name of the method agreed by both sides, such as `Payments::charge`
+    pub method: String,
+    pub args: Vec<ArgSpec>,
+    pub returns: Value,
+}
+
+/// Expected argument of a stub, serialized as `"any"` or `{ "eq": value }`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArgSpec {
+    Any,
+    Eq(Value),
+}
+
+impl ArgSpec {
+    /// Matches an argument serialized to the same JSON as the value
+    pub fn eq(value: impl Serialize) -> Self {
+        ArgSpec::Eq(serde_json::to_value(value).expect("the argument is not serializable to JSON"))
+    }
+
+    pub(crate) fn matches(&self, arg: &Value) -> bool {
+        match self {
+            ArgSpec::Any => true,
+            ArgSpec::Eq(value) => value == arg,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            ArgSpec::Any => json!("any"),
+            ArgSpec::Eq(value) => json!({ "eq": value }),
+        }
+    }
+
+    fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::String(any) if any == "any" => Ok(ArgSpec::Any),
+            Value::Object(spec) if spec.len() == 1 && spec.contains_key("eq") => {
+                Ok(ArgSpec::Eq(spec["eq"].clone()))
+            }
+            _ => Err(format!(
+                "unknown argument spec {}, expected \"any\" or {{\"eq\": value}}",
+                value
+            )),
+        }
+    }
+}
+
+impl Contract {
+    /// Version of the format written by `to_json`. `from_json` rejects other versions
+    pub const VERSION: u64 = 1;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stub returning the value serialized to JSON
+    pub fn stub(
+        mut self,
+        method: impl Into<String>,
+        args: impl IntoIterator<Item = ArgSpec>,
+        returns: impl Serialize,
+    ) -> Self {
+        self.stubs.push(Stub {
+            method: method.into(),
+            args: args.into_iter().collect(),
+            returns: serde_json::to_value(returns)
+                .expect("the returned value is not serializable to JSON"),
+        });
+        self
+    }
+
+    pub fn stubs(&self) -> &[Stub] {
+        &self.stubs
+    }
+
+    /// Serializes the contract to pretty-printed JSON to publish
+    pub fn to_json(&self) -> String {
+        let stubs: Vec<Value> = self
+            .stubs
+            .iter()
+            .map(|stub| {
+                json!({
+                    "method": stub.method,
+                    "args": stub.args.iter().map(ArgSpec::to_value).collect::<Vec<_>>(),
+                    "returns": stub.returns,
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&json!({ "version": Self::VERSION, "stubs": stubs }))
+            .expect("JSON values are always serializable")
+    }
+
+    /// Loads a contract published by `to_json`
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let contract: Value = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let contract = object(&contract, "the contract")?;
+        match contract.get("version").and_then(Value::as_u64) {
+            Some(Self::VERSION) => {}
+            Some(version) => return Err(format!("unsupported contract version {}", version)),
+            None => return Err("the contract has no version".to_string()),
+        }
+        let stubs = contract
+            .get("stubs")
+            .and_then(Value::as_array)
+            .ok_or("the contract has no array of stubs")?
+            .iter()
+            .map(|stub| {
+                let stub = object(stub, "a stub")?;
+                Ok(Stub {
+                    method: stub
+                        .get("method")
+                        .and_then(Value::as_str)
+                        .ok_or("a stub has no method name")?
+                        .to_string(),
+                    args: stub
+                        .get("args")
+                        .and_then(Value::as_array)
+                        .ok_or("a stub has no array of args")?
+                        .iter()
+                        .map(ArgSpec::from_value)
+                        .collect::<Result<_, _>>()?,
+                    returns: stub
+                        .get("returns")
+                        .cloned()
+                        .ok_or("a stub has no returned value")?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { stubs })
+    }
+}
+
+fn object<'a>(value: &'a Value, name: &str) -> Result<&'a Map<String, Value>, String> {
+    value
+        .as_object()
+        .ok_or_else(|| format!("{} is not a JSON object", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_json() {
+        let contract = Contract::new()
+            .stub(
+                "Payments::charge",
+                [ArgSpec::eq("alice"), ArgSpec::Any],
+                100,
+            )
+            .stub("Payments::refund", [], json!({ "Err": "closed" }));
+
+        assert_eq!(Contract::from_json(&contract.to_json()), Ok(contract));
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        assert_eq!(
+            Contract::from_json(r#"{ "version": 2, "stubs": [] }"#),
+            Err("unsupported contract version 2".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_arg_specs() {
+        let json = r#"{
+            "version": 1,
+            "stubs": [{ "method": "Payments::charge", "args": [3], "returns": 100 }]
+        }"#;
+
+        assert_eq!(
+            Contract::from_json(json),
+            Err("unknown argument spec 3, expected \"any\" or {\"eq\": value}".to_string())
+        );
+    }
+}
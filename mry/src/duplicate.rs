@@ -0,0 +1,36 @@
+use std::cell::Cell;
+
+/// What to do when a rule is registered with the same pattern as an existing rule of the method,
+/// which shadows the new one because the earlier rule serves the calls first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateRule {
+    /// Registers the rule silently, the default
+    Allow,
+    /// Registers the rule and reports it in `Harness::journal`
+    Warn,
+    /// Removes the earlier rules with the same pattern, so the new one serves the calls
+    Replace,
+    /// Panics at the registration
+    Panic,
+}
+
+thread_local! {
+    static DUPLICATE_RULE: Cell<DuplicateRule> = const { Cell::new(DuplicateRule::Allow) };
+}
+
+/// Sets what to do with rules registered twice with the same pattern, `DuplicateRule::Allow` by
+/// default. Patterns are the same if each argument is `Any`, `Never`, or an equal value in both.
+/// Predicates are never considered the same.
+///
+/// Rules scoped by `within` shadow outer rules on purpose, so tests doing it with the same
+/// pattern should keep `Allow`.
+///
+/// The setting is of the current thread, which is the current test under `cargo test`, so tests
+/// running in parallel do not affect each other.
+pub fn set_duplicate_rule(policy: DuplicateRule) {
+    DUPLICATE_RULE.with(|current| current.set(policy));
+}
+
+pub(crate) fn duplicate_rule() -> DuplicateRule {
+    DUPLICATE_RULE.with(Cell::get)
+}
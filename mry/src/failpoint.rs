@@ -0,0 +1,145 @@
+use std::{cell::RefCell, collections::HashMap, str::FromStr, sync::OnceLock};
+
+/// Environment variable configuring failpoints at startup, such as
+/// `MRY_FAILPOINTS="db.save=return(timeout);cache.get=panic"`
+pub const FAILPOINTS_ENV: &str = "MRY_FAILPOINTS";
+
+/// Action of a failpoint which controls rules set with `on_failpoint`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailAction {
+    /// Falls through to the other rules
+    Off,
+    /// Returns the output of the behavior, with the argument of `return(...)` if any
+    Return(Option<String>),
+    /// Panics, with the message of `panic(...)` if any
+    Panic(Option<String>),
+}
+
+impl FromStr for FailAction {
+    type Err = String;
+
+    fn from_str(action: &str) -> Result<Self, Self::Err> {
+        let action = action.trim();
+        let (name, arg) = match action.split_once('(') {
+            Some((name, rest)) => match rest.strip_suffix(')') {
+                Some(arg) => (name, Some(arg.to_string())),
+                None => {
+                    return Err(format!(
+                        "unclosed parenthesis in failpoint action `{}`",
+                        action
+                    ))
+                }
+            },
+            None => (action, None),
+        };
+        match (name, arg) {
+            ("off", None) => Ok(FailAction::Off),
+            ("return", arg) => Ok(FailAction::Return(arg)),
+            ("panic", arg) => Ok(FailAction::Panic(arg)),
+            _ => Err(format!("unknown failpoint action `{}`", action)),
+        }
+    }
+}
+
+thread_local! {
+    /// Failpoints set by `set_failpoint` on this thread, which is the current test under
+    /// `cargo test`, so tests running in parallel don't flip the rules of each other
+    static FAILPOINTS: RefCell<HashMap<String, FailAction>> = RefCell::new(HashMap::new());
+}
+
+/// Failpoints configured by the environment, read once and shared by every thread
+fn configured() -> &'static HashMap<String, FailAction> {
+    static CONFIGURED: OnceLock<HashMap<String, FailAction>> = OnceLock::new();
+    CONFIGURED.get_or_init(|| match std::env::var(FAILPOINTS_ENV) {
+        Ok(config) => {
+            parse(&config).unwrap_or_else(|err| panic!("invalid {}: {}", FAILPOINTS_ENV, err))
+        }
+        Err(_) => HashMap::new(),
+    })
+}
+
+fn parse(config: &str) -> Result<HashMap<String, FailAction>, String> {
+    config
+        .split(';')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (name, action) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("missing `=` in failpoint `{}`", entry))?;
+            Ok((name.trim().to_string(), action.parse()?))
+        })
+        .collect()
+}
+
+/// Sets the action of the failpoint, such as `set_failpoint("db.save", "return(timeout)")`.
+/// The action is one of `off`, `return`, `return(arg)`, `panic` and `panic(message)`.
+///
+/// The setting is of the current thread, which is the current test under `cargo test`, and
+/// takes precedence over `MRY_FAILPOINTS`. Calls made on other threads, such as by a thread pool
+/// of the code under test, follow `MRY_FAILPOINTS` only.
+pub fn set_failpoint(name: impl Into<String>, action: &str) -> Result<(), String> {
+    let action = action.parse()?;
+    FAILPOINTS.with(|failpoints| failpoints.borrow_mut().insert(name.into(), action));
+    Ok(())
+}
+
+/// Removes the setting of the failpoint on the current thread, which turns it off unless
+/// `MRY_FAILPOINTS` configures it
+pub fn remove_failpoint(name: &str) {
+    FAILPOINTS.with(|failpoints| failpoints.borrow_mut().remove(name));
+}
+
+pub(crate) fn action(name: &str) -> FailAction {
+    FAILPOINTS
+        .with(|failpoints| failpoints.borrow().get(name).cloned())
+        .or_else(|| configured().get(name).cloned())
+        .unwrap_or(FailAction::Off)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_actions() {
+        assert_eq!("off".parse(), Ok(FailAction::Off));
+        assert_eq!("return".parse(), Ok(FailAction::Return(None)));
+        assert_eq!(
+            "return(timeout)".parse(),
+            Ok(FailAction::Return(Some("timeout".into())))
+        );
+        assert_eq!(
+            " panic(boom) ".parse(),
+            Ok(FailAction::Panic(Some("boom".into())))
+        );
+        assert!("sleep(10)".parse::<FailAction>().is_err());
+        assert!("return(timeout".parse::<FailAction>().is_err());
+    }
+
+    #[test]
+    fn parses_config() {
+        assert_eq!(
+            parse("db.save=return(timeout); cache.get=panic;"),
+            Ok(HashMap::from([
+                (
+                    "db.save".to_string(),
+                    FailAction::Return(Some("timeout".into()))
+                ),
+                ("cache.get".to_string(), FailAction::Panic(None)),
+            ]))
+        );
+        assert!(parse("db.save").is_err());
+    }
+
+    #[test]
+    fn failpoints_of_other_threads_are_off() {
+        set_failpoint("tests.other_thread", "panic").unwrap();
+
+        let other = std::thread::spawn(|| action("tests.other_thread"))
+            .join()
+            .unwrap();
+
+        assert_eq!(action("tests.other_thread"), FailAction::Panic(None));
+        assert_eq!(other, FailAction::Off);
+    }
+}
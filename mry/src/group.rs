@@ -0,0 +1,58 @@
+use std::{cell::RefCell, collections::HashMap};
+
+/// Check of an expectation, returning the failure if any
+type Check = Box<dyn Fn() -> Option<String>>;
+
+thread_local! {
+    static GROUPS: RefCell<HashMap<String, Vec<Check>>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn push(label: String, check: Check) {
+    GROUPS.with(|groups| groups.borrow_mut().entry(label).or_default().push(check));
+}
+
+/// Verifies the expectations tagged with the label by `group`, and forgets them.
+/// Panics listing every failed expectation of the group, or if nothing is tagged with the label.
+pub fn verify_group(label: &str) {
+    let checks = GROUPS
+        .with(|groups| groups.borrow_mut().remove(label))
+        .unwrap_or_else(|| panic!("no expectation is in the group {}", label));
+    let failures: String = checks
+        .iter()
+        .filter_map(|check| check())
+        .map(|failure| format!("\n    {}", failure))
+        .collect();
+    if !failures.is_empty() {
+        panic!("Expectations of the group {} failed:{}", label, failures);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_without_failures() {
+        push("a".into(), Box::new(|| None));
+
+        verify_group("a");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expectations of the group a failed:\n    first\n    second")]
+    fn lists_failures() {
+        push("a".into(), Box::new(|| Some("first".into())));
+        push("a".into(), Box::new(|| None));
+        push("a".into(), Box::new(|| Some("second".into())));
+
+        verify_group("a");
+    }
+
+    #[test]
+    #[should_panic(expected = "no expectation is in the group b")]
+    fn panics_on_unknown_group() {
+        push("a".into(), Box::new(|| None));
+
+        verify_group("b");
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::mocks::AnyMock;
+
+/// Type-erased handle of a mocked method, so test utilities can keep mocks of any signatures in
+/// one `Vec<AnyMockHandle>` for cross-cutting checks.
+/// Get one from a locator with `handle()`, or all of an object with `Mry::handles()`.
+pub struct AnyMockHandle(Box<dyn AnyMock>);
+
+impl AnyMockHandle {
+    pub(crate) fn new(mock: Box<dyn AnyMock>) -> Self {
+        Self(mock)
+    }
+
+    /// Name of the method such as `Cat::meow`
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    /// Number of the recorded calls
+    pub fn calls(&self) -> usize {
+        self.0.calls()
+    }
+
+    /// Asserts every rule of the method served a call.
+    /// Panics with the number of the unused rules
+    pub fn verify(&self) {
+        let unused = self.0.unused_rules();
+        if unused > 0 {
+            panic!("{} rules of {} were never used", unused, self.name());
+        }
+    }
+
+    /// Removes the rules and the recorded calls of the method
+    pub fn reset(&self) {
+        self.0.reset();
+    }
+
+    /// Summary of the method such as `Cat::meow called 2 times with 1 rules (0 unused)`
+    pub fn dump(&self) -> String {
+        format!(
+            "{} called {} times with {} rules ({} unused)",
+            self.name(),
+            self.calls(),
+            self.0.rules(),
+            self.0.unused_rules()
+        )
+    }
+}
+
+impl fmt::Debug for AnyMockHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.dump())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::sync::Mutex;
+
+    use crate::{mock::Mock, mocks::SharedMock, Behavior1, Matcher};
+
+    use super::*;
+
+    fn handle() -> (SharedMock<(u8,), u8>, AnyMockHandle) {
+        let mock = Arc::new(Mutex::new(Mock::<(u8,), u8>::new("Cat::meow")));
+        (mock.clone(), AnyMockHandle::new(Box::new(mock)))
+    }
+
+    #[test]
+    fn dumps_the_mock() {
+        let (mock, handle) = handle();
+        mock.lock()
+            .returns_with(Matcher::any().wrapped(), Behavior1::from(|a| a).into());
+        mock.lock().record_call_and_find_mock_output((1,));
+
+        assert_eq!(handle.name(), "Cat::meow");
+        assert_eq!(handle.calls(), 1);
+        assert_eq!(
+            format!("{:?}", handle),
+            "Cat::meow called 1 times with 1 rules (0 unused)"
+        );
+        handle.verify();
+    }
+
+    #[test]
+    fn resets_the_mock() {
+        let (mock, handle) = handle();
+        mock.lock()
+            .returns_with(Matcher::any().wrapped(), Behavior1::from(|a| a).into());
+        mock.lock().record_call_and_find_mock_output((1,));
+
+        handle.reset();
+
+        assert_eq!(
+            handle.dump(),
+            "Cat::meow called 0 times with 0 rules (0 unused)"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "1 rules of Cat::meow were never used")]
+    fn verify_panics_on_unused_rules() {
+        let (mock, handle) = handle();
+        mock.lock()
+            .returns_with(Matcher::any().wrapped(), Behavior1::from(|a| a).into());
+
+        handle.verify();
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+use crate::Mry;
+
+/// Container of several mocked objects, to verify, reset and inspect them together.
+///
+/// ```
+/// #[mry::mry]
+/// #[derive(Default)]
+/// struct Auth {}
+///
+/// #[mry::mry]
+/// impl Auth {
+///     fn login(&self, user: String) -> bool {
+///         todo!()
+///     }
+/// }
+///
+/// let mut auth = Auth::default();
+/// let mut harness = mry::Harness::new("login succeeds");
+/// harness.register("auth", &mut auth.mry);
+///
+/// auth.mock_login(mry::Any).returns(true);
+/// auth.login("alice".into());
+///
+/// harness.verify_all();
+/// ```
+#[derive(Default)]
+pub struct Harness {
+    label: String,
+    objects: Vec<(String, Mry)>,
+}
+
+impl Harness {
+    /// Creates a harness with the label prefixed to its reports, such as the name of the test
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            objects: Vec::new(),
+        }
+    }
+
+    /// Registers the `mry` field of a mocked object with the name used in the reports
+    pub fn register(&mut self, name: impl Into<String>, mry: &mut Mry) -> &mut Self {
+        self.objects.push((name.into(), mry.generate().clone()));
+        self
+    }
+
+    /// Asserts every rule set on the registered objects served a call.
+    /// Panics listing all the unused rules of all the objects
+    pub fn verify_all(&self) {
+        let mut failures = String::new();
+        for (object, mock) in self.mocks() {
+            let unused = mock.unused_rules;
+            if unused > 0 {
+                write!(
+                    failures,
+                    "\n    {}: {} rules of {} were never used",
+                    object, unused, mock.name
+                )
+                .unwrap();
+            }
+        }
+        if !failures.is_empty() {
+            panic!("[{}] Unused rules:{}", self.label, failures);
+        }
+    }
+
+    /// Removes the rules and the recorded calls of all the registered objects
+    pub fn reset_all(&self) {
+        for (_, mry) in &self.objects {
+            // Dropped after unlocking, as `Drop` impls of recorded calls may call the mocks
+            let removed = std::mem::take(&mut *mry.shared_mocks().lock());
+            drop(removed);
+        }
+    }
+
+    /// Summary of the calls recorded by all the registered objects, one line per method, followed
+    /// by warnings such as rules registered twice with the same pattern, and notes such as seeds
+    pub fn journal(&self) -> String {
+        let mut journal = format!("[{}]", self.label);
+        for (object, mock) in self.mocks() {
+            write!(
+                journal,
+                "\n    {}: {} called {} times",
+                object, mock.name, mock.calls
+            )
+            .unwrap();
+            for warning in &mock.warnings {
+                write!(journal, "\n    {}: warning: {}", object, warning).unwrap();
+            }
+            for note in &mock.notes {
+                write!(journal, "\n    {}: {}", object, note).unwrap();
+            }
+        }
+        journal
+    }
+
+    /// Mocks of the registered objects in the registered order, and by name in an object.
+    /// Snapshots are taken so that the lock of an object is not held while reporting
+    fn mocks(&self) -> Vec<(&str, Summary)> {
+        let mut mocks = Vec::new();
+        for (object, mry) in &self.objects {
+            let mut summaries: Vec<_> = mry
+                .shared_mocks()
+                .lock()
+                .iter()
+                .map(|mock| Summary {
+                    name: mock.name(),
+                    calls: mock.calls(),
+                    unused_rules: mock.unused_rules(),
+                    warnings: mock.warnings(),
+                    notes: mock.notes(),
+                })
+                .collect();
+            summaries.sort_by_key(|summary| summary.name);
+            mocks.extend(
+                summaries
+                    .into_iter()
+                    .map(|summary| (object.as_str(), summary)),
+            );
+        }
+        mocks
+    }
+}
+
+struct Summary {
+    name: &'static str,
+    calls: usize,
+    unused_rules: usize,
+    warnings: Vec<String>,
+    notes: Vec<String>,
+}
@@ -1,17 +1,126 @@
+mod address;
+mod async_output;
+mod borrowed;
+mod callback;
+mod captor;
+mod clock;
+#[cfg(feature = "json")]
+mod contract;
+mod duplicate;
+mod failpoint;
+mod group;
+mod handle;
+#[cfg(any(debug_assertions, feature = "bench"))]
+mod harness;
+mod method_meta;
+mod misuse;
 mod mock;
 mod mock_locator;
 mod mockable;
 mod mocks;
 mod mry;
+mod projection;
+mod random;
+mod receiver;
+mod recursion;
+mod redacted;
+mod reentrancy;
 mod rule;
+mod scope;
+mod sequence;
 mod static_mocks;
+mod sync;
+mod test_token;
+mod truncate;
 
 pub use crate::mry::*;
+pub use address::*;
+pub use async_output::{__find_async_output, BoxFuture};
+pub use borrowed::Borrowed;
+pub use callback::{returned_callback, Callback};
+pub use captor::Captor;
+pub use clock::{set_clock, ClockSource, SystemClock};
+#[cfg(feature = "json")]
+pub use contract::{ArgSpec, Contract, Stub};
+pub use duplicate::{set_duplicate_rule, DuplicateRule};
+pub use failpoint::{remove_failpoint, set_failpoint, FailAction, FAILPOINTS_ENV};
+pub use group::verify_group;
+pub use handle::AnyMockHandle;
+#[cfg(any(debug_assertions, feature = "bench"))]
+pub use harness::Harness;
+pub use method_meta::*;
+pub use misuse::*;
+#[cfg(feature = "async")]
+pub use mock::CallNotified;
+pub use mock::{CalledLogs, LogStore};
 pub use mock_locator::*;
 pub use mocks::*;
-pub use mry_macros::{lock, m, mry, new};
+/// Fails the compilation of non-test builds which would contain mocks, for the root of binary
+/// crates to protect production binaries.
+///
+/// Takes the same `cfg = ...` as `#[mry::mry]`, and checks the gate of the mocks it results in:
+/// `debug_assertions` by default, narrowed by `cfg`, and widened to release builds by the `bench`
+/// feature of mry. Without `cfg`, every debug build of the binary fails, so narrow the gate of the
+/// mocks, such as to `test`, and pass the same here.
+///
+/// ```
+/// mry::deny_mocks!(cfg = test);
+/// ```
+pub use mry_macros::deny_mocks;
+/// Locks static mocks while evaluating the body, for places `#[mry::lock]` cannot be put on such as
+/// doc tests.
+///
+/// ```
+/// #[mry::mry]
+/// fn hello(count: usize) -> String {
+///     "hello".repeat(count)
+/// }
+///
+/// mry::locked!(hello => {
+///     mock_hello(mry::Any).returns("Called".to_string());
+///
+///     assert_eq!(hello(2), "Called");
+/// });
+/// ```
+pub use mry_macros::locked;
+/// Constructs a struct annotated with `#[mry::mry]` from a struct literal, filling the hidden
+/// `mry` field, so construction sites only need wrapping after annotating a struct.
+///
+/// `..rest` is ignored, since the literal names every field but `mry`.
+///
+/// ```
+/// #[mry::mry]
+/// struct Cat {
+///     name: String,
+/// }
+///
+/// let cat = mry::new!(Cat { name: "Tama".into() });
+/// ```
+pub use mry_macros::new;
+/// Runs the test with a token of its own, which tags the mocks and rules created in it. The test
+/// fails at the end if it touched mock state tagged by another test, such as a shared fixture
+/// configured elsewhere, listing what it touched. Adds `#[test]` unless another test attribute
+/// such as `#[tokio::test]` is there.
+///
+/// ```
+/// #[mry::test]
+/// fn meows() {
+///     // ...
+/// }
+/// ```
+pub use mry_macros::test;
+pub use mry_macros::{cfg_select, lock, m, mry, sequence, stub, MryMatch};
+pub use projection::Projected;
+pub use receiver::with_receiver;
+pub use recursion::{__enter_real_impl, set_recursion_limit, RealImpl};
+pub use redacted::Redacted;
 pub use rule::*;
+pub use scope::{scope, Scope};
+pub use sequence::Sequence;
 pub use static_mocks::*;
+pub use sync::Mutex;
+pub use test_token::{__run_async_test, __run_test};
+pub use truncate::set_debug_limit;
 
 pub use rule::ArgMatcher::Any;
 
@@ -0,0 +1,14 @@
+/// Metadata of a mockable method, listed in `MRY_METHODS` generated by `#[mry::mry]`
+///
+/// Type names are the ones written in the source, not resolved paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MethodMeta {
+    /// Name of the method such as `meow`
+    pub name: &'static str,
+    /// Number of arguments excluding the receiver
+    pub arity: usize,
+    /// Type names of the arguments excluding the receiver
+    pub arg_types: &'static [&'static str],
+    /// Type name of the output
+    pub return_type: &'static str,
+}
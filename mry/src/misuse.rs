@@ -0,0 +1,129 @@
+use std::{
+    any::Any,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+/// Misuse of mry which is detected at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misuse {
+    /// A static function is mocked without `mry::lock`
+    MissingLock,
+    /// A static function is locked by `mry::lock` but never mocked
+    UnusedLock,
+    /// A mock is called with arguments no rule matches
+    MockNotFound,
+    /// A rule set with `returns_once` is used twice
+    CalledMoreThanOnce,
+    /// A call does not follow the order of `mry::sequence!`
+    OutOfSequence,
+    /// A rule set with `returns_each` ran out of the values
+    Exhausted,
+}
+
+impl Misuse {
+    fn matches(&self, message: &str) -> bool {
+        match self {
+            Misuse::MissingLock => {
+                message.starts_with("the lock of `") && message.contains("` is not acquired.")
+            }
+            Misuse::UnusedLock => message.contains(" is locked but no used."),
+            Misuse::MockNotFound => message.starts_with("mock not found for "),
+            Misuse::CalledMoreThanOnce => message.contains(" was called more than once"),
+            Misuse::OutOfSequence => {
+                (message.starts_with("Expected call #")
+                    && message.contains(" of the sequence to be "))
+                    || message.ends_with(" was called after the end of the sequence")
+            }
+            Misuse::Exhausted => message.contains(" ran out of the values of returns_each after "),
+        }
+    }
+}
+
+/// Asserts the closure panics because of the misuse, so that rules of using mocks can be enforced
+/// in tests.
+///
+/// ```
+/// #[mry::mry]
+/// fn hello(count: usize) -> String {
+///     "hello".repeat(count)
+/// }
+///
+/// mry::assert_misuse(mry::Misuse::MissingLock, || {
+///     mock_hello(mry::Any).returns("Called".to_string());
+/// });
+/// ```
+pub fn assert_misuse<T>(misuse: Misuse, f: impl FnOnce() -> T) {
+    let Err(payload) = catch_unwind(AssertUnwindSafe(f)) else {
+        panic!("Expected {:?} misuse, but nothing panicked", misuse);
+    };
+    let message = panic_message(payload.as_ref());
+    if !misuse.matches(message) {
+        panic!(
+            "Expected {:?} misuse, but it panicked with: {}",
+            misuse, message
+        );
+    }
+}
+
+/// Asserts each file matching the glob, such as `tests/misuse/*.rs`, fails to compile with the
+/// errors in the `.stderr` file next to it, so that misuse caught by the compiler, such as a
+/// matcher of a wrong type, can be enforced in tests too. A thin wrapper of `trybuild`, which
+/// writes the missing `.stderr` files under `wip/` with `TRYBUILD=overwrite`.
+#[cfg(feature = "trybuild")]
+pub fn assert_compile_fails(glob: &str) {
+    trybuild::TestCases::new().compile_fail(glob);
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&'static str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_panic_messages() {
+        assert!(Misuse::MissingLock
+            .matches("the lock of `hello` is not acquired. Try `mry::lock(hello)`."));
+        assert!(
+            Misuse::UnusedLock.matches("hello is locked but no used. Remove hello from mry::lock")
+        );
+        assert!(Misuse::MockNotFound.matches("mock not found for Cat::meow"));
+        assert!(Misuse::CalledMoreThanOnce.matches(
+            "Cat::meow was called more than once, but the value of returns_once is moved out by the first call"
+        ));
+        assert!(Misuse::OutOfSequence
+            .matches("Expected call #1 of the sequence to be open, but read was called"));
+        assert!(Misuse::OutOfSequence.matches("read was called after the end of the sequence"));
+        assert!(Misuse::Exhausted.matches(
+            "read ran out of the values of returns_each after 2 calls under the Panic policy"
+        ));
+        assert!(!Misuse::MockNotFound.matches("the lock of `hello` is not acquired."));
+    }
+
+    #[test]
+    fn passes_on_misuse() {
+        assert_misuse(Misuse::MockNotFound, || {
+            panic!("mock not found for {}", "Cat::meow")
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected MockNotFound misuse, but nothing panicked")]
+    fn fails_without_panic() {
+        assert_misuse(Misuse::MockNotFound, || ());
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected MockNotFound misuse, but it panicked with: other")]
+    fn fails_on_other_panic() {
+        assert_misuse(Misuse::MockNotFound, || panic!("other"));
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Ids of the mocks whose behavior is running, the innermost last
+    static RUNNING: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+pub(crate) fn new_id() -> usize {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The mock whose behavior is running, which causes the calls made now
+pub(crate) fn running() -> Option<usize> {
+    RUNNING.with(|running| running.borrow().last().copied())
+}
+
+/// Marks the mock as running its behavior until dropped
+pub(crate) struct Running;
+
+impl Running {
+    pub(crate) fn enter(id: usize) -> Self {
+        RUNNING.with(|running| running.borrow_mut().push(id));
+        Running
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        RUNNING.with(|running| running.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nests() {
+        assert_eq!(running(), None);
+        {
+            let _outer = Running::enter(1);
+            {
+                let _inner = Running::enter(2);
+                assert_eq!(running(), Some(2));
+            }
+            assert_eq!(running(), Some(1));
+        }
+        assert_eq!(running(), None);
+    }
+}
@@ -1,46 +1,260 @@
-use std::{ops::Deref, sync::Arc};
+use std::{fmt::Debug, ops::Deref, sync::Arc};
 
-use parking_lot::Mutex;
+use crate::sync::Mutex;
 
-use crate::{times::Times, Matcher};
+use crate::{
+    times::Times,
+    truncate::{debug, elide_repeats, with_object_limit},
+    Matcher,
+};
 
-pub struct Logs<I>(Vec<Arc<Mutex<I>>>);
+/// Storage of recorded calls of a mock
+///
+/// The default store is `Vec`, which keeps every call.
+/// A custom store can keep memory flat in long running tests, as long as `count` stays accurate
+/// for the count-based assertions such as `assert_called`.
+pub trait LogStore<I>: Send {
+    /// Records a call
+    fn push(&mut self, input: Arc<Mutex<I>>);
+
+    /// Counts the recorded calls which `matches` returns true for
+    fn count(&self, matches: &dyn Fn(&I) -> bool) -> usize;
+
+    /// Retained calls in the recorded order
+    fn iter(&self) -> Box<dyn Iterator<Item = &Arc<Mutex<I>>> + '_>;
+}
+
+impl<I: Send> LogStore<I> for Vec<Arc<Mutex<I>>> {
+    fn push(&mut self, input: Arc<Mutex<I>>) {
+        Vec::push(self, input);
+    }
+
+    fn count(&self, matches: &dyn Fn(&I) -> bool) -> usize {
+        <[_]>::iter(self).filter(|log| matches(&log.lock())).count()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Arc<Mutex<I>>> + '_> {
+        Box::new(<[_]>::iter(self))
+    }
+}
+
+pub struct Logs<I> {
+    store: Box<dyn LogStore<I>>,
+    /// Limit of the renderings of the calls, set by `Mry::set_debug_limit`
+    debug_limit: Option<usize>,
+    /// Calls counted without their arguments by `#[mry(move_args(unlogged))]`, which only
+    /// patterns of any arguments count
+    unlogged: usize,
+}
+
+impl<I: Send + 'static> Logs<I> {
+    pub(crate) fn new(store: impl LogStore<I> + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            debug_limit: None,
+            unlogged: 0,
+        }
+    }
+
+    pub fn filter_matches(&self, matcher: &Matcher<I>) -> Self {
+        Self {
+            store: Box::new(
+                self.store
+                    .iter()
+                    .filter(|log| matcher.matches(&log.lock()))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            ),
+            debug_limit: self.debug_limit,
+            unlogged: if matcher.is_any() { self.unlogged } else { 0 },
+        }
+    }
+
+    pub(crate) fn assert_never_called(&self, name: &str, matcher: &Matcher<I>)
+    where
+        I: Debug,
+    {
+        let count = self.count(matcher);
+        if count > 0 {
+            let calls = with_object_limit(self.debug_limit, || {
+                elide_repeats(
+                    self.filter_matches(matcher)
+                        .iter()
+                        .map(|input| debug(&*input)),
+                )
+            });
+            panic!(
+                "Expected {} never to be called with the pattern, but it was called {} times:{}",
+                name, count, calls,
+            );
+        }
+    }
+}
 
 impl<I: 'static> Logs<I> {
     pub(crate) fn push(&mut self, item: Arc<Mutex<I>>) {
-        self.0.push(item);
+        self.store.push(item);
     }
 
-    pub fn filter_matches(&self, matcher: &Matcher<I>) -> Self {
-        Self(
-            self.0
-                .iter()
-                .filter(|log| matcher.matches(&log.lock()))
-                .cloned()
-                .collect(),
-        )
+    /// Counts a call without recording its arguments
+    pub(crate) fn push_unlogged(&mut self) {
+        self.unlogged += 1;
     }
 
-    pub(crate) fn assert_called(&self, name: &str, matcher: &Matcher<I>, times: Times) -> Self {
-        let logs = self.filter_matches(matcher);
-        let actual = logs.0.len();
+    pub(crate) fn debug_limit(&self) -> Option<usize> {
+        self.debug_limit
+    }
+
+    pub(crate) fn set_debug_limit(&mut self, limit: Option<usize>) {
+        self.debug_limit = limit;
+    }
+
+    /// Counts the recorded calls matching the pattern
+    pub fn count(&self, matcher: &Matcher<I>) -> usize {
+        let unlogged = if matcher.is_any() { self.unlogged } else { 0 };
+        self.store.count(&|input| matcher.matches(input)) + unlogged
+    }
+
+    pub(crate) fn assert_called(
+        &self,
+        name: &'static str,
+        matcher: &Matcher<I>,
+        times: Times,
+    ) -> CalledLogs<I> {
+        let actual = self.count(matcher);
         if !times.contains(&actual) {
+            let pattern = matcher
+                .describe()
+                .map(|pattern| format!("\n    pattern: {}", pattern))
+                .unwrap_or_default();
+            let rendered: Vec<_> = with_object_limit(self.debug_limit, || {
+                self.iter()
+                    .filter_map(|input| matcher.render(&input))
+                    .collect()
+            });
+            let calls = if rendered.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "\n    calls:{}\n    histogram:{}",
+                    elide_repeats(rendered.iter().cloned()),
+                    histogram(rendered)
+                )
+            };
             panic!(
-                "Expected {} to be called {} times, but it was called {} times",
-                name, times, actual,
+                "Expected {} to be called {} times, but it was called {} times{}{}",
+                name, times, actual, pattern, calls,
             );
         }
-        logs
+        CalledLogs {
+            name,
+            debug_limit: self.debug_limit,
+            logs: self
+                .store
+                .iter()
+                .filter(|log| matcher.matches(&log.lock()))
+                .cloned()
+                .collect(),
+        }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = impl Deref<Target = I> + '_> {
-        self.0.iter().map(|log| log.lock())
+        self.store.iter().map(|log| log.lock())
+    }
+}
+
+/// Counts of the distinct renderings of the calls, in the order they are first called
+fn histogram(rendered: Vec<String>) -> String {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for call in rendered {
+        match counts.iter_mut().find(|(value, _)| *value == call) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((call, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(value, count)| format!("\n    {} → {}", value, count))
+        .collect()
+}
+
+/// Calls matching the pattern, returned by `assert_called` for further assertions
+pub struct CalledLogs<I> {
+    name: &'static str,
+    debug_limit: Option<usize>,
+    logs: Vec<Arc<Mutex<I>>>,
+}
+
+impl<I> CalledLogs<I> {
+    /// Assert the relation holds for every pair of consecutive calls, such as
+    /// `deltas(|a, b| b >= a)` for monotonic progress.
+    /// Panics with the first pair violating the relation
+    pub fn deltas(self, relation: impl Fn(&I, &I) -> bool) -> Self
+    where
+        I: Debug,
+    {
+        for (index, pair) in self.logs.windows(2).enumerate() {
+            let (before, after) = (pair[0].lock(), pair[1].lock());
+            if !relation(&before, &after) {
+                let (before, after) =
+                    with_object_limit(self.debug_limit, || (debug(&*before), debug(&*after)));
+                panic!(
+                    "Expected consecutive calls of {} to satisfy the relation, but call #{} {} and call #{} {} did not",
+                    self.name,
+                    index + 1,
+                    before,
+                    index + 2,
+                    after,
+                );
+            }
+        }
+        self
+    }
+
+    /// Counts the calls per distinct arguments, in the order they are first called
+    pub fn histogram(&self) -> Vec<(I, usize)>
+    where
+        I: PartialEq + Clone,
+    {
+        let mut histogram: Vec<(I, usize)> = Vec::new();
+        for log in &self.logs {
+            let input = log.lock();
+            match histogram.iter_mut().find(|(value, _)| *value == *input) {
+                Some((_, count)) => *count += 1,
+                None => histogram.push((input.clone(), 1)),
+            }
+        }
+        histogram
+    }
+
+    /// Assert each distinct arguments are called as expected times, such as `each(1)` for
+    /// "called once per distinct key".
+    /// Panics with the histogram of the calls if the count does not match
+    pub fn each(self, times: impl Into<Times>) -> Self
+    where
+        I: PartialEq + Clone + Debug,
+    {
+        let times = times.into();
+        let histogram = self.histogram();
+        if histogram.iter().any(|(_, count)| !times.contains(count)) {
+            let lines = with_object_limit(self.debug_limit, || {
+                histogram
+                    .iter()
+                    .map(|(value, count)| format!("\n    {} → {}", debug(value), count))
+                    .collect::<String>()
+            });
+            panic!(
+                "Expected {} to be called {} times for each arguments, but the calls were:{}",
+                self.name, times, lines,
+            );
+        }
+        self
     }
 }
 
-impl<I> Default for Logs<I> {
+impl<I: Send + 'static> Default for Logs<I> {
     fn default() -> Self {
-        Self(Default::default())
+        Self::new(Vec::new())
     }
 }
 
@@ -59,6 +273,105 @@ mod test {
         let matcher = Matcher::new_eq(2);
 
         let filtered = logs.filter_matches(&matcher);
-        assert_eq!(filtered.0.len(), 2);
+        assert_eq!(filtered.iter().count(), 2);
+        assert_eq!(logs.count(&matcher), 2);
+    }
+
+    #[test]
+    fn custom_store() {
+        /// Keeps only the number of calls
+        struct Counter(usize);
+
+        impl LogStore<u8> for Counter {
+            fn push(&mut self, _: Arc<Mutex<u8>>) {
+                self.0 += 1;
+            }
+
+            fn count(&self, _: &dyn Fn(&u8) -> bool) -> usize {
+                self.0
+            }
+
+            fn iter(&self) -> Box<dyn Iterator<Item = &Arc<Mutex<u8>>> + '_> {
+                Box::new(std::iter::empty())
+            }
+        }
+
+        let mut logs = Logs::new(Counter(0));
+        logs.push(Arc::new(Mutex::new(1)));
+        logs.push(Arc::new(Mutex::new(2)));
+
+        assert_eq!(logs.count(&Matcher::any()), 2);
+        assert_eq!(logs.iter().count(), 0);
+    }
+
+    #[test]
+    fn assert_never_called_passes() {
+        let mut logs = Logs::default();
+        logs.push(Arc::new(Mutex::new(1)));
+
+        logs.assert_never_called("a", &Matcher::new_eq(2));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected a never to be called with the pattern, but it was called 2 times:\n    2 ×2"
+    )]
+    fn assert_never_called_lists_calls() {
+        let mut logs = Logs::default();
+        logs.push(Arc::new(Mutex::new(1)));
+        logs.push(Arc::new(Mutex::new(2)));
+        logs.push(Arc::new(Mutex::new(2)));
+
+        logs.assert_never_called("a", &Matcher::new_eq(2));
+    }
+
+    #[test]
+    fn deltas_passes() {
+        let mut logs = Logs::default();
+        logs.push(Arc::new(Mutex::new(1)));
+        logs.push(Arc::new(Mutex::new(3)));
+        logs.push(Arc::new(Mutex::new(3)));
+
+        logs.assert_called("a", &Matcher::any(), 3.into())
+            .deltas(|a, b| b >= a);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected consecutive calls of a to satisfy the relation, but call #2 3 and call #3 2 did not"
+    )]
+    fn deltas_reports_violating_pair() {
+        let mut logs = Logs::default();
+        logs.push(Arc::new(Mutex::new(1)));
+        logs.push(Arc::new(Mutex::new(3)));
+        logs.push(Arc::new(Mutex::new(2)));
+
+        logs.assert_called("a", &Matcher::any(), 3.into())
+            .deltas(|a, b| b >= a);
+    }
+
+    #[test]
+    fn histogram() {
+        let mut logs = Logs::default();
+        logs.push(Arc::new(Mutex::new(2)));
+        logs.push(Arc::new(Mutex::new(1)));
+        logs.push(Arc::new(Mutex::new(2)));
+
+        let called = logs.assert_called("a", &Matcher::any(), 3.into());
+
+        assert_eq!(called.histogram(), vec![(2, 2), (1, 1)]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected a to be called 1 times for each arguments, but the calls were:\n    2 → 2\n    1 → 1"
+    )]
+    fn each_lists_histogram() {
+        let mut logs = Logs::default();
+        logs.push(Arc::new(Mutex::new(2)));
+        logs.push(Arc::new(Mutex::new(1)));
+        logs.push(Arc::new(Mutex::new(2)));
+
+        logs.assert_called("a", &Matcher::any(), 3.into()).each(1);
     }
 }
@@ -1,24 +1,101 @@
+mod cause;
 mod log;
-use std::{iter::repeat, sync::Arc};
+#[cfg(feature = "async")]
+mod notify;
+use std::{iter::repeat, sync::Arc, time::Duration};
 
 pub use log::*;
+#[cfg(feature = "async")]
+pub use notify::CallNotified;
 
-use parking_lot::Mutex;
+use crate::sync::Mutex;
 
-use crate::{times::Times, Behavior, Matcher, MockableRet, Output, Rule};
+use crate::{
+    duplicate::{duplicate_rule, DuplicateRule},
+    sequence::{advance, ObjectSequences, SequenceStep},
+    times::Times,
+    Behavior, Matcher, MockableRet, Output, Rule,
+};
+
+/// Side effect run on the calls matching the pattern, registered by `inspect`
+type Inspector<I> = (Arc<Mutex<Matcher<I>>>, Box<dyn FnMut(&I) + Send>);
 
 pub struct Mock<I, O> {
     pub name: &'static str,
     pub log: Logs<I>,
+    id: usize,
+    /// Calls made from behaviors of other mocks, with the id of the mock
+    callers: Vec<(usize, Arc<Mutex<I>>)>,
     rules: Vec<Rule<I, O>>,
+    /// Calls the real implementation when no rule matches, set by the locator of the matcher
+    fallback_real_impl: Option<Arc<Mutex<Matcher<I>>>>,
+    /// Scope of `mry::scope()` setting `fallback_real_impl`
+    fallback_scope: Option<usize>,
+    sequence_steps: Vec<SequenceStep<I>>,
+    /// Sequences of the object, checked on the calls even without steps of the mock
+    sequences: ObjectSequences,
+    #[cfg(feature = "async")]
+    notifications: notify::Notifications<I>,
+    /// Rules registered with the same pattern as earlier ones under `DuplicateRule::Warn`
+    warnings: Vec<String>,
+    /// Notes for reproducing the behaviors, such as the seeds of `returns_weighted`, shared with
+    /// behaviors noting what happened on calls
+    notes: Notes,
+    /// Number of the recorded calls, which does not depend on the log store
+    call_count: u64,
+    /// Wall-times of the real implementations, measured after `measure_durations`
+    durations: Option<crate::recursion::Durations>,
+    /// Token of the `#[mry::test]` creating the mock
+    token: Option<u64>,
+    /// Message of `panics_with` to panic with after recording the call
+    panic: Option<String>,
+    inspectors: Vec<Inspector<I>>,
 }
 
-impl<I, O> Mock<I, O> {
+/// Notes of a mock, shared with the behaviors noting on calls
+pub(crate) type Notes = Arc<Mutex<Vec<String>>>;
+
+impl<I: Send + 'static, O> Mock<I, O> {
+    /// Removes the rules and the recorded calls, keeping the sequences and the notifications.
+    /// Returns the removed ones for the caller to drop after unlocking the mock, as their `Drop`
+    /// impls may call the mock again
+    #[must_use]
+    pub(crate) fn reset(&mut self) -> impl Sized {
+        self.warnings.clear();
+        self.notes.lock().clear();
+        self.call_count = 0;
+        if self.durations.is_some() {
+            self.durations = Some(Default::default());
+        }
+        (
+            std::mem::take(&mut self.rules),
+            std::mem::take(&mut self.inspectors),
+            self.fallback_real_impl.take(),
+            std::mem::take(&mut self.log),
+            std::mem::take(&mut self.callers),
+        )
+    }
+
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
             log: Default::default(),
+            id: cause::new_id(),
+            callers: Default::default(),
             rules: Default::default(),
+            fallback_real_impl: None,
+            fallback_scope: None,
+            sequence_steps: Default::default(),
+            sequences: Default::default(),
+            #[cfg(feature = "async")]
+            notifications: Default::default(),
+            warnings: Default::default(),
+            notes: Default::default(),
+            call_count: 0,
+            durations: None,
+            token: crate::test_token::current(),
+            panic: None,
+            inspectors: Vec::new(),
         }
     }
 }
@@ -29,53 +106,380 @@ impl<I, O> Mock<I, O> {
         matcher: Arc<Mutex<Matcher<I>>>,
         behavior: Behavior<I, O>,
     ) {
-        self.rules.push(Rule { matcher, behavior });
+        self.push_rule(Rule::new(matcher, behavior));
     }
 
-    pub(crate) fn returns_once(&mut self, matcher: Arc<Mutex<Matcher<I>>>, ret: O) {
-        self.returns_with(matcher, Behavior::Once(Mutex::new(Some(ret))))
+    pub(crate) fn returns_once(&mut self, matcher: Arc<Mutex<Matcher<I>>>, ret: O)
+    where
+        O: Send + 'static,
+    {
+        self.returns_with(matcher, Behavior::Once(Mutex::new(Some(Box::new(|| ret)))))
+    }
+
+    pub(crate) fn inspect(
+        &mut self,
+        matcher: Arc<Mutex<Matcher<I>>>,
+        inspector: impl FnMut(&I) + Send + 'static,
+    ) {
+        self.inspectors.push((matcher, Box::new(inspector)));
+    }
+
+    pub(crate) fn panics_with(&mut self, matcher: Arc<Mutex<Matcher<I>>>, message: String) {
+        self.push_rule(Rule::new(matcher, Behavior::Panics(message)))
     }
 
     pub(crate) fn calls_real_impl(&mut self, matcher: Arc<Mutex<Matcher<I>>>) {
-        self.rules.push(Rule {
-            matcher,
-            behavior: Behavior::CallsRealImpl,
-        })
+        self.push_rule(Rule::new(matcher, Behavior::CallsRealImpl))
+    }
+
+    /// Adds the rule, dealing with earlier rules of the same pattern as set by
+    /// `set_duplicate_rule`. Rules of the same locator and lookups falling through are not
+    /// duplicates.
+    fn push_rule(&mut self, rule: Rule<I, O>) {
+        let duplicates = |existing: &Rule<I, O>| {
+            !Arc::ptr_eq(&existing.matcher, &rule.matcher)
+                && !matches!(existing.behavior, Behavior::Lookup(_))
+                && existing.matcher.lock().same_pattern(&rule.matcher.lock())
+        };
+        if self.rules.iter().any(duplicates) {
+            match duplicate_rule() {
+                DuplicateRule::Allow => {}
+                DuplicateRule::Warn => self.warnings.push(format!(
+                    "{} has a rule with the same pattern registered earlier, which shadows the new one",
+                    self.name
+                )),
+                DuplicateRule::Replace => self.rules.retain(|existing| !duplicates(existing)),
+                DuplicateRule::Panic => panic!(
+                    "{} already has a rule with the same pattern, which would shadow the new one",
+                    self.name
+                ),
+            }
+        }
+        self.rules.push(rule);
+    }
+
+    pub(crate) fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub(crate) fn note(&mut self, note: String) {
+        self.notes.lock().push(note);
+    }
+
+    pub(crate) fn notes(&self) -> Vec<String> {
+        self.notes.lock().clone()
+    }
+
+    /// Notes for behaviors to add to while the mock is locked by the call
+    pub(crate) fn shared_notes(&self) -> Notes {
+        self.notes.clone()
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn notified_on_call(&mut self, matcher: Arc<Mutex<Matcher<I>>>) -> CallNotified {
+        self.notifications.subscribe(matcher)
+    }
+
+    pub(crate) fn in_sequence(&mut self, step: SequenceStep<I>) {
+        self.sequence_steps.push(step);
+    }
+
+    pub(crate) fn share_sequences(&mut self, sequences: ObjectSequences) {
+        self.sequences = sequences;
+    }
+
+    /// Moves the rules of the matcher to the front so that they shadow the others
+    pub(crate) fn raise_rules(&mut self, matcher: &Arc<Mutex<Matcher<I>>>) {
+        let (mut raised, rest): (Vec<_>, Vec<_>) = self
+            .rules
+            .drain(..)
+            .partition(|rule| Arc::ptr_eq(&rule.matcher, matcher));
+        raised.extend(rest);
+        self.rules = raised;
+    }
+
+    pub(crate) fn remove_rules(&mut self, matcher: &Arc<Mutex<Matcher<I>>>) {
+        self.rules
+            .retain(|rule| !Arc::ptr_eq(&rule.matcher, matcher));
+        if let Some(fallback) = &self.fallback_real_impl {
+            if Arc::ptr_eq(fallback, matcher) {
+                self.fallback_real_impl = None;
+            }
+        }
+    }
+
+    pub(crate) fn otherwise_real(&mut self, matcher: Arc<Mutex<Matcher<I>>>) {
+        self.fallback_real_impl = Some(matcher);
+        self.fallback_scope = crate::scope::current();
+    }
+
+    /// Removes the rules configured in the scope of `mry::scope()`.
+    /// Returns them for the caller to drop after unlocking the mock
+    #[must_use]
+    pub(crate) fn remove_scope(&mut self, scope: usize) -> impl Sized {
+        let (removed, kept): (Vec<_>, Vec<_>) = self
+            .rules
+            .drain(..)
+            .partition(|rule| rule.scope == Some(scope));
+        self.rules = kept;
+        let fallback = if self.fallback_scope == Some(scope) {
+            self.fallback_scope = None;
+            self.fallback_real_impl.take()
+        } else {
+            None
+        };
+        (removed, fallback)
     }
 }
 
 impl<I: 'static, O> Mock<I, O> {
-    pub(crate) fn assert_called(&self, matcher: &Matcher<I>, times: Times) {
-        self.log.assert_called(self.name, matcher, times);
+    pub(crate) fn assert_called(&self, matcher: &Matcher<I>, times: Times) -> CalledLogs<I> {
+        self.log.assert_called(self.name, matcher, times)
+    }
+
+    pub(crate) fn assert_never_called(&self, matcher: &Matcher<I>)
+    where
+        I: std::fmt::Debug + Send,
+    {
+        self.log.assert_never_called(self.name, matcher);
+    }
+
+    pub(crate) fn assert_used(&self, matcher: &Arc<Mutex<Matcher<I>>>, times: Times) {
+        let actual = self.rule_hits(matcher);
+        if !times.contains(&actual) {
+            panic!(
+                "Expected the rule of {} to be used {} times, but it was used {} times",
+                self.name, times, actual,
+            );
+        }
+    }
+
+    pub(crate) fn calls(&self) -> usize {
+        self.call_count as usize
+    }
+
+    /// Number of the calls matching the pattern. Patterns of any arguments read the counter
+    /// instead of scanning the logs
+    pub(crate) fn call_count(&self, matcher: &Matcher<I>) -> u64 {
+        if matcher.is_any() {
+            self.call_count
+        } else {
+            self.log.count(matcher) as u64
+        }
+    }
+
+    pub(crate) fn rules(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub(crate) fn unused_rules(&self) -> usize {
+        self.rules.iter().filter(|rule| rule.hits == 0).count()
+    }
+
+    /// Sums the hits of the rules registered with the given matcher
+    pub(crate) fn rule_hits(&self, matcher: &Arc<Mutex<Matcher<I>>>) -> usize {
+        self.rules
+            .iter()
+            .filter(|rule| Arc::ptr_eq(&rule.matcher, matcher))
+            .map(|rule| rule.hits)
+            .sum()
+    }
+
+    pub(crate) fn set_log_store(&mut self, store: impl LogStore<I> + 'static)
+    where
+        I: Send,
+    {
+        let debug_limit = self.log.debug_limit();
+        self.log = Logs::new(store);
+        self.log.set_debug_limit(debug_limit);
     }
 
     pub(crate) fn record_call(&mut self, input: Arc<Mutex<I>>) {
+        self.call_count += 1;
         self.log.push(input);
     }
+
+    pub(crate) fn record_call_and_find_mock_output(&mut self, input: I) -> Option<O> {
+        let (caller, result) = self.find_mock_output_of_call(&input);
+        self.record_input(caller, input);
+        self.raise_panic();
+        result
+    }
+
+    /// Same as `record_call_and_find_mock_output`, but gives the input back for the real
+    /// implementation, which is the only case copying it into the log by `clone`. Without
+    /// `clone`, the call is only counted.
+    pub(crate) fn record_call_and_find_mock_output_or_input(
+        &mut self,
+        input: I,
+        clone: Option<fn(&I) -> I>,
+    ) -> Result<O, I> {
+        match self.find_mock_output_of_call(&input) {
+            (caller, Some(output)) => {
+                self.record_input(caller, input);
+                Ok(output)
+            }
+            (caller, None) => {
+                match clone {
+                    Some(clone) => self.record_input(caller, clone(&input)),
+                    None => self.count_call(&input),
+                }
+                self.raise_panic();
+                Err(input)
+            }
+        }
+    }
+
+    /// Panics with the message of `panics_with` found for the recorded call
+    fn raise_panic(&mut self) {
+        if let Some(message) = self.panic.take() {
+            panic!("{}", message);
+        }
+    }
+
+    /// Finds the output with the mock running, along with the mock whose behavior is calling it
+    fn find_mock_output_of_call(&mut self, input: &I) -> (Option<usize>, Option<O>) {
+        advance(&self.sequence_steps, &self.sequences, self.name, input);
+        crate::test_token::touch(self.token, || format!("the mock of {}", self.name));
+        let caller = cause::running();
+        let _running = cause::Running::enter(self.id);
+        for (matcher, inspector) in &mut self.inspectors {
+            if matcher.lock().matches(input) {
+                inspector(input);
+            }
+        }
+        (caller, self.find_mock_output(input))
+    }
+
+    fn record_input(&mut self, caller: Option<usize>, input: I) {
+        let input = Arc::new(Mutex::new(input));
+        if let Some(caller) = caller {
+            self.callers.push((caller, input.clone()));
+        }
+        #[cfg(feature = "async")]
+        self.notifications.notify(&input.lock());
+        self.record_call(input);
+    }
+
+    /// Counts the call without logging its arguments
+    fn count_call(&mut self, _input: &I) {
+        self.call_count += 1;
+        self.log.push_unlogged();
+        #[cfg(feature = "async")]
+        self.notifications.notify(_input);
+    }
+
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    pub(crate) fn token(&self) -> Option<u64> {
+        self.token
+    }
+
+    pub(crate) fn assert_caused_by(&self, cause: usize, cause_name: &str, matcher: &Matcher<I>) {
+        if !self
+            .callers
+            .iter()
+            .any(|(caller, input)| *caller == cause && matcher.matches(&input.lock()))
+        {
+            panic!(
+                "Expected {} to cause a call of {} matching the pattern, but it did not",
+                cause_name, self.name,
+            );
+        }
+    }
 }
 
 impl<I, O> Mock<I, O> {
+    /// Panics if a call of a static function matches a rule configured in another test, which
+    /// leaked from there because the function was not locked or the isolation id is shared.
+    /// Only checked if the calling test configured rules of its own, not to blame helper threads.
+    pub(crate) fn detect_poisoning(&self, input: &I) {
+        let thread = std::thread::current();
+        let Some(current) = thread.name() else {
+            return;
+        };
+        if !self
+            .rules
+            .iter()
+            .any(|rule| rule.test.as_deref() == Some(current))
+        {
+            return;
+        }
+        if let Some(test) = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(input))
+            .and_then(|rule| rule.test.as_deref())
+        {
+            if test != current {
+                panic!(
+                    "{} matched a rule configured in the test {}, but it is called in the test {}. The rule leaked from the other test; lock the function with mry::lock or isolate the tests with their own ids",
+                    self.name, test, current
+                );
+            }
+        }
+    }
+
     pub(crate) fn find_mock_output(&mut self, input: &I) -> Option<O> {
         for rule in &mut self.rules {
             if !rule.matches(input) {
                 continue;
             }
+            crate::test_token::touch(rule.token, || format!("a rule of {}", self.name));
             return match rule.call_behavior(input) {
                 Output::Found(output) => Some(output),
-                Output::CallsRealImpl => None,
+                Output::CallsRealImpl => self.pass_through(),
+                Output::NotFound => continue,
+                Output::Panics(message) => {
+                    self.panic = Some(message);
+                    None
+                }
+                Output::Awaits => None,
+                Output::ErrorNotAsync => {
+                    panic!(
+                        "{} is not an async method, but it has a behavior of returns_with_async",
+                        self.name
+                    )
+                }
                 Output::ErrorCalledOnce => {
-                    panic!("{} was called more than once", self.name)
+                    panic!(
+                        "{} was called more than once, but the value of returns_once is moved out by the first call. Use returns for Clone values or returns_each for several values",
+                        self.name
+                    )
                 }
             };
         }
+        if self.fallback_real_impl.is_some() {
+            return self.pass_through();
+        }
         panic!("mock not found for {}", self.name)
     }
+
+    /// Leaves the call to the real implementation, which counts the recursion through the mock
+    /// and measures it after `measure_durations`
+    fn pass_through(&self) -> Option<O> {
+        crate::recursion::pass_to_real_impl(self.name, self.durations.clone());
+        None
+    }
+
+    pub(crate) fn measure_durations(&mut self) {
+        self.durations.get_or_insert_with(Default::default);
+    }
+
+    pub(crate) fn durations(&self) -> Vec<Duration> {
+        self.durations
+            .as_ref()
+            .map(|durations| durations.lock().clone())
+            .unwrap_or_default()
+    }
 }
 
 impl<I, O> Mock<I, O>
 where
     I: 'static,
-    O: Clone + MockableRet,
+    O: Clone + Send + MockableRet,
 {
     pub(crate) fn returns(&mut self, matcher: Arc<Mutex<Matcher<I>>>, ret: O) {
         self.returns_with(matcher, Behavior::Const(Mutex::new(Box::new(repeat(ret)))))
@@ -85,7 +489,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Behavior1;
+    use crate::{ArgMatcher, Behavior1};
 
     #[test]
     fn returns_with() {
@@ -109,6 +513,41 @@ mod test {
         assert_eq!(mock.find_mock_output(&(3,)), "aaa".to_string().into());
     }
 
+    #[test]
+    #[should_panic(
+        expected = "a matched a rule configured in the test first test, but it is called in the test "
+    )]
+    fn detect_poisoning() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        std::thread::scope(|scope| {
+            std::thread::Builder::new()
+                .name("first test".into())
+                .spawn_scoped(scope, || {
+                    mock.returns(Matcher::any().wrapped(), "leaked".into())
+                })
+                .unwrap();
+        });
+        mock.returns(Matcher::any().wrapped(), "own".into());
+
+        mock.detect_poisoning(&(1,));
+    }
+
+    #[test]
+    fn poisoning_ignores_threads_without_rules() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        mock.returns(Matcher::any().wrapped(), "a".into());
+
+        std::thread::scope(|scope| {
+            std::thread::Builder::new()
+                .name("helper".into())
+                .spawn_scoped(scope, || {
+                    let mock = &mut mock;
+                    mock.detect_poisoning(&(1,))
+                })
+                .unwrap();
+        });
+    }
+
     #[test]
     #[should_panic(expected = "mock not found for a")]
     fn returns_with_never() {
@@ -157,6 +596,37 @@ mod test {
         assert_eq!(mock.find_mock_output(&(3,)), None);
     }
 
+    #[test]
+    fn otherwise_real() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let matcher = Arc::new(Mutex::new(Matcher::new_eq((3,))));
+        mock.returns(matcher.clone(), "stub".to_string());
+        mock.otherwise_real(matcher.clone());
+        mock.returns(Matcher::new_eq((4,)).wrapped(), "later".to_string());
+
+        assert_eq!(mock.find_mock_output(&(3,)), Some("stub".to_string()));
+        assert_eq!(mock.find_mock_output(&(4,)), Some("later".to_string()));
+        assert_eq!(mock.find_mock_output(&(5,)), None);
+
+        mock.remove_rules(&matcher);
+        assert_eq!(mock.find_mock_output(&(4,)), Some("later".to_string()));
+    }
+
+    #[test]
+    fn lookup_falls_through() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let lookup = Matcher::any().wrapped();
+        mock.returns_with(
+            lookup.clone(),
+            Behavior::Lookup(Box::new(|(a,)| (*a == 1).then(|| "one".to_string()))),
+        );
+        mock.returns(Matcher::any().wrapped(), "other".to_string());
+
+        assert_eq!(mock.find_mock_output(&(1,)), Some("one".to_string()));
+        assert_eq!(mock.find_mock_output(&(2,)), Some("other".to_string()));
+        assert_eq!(mock.rule_hits(&lookup), 1);
+    }
+
     #[test]
     #[should_panic(expected = "mock not found for a")]
     fn calls_real_impl_never() {
@@ -167,7 +637,59 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "a was called more than once")]
+    fn rule_hits_counts_each_rule() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let three = Matcher::new_eq((3,)).wrapped();
+        let any = Matcher::any().wrapped();
+        mock.returns(three.clone(), "three".into());
+        mock.returns(any.clone(), "any".into());
+
+        mock.find_mock_output(&(3,));
+        mock.find_mock_output(&(3,));
+        mock.find_mock_output(&(4,));
+
+        assert_eq!(mock.rule_hits(&three), 2);
+        assert_eq!(mock.rule_hits(&any), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "mock not found for a")]
+    fn remove_rules() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let matcher = Matcher::any().wrapped();
+        mock.returns(matcher.clone(), "a".into());
+        mock.remove_rules(&matcher);
+
+        mock.find_mock_output(&(3,));
+    }
+
+    #[test]
+    fn raise_rules() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let outer = Matcher::any().wrapped();
+        let inner = Matcher::any().wrapped();
+        mock.returns(outer.clone(), "outer".into());
+        mock.returns(inner.clone(), "inner".into());
+
+        mock.raise_rules(&inner);
+        assert_eq!(mock.find_mock_output(&(3,)), Some("inner".to_string()));
+
+        mock.remove_rules(&inner);
+        assert_eq!(mock.find_mock_output(&(3,)), Some("outer".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected the rule of a to be used 1 times, but it was used 0 times")]
+    fn assert_used_fails() {
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let matcher = Matcher::any().wrapped();
+        mock.returns(matcher.clone(), "a".into());
+
+        mock.assert_used(&matcher, 1.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "a was called more than once, but the value of returns_once")]
     fn panic_on_once_called_multiple_time() {
         let mut mock = Mock::<(usize,), String>::new("a");
         mock.returns_once(Matcher::any().wrapped(), "a".repeat(3));
@@ -175,4 +697,49 @@ mod test {
         mock.find_mock_output(&(3,));
         mock.find_mock_output(&(3,));
     }
+
+    #[test]
+    fn duplicate_rule_warns() {
+        crate::set_duplicate_rule(DuplicateRule::Warn);
+        let mut mock = Mock::<(usize,), String>::new("a");
+        mock.returns(Matcher::from((1.into(),)).wrapped(), "first".into());
+        mock.returns(Matcher::from((2.into(),)).wrapped(), "other".into());
+        mock.returns(Matcher::from((1.into(),)).wrapped(), "second".into());
+
+        assert_eq!(
+            mock.warnings(),
+            ["a has a rule with the same pattern registered earlier, which shadows the new one"]
+        );
+        assert_eq!(mock.find_mock_output(&(1,)), Some("first".to_string()));
+    }
+
+    #[test]
+    fn duplicate_rule_replaces() {
+        crate::set_duplicate_rule(DuplicateRule::Replace);
+        let mut mock = Mock::<(usize,), String>::new("a");
+        mock.returns(Matcher::any().wrapped(), "first".into());
+        mock.returns(Matcher::from((ArgMatcher::Any,)).wrapped(), "first".into());
+        mock.returns(Matcher::from((ArgMatcher::Any,)).wrapped(), "second".into());
+
+        assert_eq!(mock.find_mock_output(&(1,)), Some("first".to_string()));
+        assert_eq!(mock.rules.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "a already has a rule with the same pattern")]
+    fn duplicate_rule_panics() {
+        crate::set_duplicate_rule(DuplicateRule::Panic);
+        let mut mock = Mock::<(usize,), String>::new("a");
+        mock.returns(Matcher::from((ArgMatcher::Any,)).wrapped(), "first".into());
+        mock.returns(Matcher::from((ArgMatcher::Any,)).wrapped(), "second".into());
+    }
+
+    #[test]
+    fn predicates_are_not_duplicates() {
+        crate::set_duplicate_rule(DuplicateRule::Panic);
+        let mut mock = Mock::<(usize,), String>::new("a");
+        let large = || Matcher::from((ArgMatcher::Fn(Box::new(|a: &usize| *a > 2)),));
+        mock.returns(large().wrapped(), "first".into());
+        mock.returns(large().wrapped(), "second".into());
+    }
 }
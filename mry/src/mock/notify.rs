@@ -0,0 +1,89 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use crate::sync::Mutex;
+
+use crate::Matcher;
+
+#[derive(Default)]
+struct State {
+    called: bool,
+    waker: Option<Waker>,
+}
+
+/// Future resolved by the next call matching the pattern, returned by `notified_on_call`
+#[must_use = "futures do nothing unless awaited"]
+pub struct CallNotified(Arc<Mutex<State>>);
+
+impl Future for CallNotified {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.0.lock();
+        if state.called {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Pattern of a `CallNotified` and its state
+type Waiter<I> = (Arc<Mutex<Matcher<I>>>, Arc<Mutex<State>>);
+
+/// Waiters of `CallNotified`
+pub(crate) struct Notifications<I>(Vec<Waiter<I>>);
+
+impl<I> Default for Notifications<I> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<I> Notifications<I> {
+    pub(crate) fn subscribe(&mut self, matcher: Arc<Mutex<Matcher<I>>>) -> CallNotified {
+        let state = Arc::new(Mutex::new(State::default()));
+        self.0.push((matcher, state.clone()));
+        CallNotified(state)
+    }
+
+    /// Resolves and forgets the waiters the call matches
+    pub(crate) fn notify(&mut self, input: &I) {
+        self.0.retain(|(matcher, state)| {
+            if !matcher.lock().matches(input) {
+                return true;
+            }
+            let mut state = state.lock();
+            state.called = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+            false
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Waker;
+
+    use super::*;
+
+    #[test]
+    fn resolves_on_matching_call() {
+        let mut notifications = Notifications::<(usize,)>::default();
+        let mut notified = notifications.subscribe(Arc::new(Mutex::new(Matcher::new_eq((1,)))));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(Pin::new(&mut notified).poll(&mut cx).is_pending());
+        notifications.notify(&(2,));
+        assert!(Pin::new(&mut notified).poll(&mut cx).is_pending());
+        notifications.notify(&(1,));
+        assert!(Pin::new(&mut notified).poll(&mut cx).is_ready());
+    }
+}
@@ -1,20 +1,40 @@
+#[cfg(feature = "async")]
+mod sleep;
 pub mod times;
 
 use std::any::TypeId;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
 use std::sync::Arc;
+use std::time::Duration;
 
-use parking_lot::Mutex;
+use crate::sync::Mutex;
 
-use crate::{mockable::MockableRet, Behavior, Matcher, MockGetter};
+#[cfg(feature = "json")]
+use crate::contract::{ArgSpec, Contract};
+#[cfg(feature = "async")]
+use crate::mock::CallNotified;
+use crate::mock::{CalledLogs, LogStore};
+use crate::mockable::MockableArg;
+use crate::mocks::SharedMock;
+use crate::random::SplitMix64;
+use crate::sequence::SequenceStep;
+use crate::{
+    mockable::MockableRet, AnyMockHandle, ArgMatcher, Behavior, FailAction, Matcher, MockGetter,
+    Output, Sequence,
+};
 
 use self::times::Times;
 
 /// Mock locator returned by mock_* methods
 pub struct MockLocator<I, O, B> {
-    pub(crate) mocks: Arc<Mutex<dyn MockGetter<I, O>>>,
+    pub(crate) mocks: Arc<Mutex<dyn MockGetter<I, O> + Send>>,
     pub(crate) key: TypeId,
     pub(crate) name: &'static str,
+    /// Names of the parameters in the signature, rendered by `explain`
+    pub(crate) arg_names: &'static [&'static str],
     pub(crate) matcher: Arc<Mutex<Matcher<I>>>,
     #[allow(clippy::type_complexity)]
     _phantom: PhantomData<fn() -> (I, O, B)>,
@@ -23,24 +43,52 @@ pub struct MockLocator<I, O, B> {
 impl<I, O, B> MockLocator<I, O, B> {
     #[doc(hidden)]
     pub fn new(
-        mocks: Arc<Mutex<dyn MockGetter<I, O>>>,
+        mocks: Arc<Mutex<dyn MockGetter<I, O> + Send>>,
         key: TypeId,
         name: &'static str,
+        arg_names: &'static [&'static str],
         matcher: Matcher<I>,
     ) -> Self {
         Self {
             mocks,
             key,
             name,
+            arg_names,
             matcher: Arc::new(Mutex::new(matcher)),
             _phantom: Default::default(),
         }
     }
 }
 
+impl<I: 'static, O: 'static, B> MockLocator<I, O, B> {
+    fn mock(&self) -> SharedMock<I, O> {
+        let mock = self.mocks.lock().get_mut_or_create(self.key, self.name);
+        let token = mock.lock().token();
+        crate::test_token::touch(token, || format!("the mock of {}", self.name));
+        let scoped = mock.clone();
+        crate::scope::register(Arc::as_ptr(&mock) as usize, move |scope| {
+            let removed = scoped.lock().remove_scope(scope);
+            drop(removed);
+        });
+        mock
+    }
+}
+
+/// Rules raised by `within`, removed from the mock when dropped
+struct RaisedRules<I, O> {
+    mock: SharedMock<I, O>,
+    matcher: Arc<Mutex<Matcher<I>>>,
+}
+
+impl<I, O> Drop for RaisedRules<I, O> {
+    fn drop(&mut self) {
+        self.mock.lock().remove_rules(&self.matcher);
+    }
+}
+
 macro_rules! get_mut_or_default {
     ($self:ident) => {
-        $self.mocks.lock().get_mut_or_create($self.key, $self.name)
+        $self.mock().lock()
     };
 }
 
@@ -57,8 +105,34 @@ where
         self
     }
 
+    /// Same as `returns_with`, but the closure is called only once for each distinct arguments.
+    /// Later calls with equal arguments return a clone of the first output.
+    pub fn memoizes<T: Into<B>>(self, behavior: T) -> Self
+    where
+        I: Clone + Eq + Hash + Send,
+        O: Clone + Send,
+    {
+        let mut behavior: Behavior<I, O> = behavior.into().into();
+        let mut outputs = HashMap::new();
+        self.behaves(Behavior::FunctionRef(Box::new(move |input: &I| {
+            outputs
+                .entry(input.clone())
+                .or_insert_with(|| match behavior.called(input) {
+                    Output::Found(output) => output,
+                    _ => unreachable!("behaviors made of closures always return a value"),
+                })
+                .clone()
+        })))
+    }
+
     /// Returns value once. After that, it panics.
-    pub fn returns_once(self, ret: O) -> Self {
+    ///
+    /// This doesn't require `Clone`, so it can return move-only values such as `File` or the
+    /// sender of a channel.
+    pub fn returns_once(self, ret: O) -> Self
+    where
+        O: Send,
+    {
         get_mut_or_default!(self).returns_once(self.matcher.clone(), ret);
         self
     }
@@ -69,24 +143,500 @@ where
     I: 'static,
     O: 'static,
 {
+    pub(crate) fn behaves(self, behavior: Behavior<I, O>) -> Self {
+        get_mut_or_default!(self).returns_with(self.matcher.clone(), behavior);
+        self
+    }
+
+    /// Returns values received from the channel, waiting for the next one on each call,
+    /// so that a test or another thread can feed values while the code under test runs.
+    /// The mock is locked while a call waits, so other calls and assertions on it wait too.
+    /// Panics once the channel is closed and drained, or if no value comes within 10 seconds
+    pub fn returns_from_channel(self, receiver: Receiver<O>) -> Self
+    where
+        O: Send,
+    {
+        self.returns_from_channel_within(receiver, Duration::from_secs(10))
+    }
+
+    /// Same as `returns_from_channel`, but waits for each value up to the timeout
+    pub fn returns_from_channel_within(self, receiver: Receiver<O>, timeout: Duration) -> Self
+    where
+        O: Send,
+    {
+        let name = self.name;
+        self.behaves(Behavior::FunctionRef(Box::new(move |_| {
+            receiver.recv_timeout(timeout).unwrap_or_else(|err| match err {
+                RecvTimeoutError::Timeout => panic!(
+                    "{} waited {:?} for a value from the channel of returns_from_channel. The mock is locked while waiting, so send the value from another thread than the ones calling or asserting the mock",
+                    name, timeout
+                ),
+                RecvTimeoutError::Disconnected => panic!("the channel of {} is closed", name),
+            })
+        })))
+    }
+
+    /// Returns the values picked pseudo-randomly by the weights, such as
+    /// `returns_weighted([(0.9, Ok(v)), (0.1, Err(e))], 42)` for chaos testing of retries.
+    /// The same seed picks the same values in the same order, and the seed is noted in
+    /// `Harness::journal` to reproduce failures.
+    /// Panics if a weight is negative or the weights are not positive in total
+    pub fn returns_weighted(self, weights: impl IntoIterator<Item = (f64, O)>, seed: u64) -> Self
+    where
+        O: Clone + Send,
+    {
+        let weights: Vec<(f64, O)> = weights.into_iter().collect();
+        let total: f64 = weights.iter().map(|(weight, _)| weight).sum();
+        let valid = weights
+            .iter()
+            .all(|(weight, _)| weight.is_finite() && *weight >= 0.0)
+            && total > 0.0;
+        if !valid {
+            panic!(
+                "the weights of returns_weighted of {} must not be negative and must be positive in total",
+                self.name
+            );
+        }
+        get_mut_or_default!(self).note(format!(
+            "{} picks weighted values with seed {}",
+            self.name, seed
+        ));
+        let mut random = SplitMix64::new(seed);
+        self.behaves(Behavior::FunctionRef(Box::new(move |_| {
+            let mut point = random.next_f64() * total;
+            for (weight, value) in &weights {
+                if point < *weight {
+                    return value.clone();
+                }
+                point -= weight;
+            }
+            // Rounding errors of the sum
+            let (_, value) = weights
+                .iter()
+                .rev()
+                .find(|(weight, _)| *weight > 0.0)
+                .unwrap();
+            value.clone()
+        })))
+    }
+
+    /// Lets the named failpoint control the rule, so that failures can be toggled at runtime by
+    /// `mry::set_failpoint` or `MRY_FAILPOINTS="name=return(arg)"` without changing the stubs.
+    /// While the failpoint returns, the closure makes the output from the argument of
+    /// `return(...)` if any. While it panics, the mock panics. While it is off, calls fall through
+    /// to the rules set after this one.
+    pub fn on_failpoint(
+        self,
+        name: &'static str,
+        mut behavior: impl FnMut(Option<String>) -> O + Send + 'static,
+    ) -> Self {
+        self.behaves(Behavior::Lookup(Box::new(
+            move |_| match crate::failpoint::action(name) {
+                FailAction::Off => None,
+                FailAction::Return(arg) => Some(behavior(arg)),
+                FailAction::Panic(message) => {
+                    panic!(
+                        "{}",
+                        message.unwrap_or_else(|| format!("failpoint {} panicked", name))
+                    )
+                }
+            },
+        )))
+    }
+
     /// This make the mock calls real impl. This is used for partial mocking.
     pub fn calls_real_impl(self) -> Self {
         get_mut_or_default!(self).calls_real_impl(self.matcher.clone());
         self
     }
 
+    /// Runs the closure with the arguments of each call matching the pattern, whichever behavior
+    /// serves the call, for assertions or sending the arguments to a channel, such as
+    /// `mock_send(mry::Any).inspect(move |(message,)| tx.send(message.clone()).unwrap())`.
+    /// The closure must not call the same method.
+    pub fn inspect(self, inspector: impl FnMut(&I) + Send + 'static) -> Self {
+        get_mut_or_default!(self).inspect(self.matcher.clone(), inspector);
+        self
+    }
+
+    /// Panics with the message in the mocked method for the matching calls, after recording them,
+    /// for testing how callers handle and unwind from panics of the collaborator.
+    pub fn panics_with(self, message: impl Into<String>) -> Self {
+        get_mut_or_default!(self).panics_with(self.matcher.clone(), message.into());
+        self
+    }
+
+    /// Returns the value once, and calls the real implementation after that, such as
+    /// `mock_refresh(mry::Any).returns_once_then_real(token)` for a warm-up call.
+    pub fn returns_once_then_real(self, ret: O) -> Self
+    where
+        O: Send,
+    {
+        let mut ret = Some(ret);
+        self.behaves(Behavior::Lookup(Box::new(move |_| ret.take())))
+            .calls_real_impl()
+    }
+
+    /// Calls the real implementation for the arguments no rule matches, such as
+    /// `mock_get(42).returns(user).otherwise_real()`.
+    /// Unlike `mock_get(mry::Any).calls_real_impl()`, this stays behind the rules set later.
+    pub fn otherwise_real(self) -> Self {
+        get_mut_or_default!(self).otherwise_real(self.matcher.clone());
+        self
+    }
+
+    /// Explains whether and why the pattern matches the given arguments.
+    /// Useful for debugging complex patterns.
+    pub fn explain(&self, input: I) -> String
+    where
+        I: std::fmt::Debug,
+    {
+        let debug_limit = get_mut_or_default!(self).log.debug_limit();
+        crate::truncate::with_object_limit(debug_limit, || {
+            let matcher = self.matcher.lock();
+            let result = if matcher.matches(&input) {
+                "matches"
+            } else {
+                "does not match"
+            };
+            let reasons: String = matcher
+                .explain(&input)
+                .into_iter()
+                .enumerate()
+                .map(|(index, reason)| {
+                    let position = format!("argument #{}:", index + 1);
+                    match (reason.strip_prefix(&position), self.arg_names.get(index)) {
+                        (Some(reason), Some(name)) => {
+                            format!("\n    argument `{}`:{}", name, reason)
+                        }
+                        _ => format!("\n    {}", reason),
+                    }
+                })
+                .collect();
+            format!(
+                "{}{} {} the pattern{}",
+                self.name,
+                crate::truncate::debug(&input),
+                result,
+                reasons
+            )
+        })
+    }
+
+    /// Replaces the store of the recorded calls of the method.
+    /// Calls recorded before are discarded.
+    pub fn log_store(self, store: impl LogStore<I> + 'static) -> Self
+    where
+        I: Send,
+    {
+        get_mut_or_default!(self).set_log_store(store);
+        self
+    }
+
+    /// Appends the pattern to the sequence as the next expected call. Until the sequence ends,
+    /// calls of the other methods of the object fail as well.
+    /// Usually called through `mry::sequence!`.
+    pub fn in_sequence(self, sequence: &Sequence) -> Self {
+        let (state, index) = sequence.push_step(self.name);
+        self.mocks.lock().join_sequence(sequence);
+        get_mut_or_default!(self).in_sequence(SequenceStep {
+            state,
+            index,
+            matcher: self.matcher.clone(),
+        });
+        self
+    }
+
+    /// Tags the rules set through this locator with the label, expecting them to be used.
+    /// `mry::verify_group(label)` verifies every expectation of the label at once, and reports the
+    /// failures together.
+    pub fn group(self, label: impl Into<String>) -> Self {
+        let mock = self.mock();
+        let matcher = self.matcher.clone();
+        let name = self.name;
+        crate::group::push(
+            label.into(),
+            Box::new(move || {
+                (mock.lock().rule_hits(&matcher) == 0)
+                    .then(|| format!("the rule of {} was never used", name))
+            }),
+        );
+        self
+    }
+
+    /// Runs the closure and removes the rules set through this locator after that.
+    /// While running, the rules shadow the other rules of the method, and nested `within` shadows
+    /// the outer one. This keeps the stubs from leaking into the rest of the test.
+    pub fn within<T>(self, f: impl FnOnce() -> T) -> T {
+        let _rules = self.raise_rules();
+        f()
+    }
+
+    /// Same as `within`, but asserts the rules served the calls as `assert_used` before removing them.
+    pub fn within_used<T>(self, times: impl Into<Times>, f: impl FnOnce() -> T) -> T {
+        let _rules = self.raise_rules();
+        let ret = f();
+        self.assert_used(times);
+        ret
+    }
+
+    /// Raises the rules set through this locator until the returned guard drops, which removes
+    /// them even if the closure of `within` panics
+    fn raise_rules(&self) -> RaisedRules<I, O> {
+        let mock = self.mock();
+        mock.lock().raise_rules(&self.matcher);
+        RaisedRules {
+            mock,
+            matcher: self.matcher.clone(),
+        }
+    }
+
+    /// Number of the calls matching the pattern.
+    /// With `mry::Any` for every argument, reads a counter of the method instead of scanning the
+    /// recorded calls, for mocks called millions of times
+    pub fn call_count(&self) -> u64 {
+        get_mut_or_default!(self).call_count(&self.matcher.lock())
+    }
+
+    /// Measures the wall-time of each call passed to the real implementation from now on, by
+    /// `calls_real_impl` or `otherwise_real`, to read by `durations`. Calls of async methods are
+    /// not measured.
+    pub fn measure_durations(self) -> Self {
+        get_mut_or_default!(self).measure_durations();
+        self
+    }
+
+    /// Wall-times of the real implementations of the method in the order of the calls, measured
+    /// after `measure_durations`
+    pub fn durations(&self) -> Vec<Duration> {
+        get_mut_or_default!(self).durations()
+    }
+
     /// Assert the mock is called.
-    /// Returns `MockResult` allows to call `times(n)`
+    /// Returns `CalledLogs` allows to call `deltas(|a, b| b >= a)`
     /// Panics if not called
-    pub fn assert_called(&self, times: impl Into<Times>) {
-        get_mut_or_default!(self).assert_called(&self.matcher.lock(), times.into());
+    pub fn assert_called(&self, times: impl Into<Times>) -> CalledLogs<I> {
+        get_mut_or_default!(self).assert_called(&self.matcher.lock(), times.into())
+    }
+
+    /// Same as `assert_called`, but waits until the deadline for calls made by spawned tasks or
+    /// threads, checking the calls every millisecond. Works on any async runtime.
+    /// Panics if the calls do not satisfy `times` within the timeout
+    #[cfg(feature = "async")]
+    pub async fn assert_called_eventually(
+        &self,
+        times: impl Into<Times>,
+        timeout: Duration,
+    ) -> CalledLogs<I> {
+        let times = times.into();
+        let deadline = crate::clock::now() + timeout;
+        loop {
+            let actual = get_mut_or_default!(self).log.count(&self.matcher.lock());
+            if times.contains(&actual) {
+                return self.assert_called(times);
+            }
+            if crate::clock::now() >= deadline {
+                panic!(
+                    "Expected {} to be called {} times within {:?}, but it was called {} times",
+                    self.name, times, timeout, actual
+                );
+            }
+            self::sleep::pause(
+                deadline
+                    .saturating_duration_since(crate::clock::now())
+                    .min(Duration::from_millis(1)),
+            )
+            .await;
+        }
+    }
+
+    /// Type-erased handle of the mocked method, to keep mocks of any signatures together
+    pub fn handle(&self) -> AnyMockHandle
+    where
+        I: MockableArg,
+        O: MockableRet,
+    {
+        AnyMockHandle::new(Box::new(self.mock()))
+    }
+
+    /// Returns a future resolved by the next call matching the pattern, so async tests can wait for
+    /// the background activity of the unit precisely instead of polling.
+    /// Calls made before this are not counted
+    #[cfg(feature = "async")]
+    pub fn notified_on_call(&self) -> CallNotified {
+        get_mut_or_default!(self).notified_on_call(self.matcher.clone())
+    }
+
+    /// Assert no call matching the pattern is recorded.
+    /// Calls with other arguments are allowed.
+    /// Panics with the list of matched calls if any
+    pub fn assert_never_called(&self)
+    where
+        I: std::fmt::Debug + Send,
+    {
+        get_mut_or_default!(self).assert_never_called(&self.matcher.lock());
+    }
+
+    /// Assert the rules set through this locator served the calls.
+    /// Unlike `assert_called`, calls served by other rules are not counted
+    /// even if they match the same pattern.
+    /// Panics if the count does not match
+    pub fn assert_used(&self, times: impl Into<Times>) {
+        get_mut_or_default!(self).assert_used(&self.matcher, times.into());
+    }
+}
+
+/// Assert a behavior of the `cause` method called the `effect` method with the pattern.
+/// Patterns of `cause` are not considered.
+pub fn assert_caused<I1, O1, B1, I2, O2, B2>(
+    cause: &MockLocator<I1, O1, B1>,
+    effect: &MockLocator<I2, O2, B2>,
+) where
+    I1: 'static,
+    O1: 'static,
+    I2: 'static,
+    O2: 'static,
+{
+    let cause_id = get_mut_or_default!(cause).id();
+    get_mut_or_default!(effect).assert_caused_by(cause_id, cause.name, &effect.matcher.lock());
+}
+
+impl<I, O, B> MockLocator<I, O, B>
+where
+    I: Clone + 'static,
+    O: 'static,
+{
+    /// Returns the arguments as they are, as a tuple.
+    pub fn identity(self) -> Self
+    where
+        I: Into<O>,
+    {
+        get_mut_or_default!(self).returns_with(
+            self.matcher.clone(),
+            Behavior::Function {
+                clone: Clone::clone,
+                call: Box::new(Into::into),
+            },
+        );
+        self
+    }
+}
+
+#[cfg(feature = "json")]
+impl<I, O, B> MockLocator<I, O, B>
+where
+    I: serde::Serialize + 'static,
+    O: serde::de::DeserializeOwned + Clone + Send + 'static,
+{
+    /// Returns the values of the stubs of the method in the contract, such as
+    /// `returns_contract(&contract, "Payments::charge")`. Arguments no stub matches fall through
+    /// to the rules set after this one.
+    ///
+    /// Panics if the contract has no stubs of the method or a stub returns another type.
+    pub fn returns_contract(self, contract: &Contract, method: &str) -> Self {
+        let stubs: Vec<(Vec<ArgSpec>, O)> = contract
+            .stubs()
+            .iter()
+            .filter(|stub| stub.method == method)
+            .map(|stub| {
+                let output = serde_json::from_value(stub.returns.clone()).unwrap_or_else(|error| {
+                    panic!(
+                        "the stub of {} in the contract does not return {}: {}",
+                        method,
+                        std::any::type_name::<O>(),
+                        error
+                    )
+                });
+                (stub.args.clone(), output)
+            })
+            .collect();
+        if stubs.is_empty() {
+            panic!("the contract has no stubs of {}", method);
+        }
+        self.behaves(Behavior::Lookup(Box::new(move |input| {
+            let args = match serde_json::to_value(input).ok()? {
+                // Methods without arguments take `()`
+                serde_json::Value::Null => Vec::new(),
+                serde_json::Value::Array(args) => args,
+                _ => return None,
+            };
+            stubs
+                .iter()
+                .find(|(specs, _)| {
+                    specs.len() == args.len()
+                        && specs.iter().zip(&args).all(|(spec, arg)| spec.matches(arg))
+                })
+                .map(|(_, output)| output.clone())
+        })))
+    }
+}
+
+impl<A, O, B> MockLocator<(A,), O, B>
+where
+    A: Clone + 'static,
+    O: 'static,
+{
+    /// Returns the value of the argument in the map, such as `returns_map([("apple".into(), 100)])`.
+    /// Arguments not in the map fall through to the rules set after this one.
+    pub fn returns_map(self, map: impl IntoIterator<Item = (A, O)>) -> Self
+    where
+        A: Eq + Hash + Send,
+        O: Clone + Send,
+    {
+        let map: HashMap<A, O> = map.into_iter().collect();
+        self.behaves(Behavior::Lookup(Box::new(move |(arg,)| {
+            map.get(arg).cloned()
+        })))
+    }
+
+    /// Returns the value of the first pair whose matcher matches the argument, such as
+    /// `returns_for([("apple".into(), 100), (mry::Any, 0)])`.
+    /// Arguments no matcher matches fall through to the rules set after this one.
+    pub fn returns_for<M: Into<ArgMatcher<A>>>(
+        self,
+        pairs: impl IntoIterator<Item = (M, O)>,
+    ) -> Self
+    where
+        A: Send,
+        O: Clone + Send,
+    {
+        let pairs: Vec<(ArgMatcher<A>, O)> = pairs
+            .into_iter()
+            .map(|(matcher, value)| (matcher.into(), value))
+            .collect();
+        self.behaves(Behavior::Lookup(Box::new(move |(arg,)| {
+            pairs
+                .iter()
+                .find(|(matcher, _)| matcher.matches(arg))
+                .map(|(_, value)| value.clone())
+        })))
+    }
+
+    /// Returns the single argument of the method.
+    pub fn echoes(self) -> Self
+    where
+        A: Into<O>,
+    {
+        self.echoes_field(Into::into)
+    }
+
+    /// Returns a part of the single argument of the method, such as `echoes_field(|user| user.id)`.
+    pub fn echoes_field(self, mut field: impl FnMut(A) -> O + Send + 'static) -> Self {
+        get_mut_or_default!(self).returns_with(
+            self.matcher.clone(),
+            Behavior::Function {
+                clone: Clone::clone,
+                call: Box::new(move |(arg,)| field(arg)),
+            },
+        );
+        self
     }
 }
 
 impl<I, O, B> MockLocator<I, O, B>
 where
     I: 'static,
-    O: Clone + MockableRet,
+    O: Clone + Send + MockableRet,
 {
     /// This makes the mock returns the given constant value.
     /// This requires `Clone`. For returning not clone value, use `returns_once`.
@@ -94,4 +644,133 @@ where
         get_mut_or_default!(self).returns(self.matcher.clone(), ret);
         self
     }
+
+    /// Same as `returns`, but the mocked async method sleeps for the duration before returning
+    /// the value, to test timeouts and races. The delay follows `tokio::time::pause` with the
+    /// `tokio` feature.
+    /// Panics if the method is not async
+    #[cfg(feature = "async")]
+    pub fn returns_after(self, duration: Duration, ret: O) -> Self {
+        self.behaves(Behavior::Async(Box::new(move |_| {
+            let delay = self::sleep::delay(duration);
+            let ret = ret.clone();
+            crate::async_output::stash(Box::pin(async move {
+                delay.await;
+                ret
+            }))
+        })))
+    }
+
+    /// Returns the values one by one, and follows the policy after running out of them.
+    /// The first call after running out notes the policy in `Harness::journal()`.
+    pub fn returns_each(
+        self,
+        values: impl IntoIterator<Item = O>,
+        when_exhausted: Exhausted<O>,
+    ) -> Self {
+        let name = self.name;
+        let notes = get_mut_or_default!(self).shared_notes();
+        let mut values: VecDeque<O> = values.into_iter().collect();
+        let mut last = None;
+        let mut served = 0;
+        let mut noted = false;
+        self.behaves(Behavior::FunctionRef(Box::new(move |_| {
+            if let Some(value) = values.pop_front() {
+                served += 1;
+                last = Some(value.clone());
+                return value;
+            }
+            if !std::mem::replace(&mut noted, true) {
+                notes.lock().push(format!(
+                    "{} ran out of the values of returns_each after {} calls and follows the {:?} policy",
+                    name, served, when_exhausted
+                ));
+            }
+            match (&when_exhausted, &last) {
+                (Exhausted::Return(sentinel), _) => sentinel.clone(),
+                (Exhausted::RepeatLast, Some(last)) => last.clone(),
+                (policy, _) => panic!(
+                    "{} ran out of the values of returns_each after {} calls under the {:?} policy",
+                    name, served, policy
+                ),
+            }
+        })))
+    }
+}
+
+impl<I, O, B> MockLocator<I, O, B>
+where
+    I: 'static,
+    O: Default + MockableRet,
+{
+    /// Returns `Default::default()` always, for calls whose return value doesn't matter to the
+    /// test. It doesn't require `Clone`.
+    pub fn returns_default(self) -> Self {
+        self.behaves(Behavior::FunctionRef(Box::new(|_| O::default())))
+    }
+}
+
+impl<I, T, E, B> MockLocator<I, Result<T, E>, B>
+where
+    I: 'static,
+    T: Clone + Send + 'static,
+    E: Send + 'static,
+{
+    /// Returns `Ok` of the value always, without requiring `Clone` of the error type
+    pub fn returns_ok(self, value: T) -> Self {
+        self.behaves(Behavior::FunctionRef(Box::new(move |_| Ok(value.clone()))))
+    }
+}
+
+impl<I, T, E, B> MockLocator<I, Result<T, E>, B>
+where
+    I: 'static,
+    T: Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Returns `Err` of the error always, without requiring `Clone` of the value type
+    pub fn returns_err(self, error: E) -> Self {
+        self.behaves(Behavior::FunctionRef(Box::new(move |_| Err(error.clone()))))
+    }
+}
+
+impl<I, T, B> MockLocator<I, Option<T>, B>
+where
+    I: 'static,
+    T: Send + 'static,
+{
+    /// Returns `Some` of the value always
+    pub fn returns_some(self, value: T) -> Self
+    where
+        T: Clone,
+    {
+        self.behaves(Behavior::FunctionRef(Box::new(move |_| {
+            Some(value.clone())
+        })))
+    }
+
+    /// Returns `None` always, without requiring `Clone` of the value type
+    pub fn returns_none(self) -> Self {
+        self.behaves(Behavior::FunctionRef(Box::new(|_| None)))
+    }
+}
+
+/// What `returns_each` returns after running out of the values
+pub enum Exhausted<O> {
+    /// Panics, which `mry::assert_misuse(mry::Misuse::Exhausted, ...)` detects
+    Panic,
+    /// Repeats the last value, or panics if there were no values
+    RepeatLast,
+    /// Returns the sentinel value
+    Return(O),
+}
+
+impl<O> std::fmt::Debug for Exhausted<O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exhausted::Panic => f.write_str("Panic"),
+            Exhausted::RepeatLast => f.write_str("RepeatLast"),
+            Exhausted::Return(_) => f.write_str("Return"),
+        }
+    }
 }
@@ -0,0 +1,149 @@
+use std::time::Duration;
+#[cfg(not(feature = "sim"))]
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::OnceLock,
+    task::{Context, Poll, Waker},
+    time::Instant,
+};
+
+#[cfg(not(feature = "sim"))]
+use crate::sync::{Condvar, Mutex};
+use crate::BoxFuture;
+
+/// Runtime-agnostic sleep, which is woken by the timer thread of mry not to depend on a timer of
+/// any async runtime
+#[cfg(not(feature = "sim"))]
+pub(crate) struct Sleep {
+    deadline: Instant,
+}
+
+#[cfg(not(feature = "sim"))]
+impl Sleep {
+    pub(crate) fn until(deadline: Instant) -> Self {
+        Self { deadline }
+    }
+}
+
+#[cfg(not(feature = "sim"))]
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if crate::clock::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        timer().wake_at(self.deadline, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Single thread waking the sleeps of all tasks at their deadlines
+#[cfg(not(feature = "sim"))]
+struct Timer {
+    wakers: Mutex<Vec<(Instant, Waker)>>,
+    changed: Condvar,
+}
+
+#[cfg(not(feature = "sim"))]
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<&'static Timer> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        let timer: &'static Timer = Box::leak(Box::new(Timer {
+            wakers: Mutex::new(Vec::new()),
+            changed: Condvar::new(),
+        }));
+        std::thread::Builder::new()
+            .name("mry-timer".into())
+            .spawn(move || timer.run())
+            .expect("failed to spawn the timer thread of mry");
+        timer
+    })
+}
+
+#[cfg(not(feature = "sim"))]
+impl Timer {
+    fn wake_at(&self, deadline: Instant, waker: Waker) {
+        let mut wakers = self.wakers.lock();
+        if let Some((_, registered)) = wakers
+            .iter_mut()
+            .find(|(at, registered)| *at == deadline && registered.will_wake(&waker))
+        {
+            *registered = waker;
+            return;
+        }
+        wakers.push((deadline, waker));
+        self.changed.notify_one();
+    }
+
+    fn run(&self) {
+        let mut wakers = self.wakers.lock();
+        loop {
+            let now = crate::clock::now();
+            wakers.retain(|(deadline, waker)| {
+                if *deadline <= now {
+                    waker.wake_by_ref();
+                    false
+                } else {
+                    true
+                }
+            });
+            match wakers.iter().map(|(deadline, _)| *deadline).min() {
+                // Waits the remaining time of the clock, which may not be the system one
+                Some(next) => {
+                    self.changed.wait_for(&mut wakers, next - now);
+                }
+                None => self.changed.wait(&mut wakers),
+            }
+        }
+    }
+}
+
+/// Sleeps for the duration from now, on the sleep of the clock if it has one, on the timer of
+/// tokio if the task runs on it so `tokio::time::pause` advances the delay, or on `Sleep` otherwise
+pub(crate) fn delay(duration: Duration) -> BoxFuture<()> {
+    if let Some(sleep) = crate::clock::sleep(duration) {
+        return sleep;
+    }
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Box::pin(tokio::time::sleep(duration));
+    }
+    sleep_on_timer(duration)
+}
+
+/// Sleeps for the duration from now, on the sleep of the clock if it has one, or on `Sleep`
+/// otherwise
+pub(crate) fn pause(duration: Duration) -> BoxFuture<()> {
+    crate::clock::sleep(duration).unwrap_or_else(|| sleep_on_timer(duration))
+}
+
+#[cfg(not(feature = "sim"))]
+fn sleep_on_timer(duration: Duration) -> BoxFuture<()> {
+    Box::pin(Sleep::until(crate::clock::now() + duration))
+}
+
+#[cfg(feature = "sim")]
+fn sleep_on_timer(_duration: Duration) -> BoxFuture<()> {
+    panic!(
+        "mry has no timer thread with the sim feature, so its waits need a clock with a sleep, such as one returning madsim::time::sleep, set by mry::set_clock"
+    )
+}
+
+#[cfg(all(test, not(feature = "sim")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wakes_sleeps_from_one_thread() {
+        let start = Instant::now();
+        async_std::task::block_on(async {
+            for millis in [3, 1, 2] {
+                Sleep::until(start + Duration::from_millis(millis)).await;
+            }
+        });
+
+        assert!(start.elapsed() >= Duration::from_millis(3));
+    }
+}
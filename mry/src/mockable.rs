@@ -5,14 +5,40 @@
 pub trait MockableArg: Send + 'static {}
 
 #[diagnostic::on_unimplemented(
-    message = "`{Self}` is not mockable output because it is not `Send + 'static`",
+    message = "`{Self}` is not mockable output because it is not `'static`",
     // note = "If you don't need to mock this argument, you can add it to the skip list: `#[mry::mry(skip({Self}))]`"
 )]
-pub trait MockableRet: Send + 'static {}
+pub trait MockableRet: 'static {}
 
 impl<T: Send + 'static> MockableArg for T {}
 
-impl<T: Send + 'static> MockableRet for T {}
+impl<T: 'static> MockableRet for T {}
+
+/// Implemented by nothing, so `mock_*` of a method skipped by `#[mry(skip)]` fails to compile with
+/// the reason.
+///
+/// ```compile_fail
+/// struct Cat {
+///     mry: mry::Mry,
+/// }
+///
+/// #[mry::mry]
+/// impl Cat {
+///     #[mry(skip)]
+///     fn meow(&self) -> String {
+///         "meow".into()
+///     }
+/// }
+///
+/// let mut cat = Cat { mry: Default::default() };
+/// cat.mock_meow().returns("Called".into());
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not mockable because it is skipped by `#[mry(skip)]`",
+    label = "remove `#[mry(skip)]` from `{Self}` to mock it"
+)]
+#[doc(hidden)]
+pub trait MockableMethod {}
 
 pub fn assert_mockable<T: MockableArg>(arg: T) -> T {
     arg
@@ -3,82 +3,241 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
-use parking_lot::Mutex;
+use crate::sync::Mutex;
 
 use crate::mock::Mock;
 use crate::mockable::{MockableArg, MockableRet};
+use crate::sequence::ObjectSequences;
+use crate::{Matcher, Sequence};
 
-type BoxAnySend = Box<dyn Any + Send>;
+/// Mock of any argument and output types, for operations over every mock of an object
+pub(crate) trait AnyMock: Send {
+    fn as_any(&self) -> &dyn Any;
+    fn name(&self) -> &'static str;
+    /// Number of the recorded calls
+    fn calls(&self) -> usize;
+    /// Number of the rules
+    fn rules(&self) -> usize;
+    /// Number of the rules which served no call
+    fn unused_rules(&self) -> usize;
+    /// Warnings of the rules, such as duplicates
+    fn warnings(&self) -> Vec<String>;
+    /// Notes for reproducing the behaviors, such as seeds
+    fn notes(&self) -> Vec<String>;
+    /// Removes the rules and the recorded calls
+    fn reset(&self);
+    /// Calls the real implementation when no rule matches, as `otherwise_real` of any arguments
+    fn otherwise_real(&self);
+    /// Limits the renderings of the calls in panic messages, as `Mry::set_debug_limit`
+    fn set_debug_limit(&self, limit: usize);
+    /// Shares the sequences of the object, which check the calls of the mock
+    fn share_sequences(&self, sequences: ObjectSequences);
+    fn boxed_clone(&self) -> Box<dyn AnyMock>;
+}
+
+impl<I: MockableArg, O: MockableRet> AnyMock for SharedMock<I, O> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &'static str {
+        self.lock().name
+    }
+
+    fn calls(&self) -> usize {
+        self.lock().calls()
+    }
+
+    fn rules(&self) -> usize {
+        self.lock().rules()
+    }
+
+    fn unused_rules(&self) -> usize {
+        self.lock().unused_rules()
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.lock().warnings().to_vec()
+    }
+
+    fn notes(&self) -> Vec<String> {
+        self.lock().notes()
+    }
+
+    fn reset(&self) {
+        let removed = self.lock().reset();
+        drop(removed);
+    }
+
+    fn otherwise_real(&self) {
+        self.lock()
+            .otherwise_real(Arc::new(Mutex::new(Matcher::any())));
+    }
+
+    fn set_debug_limit(&self, limit: usize) {
+        self.lock().log.set_debug_limit(Some(limit));
+    }
+
+    fn share_sequences(&self, sequences: ObjectSequences) {
+        self.lock().share_sequences(sequences);
+    }
+
+    fn boxed_clone(&self) -> Box<dyn AnyMock> {
+        Box::new(self.clone())
+    }
+}
+
+type BoxAnyMock = Box<dyn AnyMock>;
 
 #[doc(hidden)]
 pub trait MockGetter<I, O> {
-    fn get(&self, key: &TypeId, name: &'static str) -> Option<&Mock<I, O>>;
-    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> &mut Mock<I, O>;
+    fn get(&self, key: &TypeId, name: &'static str) -> Option<SharedMock<I, O>>;
+    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> SharedMock<I, O>;
+    /// Makes the calls of every mock of the object checked against the sequence
+    fn join_sequence(&mut self, _sequence: &Sequence) {}
 }
 
+/// Each mock has its own lock so that the lock of the whole `Mocks` is not held while
+/// behaviors run, which allows a behavior to call other mocked methods of the same object.
+pub(crate) type SharedMock<I, O> = Arc<Mutex<Mock<I, O>>>;
+
 impl<I, O, T, M: 'static> MockGetter<I, O> for T
 where
     T: DerefMut<Target = M> + Deref<Target = M>,
     M: MockGetter<I, O>,
 {
-    fn get<'a>(&'a self, key: &TypeId, name: &'static str) -> Option<&'a Mock<I, O>> {
+    fn get(&self, key: &TypeId, name: &'static str) -> Option<SharedMock<I, O>> {
         self.deref().get(key, name)
     }
 
-    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> &mut Mock<I, O> {
+    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> SharedMock<I, O> {
         self.deref_mut().get_mut_or_create(key, name)
     }
+
+    fn join_sequence(&mut self, sequence: &Sequence) {
+        MockGetter::<I, O>::join_sequence(self.deref_mut(), sequence)
+    }
 }
 
 #[derive(Default)]
 #[doc(hidden)]
 pub struct Mocks {
-    pub(crate) mock_objects: HashMap<TypeId, BoxAnySend>,
+    pub(crate) mock_objects: HashMap<TypeId, BoxAnyMock>,
+    /// Whether the methods call the real implementation when no rule matches, set by
+    /// `calls_real_impl_all`
+    otherwise_real: bool,
+    /// Limit of the renderings of the calls, set by `Mry::set_debug_limit`
+    debug_limit: Option<usize>,
+    /// Sequences the object takes part in, shared by its mocks
+    sequences: ObjectSequences,
 }
 
 impl<I: MockableArg, O: MockableRet> MockGetter<I, O> for Mocks {
-    fn get(&self, key: &TypeId, _name: &'static str) -> Option<&Mock<I, O>> {
-        self.mock_objects
-            .get(key)
-            .map(|mock| mock.downcast_ref().unwrap())
+    fn get(&self, key: &TypeId, _name: &'static str) -> Option<SharedMock<I, O>> {
+        self.mock_objects.get(key).map(|mock| {
+            mock.as_any()
+                .downcast_ref::<SharedMock<I, O>>()
+                .unwrap()
+                .clone()
+        })
     }
 
-    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> &mut Mock<I, O> {
+    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> SharedMock<I, O> {
+        let otherwise_real = self.otherwise_real;
+        let debug_limit = self.debug_limit;
+        let sequences = self.sequences.clone();
         self.mock_objects
             .entry(key)
-            .or_insert(Box::new(Mock::<I, O>::new(name)))
-            .downcast_mut()
+            .or_insert_with(|| {
+                let mock: BoxAnyMock = Box::new(Arc::new(Mutex::new(Mock::<I, O>::new(name))));
+                if otherwise_real {
+                    mock.otherwise_real();
+                }
+                if let Some(limit) = debug_limit {
+                    mock.set_debug_limit(limit);
+                }
+                mock.share_sequences(sequences);
+                mock
+            })
+            .as_any()
+            .downcast_ref::<SharedMock<I, O>>()
             .unwrap()
+            .clone()
     }
-}
 
-impl Mocks {
-    #[doc(hidden)]
-    pub fn record_call_and_find_mock_output<I: MockableArg, O: MockableRet>(
-        &mut self,
-        key: TypeId,
-        name: &'static str,
-        input: I,
-    ) -> Option<O> {
-        let mock = self.get_mut_or_create(key, name);
-        let result = mock.find_mock_output(&input);
-        mock.record_call(Arc::new(Mutex::new(input)));
-        result
+    fn join_sequence(&mut self, sequence: &Sequence) {
+        let sequence = sequence.downgrade();
+        let mut sequences = self.sequences.lock();
+        if !sequences.iter().any(|joined| joined.ptr_eq(&sequence)) {
+            sequences.push(sequence);
+        }
     }
+}
+
+/// Records the call and finds the output without holding the lock of `mocks` while the behavior runs.
+pub(crate) fn record_call_and_find_mock_output<I: MockableArg, O: MockableRet>(
+    mocks: &Mutex<Mocks>,
+    key: TypeId,
+    name: &'static str,
+    input: I,
+) -> Option<O> {
+    let mock = MockGetter::<I, O>::get_mut_or_create(&mut *mocks.lock(), key, name);
+    let output =
+        crate::reentrancy::lock_for_call(&mock, name)?.record_call_and_find_mock_output(input);
+    output
+}
 
+/// Same as `record_call_and_find_mock_output`, but gives the input back if the call is not served
+pub(crate) fn record_call_and_find_mock_output_or_input<I: MockableArg, O: MockableRet>(
+    mocks: &Mutex<Mocks>,
+    key: TypeId,
+    name: &'static str,
+    input: I,
+    clone: Option<fn(&I) -> I>,
+) -> Result<O, I> {
+    let mock = MockGetter::<I, O>::get_mut_or_create(&mut *mocks.lock(), key, name);
+    let Some(mut mock) = crate::reentrancy::lock_for_call(&mock, name) else {
+        return Err(input);
+    };
+    mock.record_call_and_find_mock_output_or_input(input, clone)
+}
+
+impl Mocks {
     #[cfg(test)]
     pub(crate) fn insert<I: MockableArg, O: MockableRet>(&mut self, key: TypeId, item: Mock<I, O>) {
-        self.mock_objects.insert(key, Box::new(item));
+        self.mock_objects
+            .insert(key, Box::new(Arc::new(Mutex::new(item))));
     }
 
     pub(crate) fn remove(&mut self, key: &TypeId) -> Option<()> {
         self.mock_objects.remove(key).map(|_| ())
     }
+
+    /// Makes the methods mocked so far and later call the real implementation when no rule
+    /// matches
+    pub(crate) fn calls_real_impl_all(&mut self) {
+        self.otherwise_real = true;
+        for mock in self.mock_objects.values() {
+            mock.otherwise_real();
+        }
+    }
+
+    /// Limits the renderings of the calls of the methods mocked so far and later
+    pub(crate) fn set_debug_limit(&mut self, limit: usize) {
+        self.debug_limit = Some(limit);
+        for mock in self.mock_objects.values() {
+            mock.set_debug_limit(limit);
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &dyn AnyMock> {
+        self.mock_objects.values().map(|mock| mock.as_ref())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{Behavior, Matcher};
+    use crate::Behavior;
 
     use super::*;
 
@@ -114,6 +273,7 @@ mod test {
         assert_eq!(
             mock_data
                 .get_mut_or_create(TypeId::of::<usize>(), "meow")
+                .lock()
                 .find_mock_output(&1u8),
             Some(4u8)
         );
@@ -130,4 +290,22 @@ mod test {
             "meow",
         );
     }
+
+    #[test]
+    fn calls_real_impl_all() {
+        let mut mock_data = Mocks::default();
+        let existing = TypeId::of::<usize>();
+        MockGetter::<u8, u8>::get_mut_or_create(&mut mock_data, existing, "a");
+        mock_data.calls_real_impl_all();
+        let created = TypeId::of::<u8>();
+
+        for key in [existing, created] {
+            assert_eq!(
+                MockGetter::<u8, u8>::get_mut_or_create(&mut mock_data, key, "a")
+                    .lock()
+                    .find_mock_output(&1),
+                None
+            );
+        }
+    }
 }
@@ -1,46 +1,48 @@
 use crate::mockable::MockableArg;
 use crate::mockable::MockableRet;
-#[cfg(debug_assertions)]
-use parking_lot::Mutex;
+#[cfg(any(debug_assertions, feature = "bench"))]
+use crate::sync::Mutex;
 use std::any::TypeId;
 use std::cmp::Ordering;
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "bench"))]
 use std::sync::atomic::AtomicU16;
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "bench"))]
 use std::sync::Arc;
+#[cfg(any(debug_assertions, feature = "bench"))]
+use std::{cell::RefCell, collections::HashMap};
 
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "bench"))]
 use crate::MockGetter;
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "bench"))]
 use crate::Mocks;
 
 /// A unique id for an object
 pub type MryId = u16;
-#[cfg(debug_assertions)]
+#[cfg(any(debug_assertions, feature = "bench"))]
 static ID: AtomicU16 = AtomicU16::new(0);
 
 #[derive(Clone)]
 /// Mock container that has blank and harmless trait implementation for major traits such as `Eq` and `Ord`
 pub struct Mry {
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
     id: MryId,
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
     mocks: Option<Arc<Mutex<Mocks>>>,
 }
 
 impl std::fmt::Debug for Mry {
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Mry").field("id", &self.id).finish()
     }
-    #[cfg(not(debug_assertions))]
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Mry").finish()
     }
 }
 
 impl Mry {
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
     pub(crate) fn generate(&mut self) -> &mut Self {
         self.mocks
             .get_or_insert(Arc::new(Mutex::new(Default::default())));
@@ -48,7 +50,7 @@ impl Mry {
     }
 
     #[doc(hidden)]
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
     pub fn record_call_and_find_mock_output<I: MockableArg, O: MockableRet>(
         &self,
         key: TypeId,
@@ -56,13 +58,28 @@ impl Mry {
         input: I,
     ) -> Option<O> {
         self.mocks.as_ref().and_then(|mocks| {
-            mocks
-                .lock()
-                .record_call_and_find_mock_output(key, name, input)
+            crate::mocks::record_call_and_find_mock_output(mocks, key, name, input)
         })
     }
 
-    #[cfg(not(debug_assertions))]
+    #[doc(hidden)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn record_call_and_find_mock_output_or_input<I: MockableArg, O: MockableRet>(
+        &self,
+        key: TypeId,
+        name: &'static str,
+        input: I,
+        clone: Option<fn(&I) -> I>,
+    ) -> Result<O, I> {
+        match &self.mocks {
+            Some(mocks) => crate::mocks::record_call_and_find_mock_output_or_input(
+                mocks, key, name, input, clone,
+            ),
+            None => Err(input),
+        }
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
     pub fn record_call_and_find_mock_output<
         I: PartialEq + std::fmt::Debug + Clone + Send + 'static,
         O: std::fmt::Debug + Send + 'static,
@@ -75,15 +92,165 @@ impl Mry {
         None
     }
 
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
+    pub fn record_call_and_find_mock_output_or_input<I: Send + 'static, O>(
+        &self,
+        _key: TypeId,
+        _name: &'static str,
+        input: I,
+        _clone: Option<fn(&I) -> I>,
+    ) -> Result<O, I> {
+        Err(input)
+    }
+
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub(crate) fn shared_mocks(&self) -> &Arc<Mutex<Mocks>> {
+        self.mocks
+            .as_ref()
+            .expect("mocks of the object are not generated yet")
+    }
+
+    /// Assert no mocked method of the object is called, for paths where the object must be
+    /// completely untouched.
+    /// Panics listing the called methods
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn assert_no_interactions(&self) {
+        let Some(mocks) = &self.mocks else {
+            return;
+        };
+        let mut calls: Vec<_> = mocks
+            .lock()
+            .iter()
+            .map(|mock| (mock.name(), mock.calls()))
+            .filter(|(_, calls)| *calls > 0)
+            .collect();
+        if calls.is_empty() {
+            return;
+        }
+        calls.sort();
+        let calls = calls
+            .into_iter()
+            .map(|(name, calls)| format!("\n    {} was called {} times", name, calls))
+            .collect::<String>();
+        panic!("Expected no interactions with the object, but:{}", calls);
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
+    pub fn assert_no_interactions(&self) {}
+
+    /// Makes every mocked method of the object call the real implementation when no rule
+    /// matches, as `otherwise_real` on each of them, while calls are still recorded.
+    /// Rules set before or after still take precedence, which spies on the whole object
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn calls_real_impl_all(&mut self) {
+        self.generate();
+        self.shared_mocks().lock().calls_real_impl_all();
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
+    pub fn calls_real_impl_all(&mut self) {}
+
+    /// Limits the length of the arguments of this object rendered in panic messages, in place of
+    /// the limit of the thread set by `mry::set_debug_limit`, for objects taking huge arguments
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn set_debug_limit(&mut self, limit: usize) {
+        self.generate();
+        self.shared_mocks().lock().set_debug_limit(limit);
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
+    pub fn set_debug_limit(&mut self, _limit: usize) {}
+
+    /// Type-erased handles of the mocked methods of the object, sorted by name
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn handles(&self) -> Vec<crate::AnyMockHandle> {
+        let mut handles: Vec<_> = self
+            .mocks
+            .iter()
+            .flat_map(|mocks| {
+                mocks
+                    .lock()
+                    .iter()
+                    .map(|mock| crate::AnyMockHandle::new(mock.boxed_clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        handles.sort_by_key(|handle| handle.name());
+        handles
+    }
+
+    /// Asserts each of the methods has a rule, for `assert_fully_stubbed` of trait mocks.
+    /// Panics listing the methods without rules
     #[doc(hidden)]
-    #[cfg(debug_assertions)]
-    pub fn mocks<I: MockableArg, O: MockableRet>(&mut self) -> Arc<Mutex<dyn MockGetter<I, O>>> {
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn assert_stubbed(&self, names: &[&'static str]) {
+        let stubbed: Vec<_> = self
+            .mocks
+            .iter()
+            .flat_map(|mocks| {
+                mocks
+                    .lock()
+                    .iter()
+                    .filter(|mock| mock.rules() > 0)
+                    .map(|mock| mock.name())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let missing = names
+            .iter()
+            .filter(|name| !stubbed.contains(name))
+            .map(|name| format!("\n    {} has no rule", name))
+            .collect::<String>();
+        if !missing.is_empty() {
+            panic!(
+                "Expected every method of the mock to be stubbed, but:{}",
+                missing
+            );
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
+    pub fn assert_stubbed(&self, _names: &[&'static str]) {}
+
+    #[doc(hidden)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
+    pub fn mocks<I: MockableArg, O: MockableRet>(
+        &mut self,
+    ) -> Arc<Mutex<dyn MockGetter<I, O> + Send>> {
         self.generate().mocks.as_ref().unwrap().clone()
     }
 }
 
+#[cfg(any(debug_assertions, feature = "bench"))]
+thread_local! {
+    static SHARED: RefCell<HashMap<&'static str, Mry>> = RefCell::new(HashMap::new());
+}
+
+/// Mocks of a struct with `#[mry::mry(shared)]`, which its instances share on the thread
+#[doc(hidden)]
+#[cfg(any(debug_assertions, feature = "bench"))]
+pub fn shared_mry(type_name: &'static str) -> Mry {
+    SHARED.with(|shared| shared.borrow().get(type_name).cloned().unwrap_or_default())
+}
+
+/// Mocks of a method of a struct with `#[mry::mry(shared)]`, generating the ones of the struct
+#[doc(hidden)]
+#[cfg(any(debug_assertions, feature = "bench"))]
+pub fn shared_mocks<I: MockableArg, O: MockableRet>(
+    type_name: &'static str,
+) -> Arc<Mutex<dyn MockGetter<I, O> + Send>> {
+    SHARED.with(|shared| shared.borrow_mut().entry(type_name).or_default().mocks())
+}
+
+#[doc(hidden)]
+#[cfg(not(any(debug_assertions, feature = "bench")))]
+pub fn shared_mry(_type_name: &'static str) -> Mry {
+    Mry::default()
+}
+
 impl Default for Mry {
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "bench"))]
     fn default() -> Self {
         Self {
             id: ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
@@ -91,7 +258,7 @@ impl Default for Mry {
         }
     }
 
-    #[cfg(not(debug_assertions))]
+    #[cfg(not(any(debug_assertions, feature = "bench")))]
     fn default() -> Self {
         Self {}
     }
@@ -103,9 +270,7 @@ impl PartialOrd for Mry {
     }
 }
 
-impl Eq for Mry {
-    fn assert_receiver_is_total_eq(&self) {}
-}
+impl Eq for Mry {}
 
 impl Ord for Mry {
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
@@ -226,6 +391,35 @@ mod test {
         assert_eq!(mry.clone().mocks.unwrap().lock().mock_objects.len(), 1);
     }
 
+    #[test]
+    fn assert_no_interactions_passes_without_mocks() {
+        Mry::default().assert_no_interactions();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected no interactions with the object, but:\n    Cat::meow was called 1 times"
+    )]
+    fn assert_no_interactions_lists_calls() {
+        let mut mry = Mry::default();
+        mry.generate();
+        let mut mock = Mock::<usize, usize>::new("Cat::meow");
+        mock.returns(Matcher::any().wrapped(), 1);
+        mock.record_call_and_find_mock_output(0);
+        mry.mocks
+            .as_ref()
+            .unwrap()
+            .lock()
+            .insert(TypeId::of::<usize>(), mock);
+        mry.mocks
+            .as_ref()
+            .unwrap()
+            .lock()
+            .insert(TypeId::of::<u8>(), Mock::<usize, usize>::new("Cat::purr"));
+
+        mry.assert_no_interactions();
+    }
+
     #[test]
     fn inner_called_returns_none_when_no_mocks() {
         let mry = Mry::default();
@@ -243,6 +437,7 @@ mod test {
         mry.mocks()
             .lock()
             .get_mut_or_create(TypeId::of::<usize>(), "name")
+            .lock()
             .returns(Matcher::new_eq(1u8).wrapped(), 1u8);
 
         assert_eq!(
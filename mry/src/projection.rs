@@ -0,0 +1,223 @@
+use std::{borrow::Borrow, fmt::Debug, ops::Deref, sync::Arc};
+
+use crate::sync::Mutex;
+
+use crate::{borrowed::Loan, ArgMatcher, MockableArg};
+
+/// Argument of a parameter with `#[mry(log_with = ..)]`, which records only its projection.
+/// Patterns match the whole argument while the call runs, and the projection after that, so
+/// `assert_called` compares the projections of the expected and the recorded values. Behaviors
+/// and logs see the projection.
+pub struct Projected<T: ?Sized + 'static, P> {
+    projection: P,
+    value: Arc<Mutex<Option<Loan<T>>>>,
+}
+
+impl<T: ?Sized, P: Clone> Clone for Projected<T, P> {
+    fn clone(&self) -> Self {
+        Self {
+            projection: self.projection.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized, P: Debug> Debug for Projected<T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.projection.fmt(f)
+    }
+}
+
+impl<T: ?Sized, P> Deref for Projected<T, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.projection
+    }
+}
+
+/// Ends the loan of the argument when the call returns
+struct Return<T: ?Sized + 'static>(Arc<Mutex<Option<Loan<T>>>>);
+
+impl<T: ?Sized> Drop for Return<T> {
+    fn drop(&mut self) {
+        self.0.lock().take();
+    }
+}
+
+impl<T: ?Sized, P> Projected<T, P> {
+    /// Lends the argument and its projection to the `Projected` given to `f`
+    #[doc(hidden)]
+    pub fn lend<R>(value: &T, projection: impl FnOnce(&T) -> P, f: impl FnOnce(Self) -> R) -> R {
+        let lent = Arc::new(Mutex::new(Some(Loan::new(value))));
+        let _return = Return(lent.clone());
+        f(Self {
+            projection: projection(value),
+            value: lent,
+        })
+    }
+
+    /// Runs `f` on the whole argument while the call runs on this thread
+    fn with_value<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.value.lock().as_ref().and_then(Loan::get).map(f)
+    }
+}
+
+/// Runs the closure on the whole argument as the type matched by the patterns
+type View<T, O> = fn(&T, &mut dyn FnMut(&O));
+
+impl<T: ?Sized, P: PartialEq + MockableArg> Projected<T, P> {
+    /// Whether the whole argument matches, if the call is running on this thread
+    fn matches<O>(&self, view: View<T, O>, matches: impl Fn(&O) -> bool) -> Option<bool> {
+        self.with_value(|value| {
+            let mut matched = false;
+            view(value, &mut |value| matched = matches(value));
+            matched
+        })
+    }
+
+    /// Matcher of the whole argument, which compares the projection of the expected value for
+    /// the recorded calls
+    fn matcher_of<O: Send + 'static>(
+        matcher: ArgMatcher<O>,
+        projection: impl Fn(&O) -> P,
+        view: View<T, O>,
+    ) -> ArgMatcher<Self> {
+        match matcher {
+            ArgMatcher::Any => ArgMatcher::Any,
+            ArgMatcher::Never => ArgMatcher::Never,
+            ArgMatcher::Eq { value, partial_eq } => {
+                let expected = projection(&value);
+                ArgMatcher::Fn(Box::new(move |projected: &Self| {
+                    projected
+                        .matches(view, |actual| partial_eq(&value, actual))
+                        .unwrap_or_else(|| projected.projection == expected)
+                }))
+            }
+            ArgMatcher::Captures(capture) => {
+                ArgMatcher::Captures(Box::new(move |projected: &Self| {
+                    projected.matches(view, |value| {
+                        capture(value);
+                        true
+                    });
+                }))
+            }
+            matcher => {
+                let pattern = matcher.describe();
+                ArgMatcher::Fn(Box::new(move |projected: &Self| {
+                    projected
+                        .matches(view, |value| matcher.matches(value))
+                        .unwrap_or_else(|| {
+                            panic!(
+                                "the pattern {} cannot match the calls after they return, as log_with records only the projection of the argument. Use a value or mry::Any",
+                                pattern
+                            )
+                        })
+                }))
+            }
+        }
+    }
+}
+
+impl<T: Send + 'static, P: PartialEq + MockableArg> Projected<T, P> {
+    #[doc(hidden)]
+    pub fn matcher(
+        matcher: ArgMatcher<T>,
+        projection: impl Fn(&T) -> P + Send + 'static,
+    ) -> ArgMatcher<Self> {
+        Self::matcher_of(matcher, projection, |value, f| f(value))
+    }
+}
+
+impl<T: ?Sized + ToOwned, P: PartialEq + MockableArg> Projected<T, P>
+where
+    T::Owned: Send + 'static,
+{
+    /// Matcher of an unsized argument such as `&str`, which patterns match as its owned type
+    #[doc(hidden)]
+    pub fn owned_matcher(
+        matcher: ArgMatcher<T::Owned>,
+        projection: impl Fn(&T) -> P + Send + 'static,
+    ) -> ArgMatcher<Self> {
+        Self::matcher_of(
+            matcher,
+            move |value: &T::Owned| projection(value.borrow()),
+            |value, f| f(&value.to_owned()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct Report {
+        id: u32,
+        body: Vec<u8>,
+    }
+
+    fn report(id: u32, len: usize) -> Report {
+        Report {
+            id,
+            body: vec![0; len],
+        }
+    }
+
+    #[test]
+    fn matches_whole_argument_during_call() {
+        let matcher = Projected::matcher(
+            crate::pred(|report: &Report| report.body.len() > 2),
+            |report| report.id,
+        );
+
+        assert!(Projected::lend(
+            &report(1, 3),
+            |report| report.id,
+            |projected| { matcher.matches(&projected) }
+        ));
+        assert!(!Projected::lend(
+            &report(1, 1),
+            |report| report.id,
+            |projected| { matcher.matches(&projected) }
+        ));
+    }
+
+    #[test]
+    fn compares_projections_after_call() {
+        let matcher = Projected::matcher(ArgMatcher::new_eq(report(1, 0)), |report| report.id);
+        let recorded = Projected::lend(
+            &report(1, 3),
+            |report| report.id,
+            |projected| {
+                assert!(!matcher.matches(&projected));
+                projected
+            },
+        );
+
+        assert!(matcher.matches(&recorded));
+        assert_eq!(*recorded, 1);
+    }
+
+    #[test]
+    fn matches_unsized_argument_as_owned() {
+        let matcher =
+            Projected::owned_matcher(ArgMatcher::new_eq("report".to_string()), |text: &str| {
+                text.len()
+            });
+
+        assert!(Projected::lend("report", str::len, |projected| matcher.matches(&projected)));
+        assert!(!Projected::lend("record", str::len, |projected| matcher.matches(&projected)));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the pattern predicate cannot match the calls after they return, as log_with records only the projection of the argument"
+    )]
+    fn panics_on_predicate_after_call() {
+        let matcher = Projected::matcher(crate::pred(|_: &Report| true), |report| report.id);
+        let recorded = Projected::lend(&report(1, 3), |report| report.id, |projected| projected);
+
+        matcher.matches(&recorded);
+    }
+}
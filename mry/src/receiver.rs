@@ -0,0 +1,90 @@
+use std::{
+    any::{type_name, TypeId},
+    cell::Cell,
+};
+
+thread_local! {
+    static RECEIVER: Cell<Option<(TypeId, *const ())>> = const { Cell::new(None) };
+}
+
+struct Restore(Option<(TypeId, *const ())>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        RECEIVER.with(|current| current.set(self.0));
+    }
+}
+
+/// Lends the receiver of a mocked method to `returns_with_self` while looking up the mock output
+#[doc(hidden)]
+pub fn with_receiver<S: 'static, R>(receiver: &S, f: impl FnOnce() -> R) -> R {
+    let previous = RECEIVER.with(|current| {
+        current.replace(Some((TypeId::of::<S>(), receiver as *const S as *const ())))
+    });
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Calls `f` with the receiver lent by `with_receiver`.
+/// Panics if there is none or it is not `S`.
+pub(crate) fn with_current_receiver<S: 'static, R>(name: &str, f: impl FnOnce(&S) -> R) -> R {
+    match RECEIVER.with(Cell::get) {
+        // SAFETY: the receiver is borrowed for the whole `with_receiver` which runs this
+        Some((type_id, receiver)) if type_id == TypeId::of::<S>() => {
+            f(unsafe { &*(receiver as *const S) })
+        }
+        Some(_) => panic!(
+            "returns_with_self of {} expects the receiver of type {}, but it is called with another one",
+            name,
+            type_name::<S>()
+        ),
+        None => panic!(
+            "returns_with_self of {} expects the receiver of type {}, but it is called without one. Generic types and types with lifetimes do not lend their receivers",
+            name,
+            type_name::<S>()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lends_receiver() {
+        let receiver = "Tama".to_string();
+
+        assert_eq!(
+            with_receiver(&receiver, || with_current_receiver(
+                "Cat::meow",
+                String::len
+            )),
+            4
+        );
+    }
+
+    #[test]
+    fn restores_outer_receiver() {
+        with_receiver(&1u8, || {
+            with_receiver(&"inner", || ());
+
+            assert_eq!(with_current_receiver("Cat::meow", |a: &u8| *a), 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "returns_with_self of Cat::meow expects the receiver of type u8, but it is called with another one"
+    )]
+    fn another_type() {
+        with_receiver(&1u16, || with_current_receiver("Cat::meow", |a: &u8| *a));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "returns_with_self of Cat::meow expects the receiver of type u8, but it is called without one"
+    )]
+    fn without_receiver() {
+        with_current_receiver("Cat::meow", |a: &u8| *a);
+    }
+}
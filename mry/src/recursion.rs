@@ -0,0 +1,162 @@
+use std::{
+    cell::{Cell, RefCell},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::sync::Mutex;
+
+/// Durations of the real implementations of a mock, shared with the calls measuring them
+pub(crate) type Durations = Arc<Mutex<Vec<Duration>>>;
+
+thread_local! {
+    static RECURSION_LIMIT: Cell<usize> = const { Cell::new(256) };
+    /// Names of the mocked methods whose real implementations are running on this thread, the
+    /// innermost last
+    static REAL_IMPLS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    /// Mock of the name passing the call to its real implementation, which is entered next, and
+    /// where to record the duration of it
+    static PASSED: RefCell<Option<(&'static str, Option<Durations>)>> = const { RefCell::new(None) };
+}
+
+/// Limits how many times the real implementation of a mocked method may be entered again before
+/// the outer one returns, 256 by default. Exceeding it panics with the cycle, such as a
+/// `calls_real_impl` body calling the same method through the mock, instead of overflowing the
+/// stack.
+///
+/// Raise it for methods recursing deeply on purpose. The setting is of the current thread, which
+/// is the current test under `cargo test`.
+pub fn set_recursion_limit(limit: usize) {
+    RECURSION_LIMIT.with(|current| current.set(limit));
+}
+
+/// Real implementation of a mocked method in progress on this thread. Only calls passed through
+/// by the mock, such as by `calls_real_impl`, are counted
+#[doc(hidden)]
+pub struct RealImpl {
+    counted: bool,
+    measured: Option<(Instant, Durations)>,
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl Drop for RealImpl {
+    fn drop(&mut self) {
+        if let Some((start, durations)) = self.measured.take() {
+            durations.lock().push(crate::clock::now() - start);
+        }
+        if self.counted {
+            REAL_IMPLS.with(|real_impls| real_impls.borrow_mut().pop());
+        }
+    }
+}
+
+/// Makes the real implementation entered next by the name counted, and record its duration
+/// after `measure_durations`
+pub(crate) fn pass_to_real_impl(name: &'static str, durations: Option<Durations>) {
+    PASSED.with(|passed| *passed.borrow_mut() = Some((name, durations)));
+}
+
+#[doc(hidden)]
+pub fn __enter_real_impl(name: &'static str) -> RealImpl {
+    let Some(durations) = PASSED
+        .with(|passed| passed.borrow_mut().take())
+        .filter(|(passed, _)| *passed == name)
+        .map(|(_, durations)| durations)
+    else {
+        return RealImpl {
+            counted: false,
+            measured: None,
+            _not_send: std::marker::PhantomData,
+        };
+    };
+    REAL_IMPLS.with(|real_impls| {
+        let mut real_impls = real_impls.borrow_mut();
+        let depth = real_impls.iter().filter(|entered| **entered == name).count();
+        let limit = RECURSION_LIMIT.with(Cell::get);
+        if depth >= limit {
+            let start = real_impls.iter().rposition(|entered| *entered == name);
+            let cycle = real_impls[start.unwrap_or(0)..]
+                .iter()
+                .chain([&name])
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            drop(real_impls);
+            panic!(
+                "{} recursed through its real implementation {} times, exceeding the limit of mry::set_recursion_limit. The cycle: {}",
+                name, depth, cycle
+            );
+        }
+        real_impls.push(name);
+    });
+    RealImpl {
+        counted: true,
+        measured: durations.map(|durations| (crate::clock::now(), durations)),
+        _not_send: std::marker::PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Enters the real implementation as passed through by the mock
+    fn enter(name: &'static str) -> RealImpl {
+        pass_to_real_impl(name, None);
+        __enter_real_impl(name)
+    }
+
+    #[test]
+    fn leaves_on_drop() {
+        set_recursion_limit(1);
+
+        drop(enter("a"));
+
+        let _a = enter("a");
+    }
+
+    #[test]
+    fn counts_other_methods_separately() {
+        set_recursion_limit(1);
+
+        let _a = enter("a");
+        let _b = enter("b");
+    }
+
+    #[test]
+    fn counts_only_passed_calls() {
+        set_recursion_limit(1);
+
+        let _a = enter("a");
+        let _a2 = __enter_real_impl("a");
+        pass_to_real_impl("b", None);
+        let _a3 = __enter_real_impl("a");
+    }
+
+    #[test]
+    fn measures_named_real_impl() {
+        let durations = Arc::new(Mutex::new(Vec::new()));
+
+        pass_to_real_impl("a", Some(durations.clone()));
+        drop(__enter_real_impl("b"));
+        pass_to_real_impl("a", Some(durations.clone()));
+        drop(__enter_real_impl("a"));
+        drop(__enter_real_impl("a"));
+
+        assert_eq!(durations.lock().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "a recursed through its real implementation 2 times, exceeding the limit of mry::set_recursion_limit. The cycle: a -> b -> a"
+    )]
+    fn panics_with_cycle() {
+        set_recursion_limit(2);
+
+        let _a = enter("a");
+        let _b = enter("b");
+        let _a2 = enter("a");
+        let _b2 = enter("b");
+        let _a3 = enter("a");
+    }
+}
@@ -0,0 +1,58 @@
+use std::{fmt::Debug, ops::Deref};
+
+use crate::ArgMatcher;
+
+/// Argument of a parameter with `#[mry(redact)]`, which shows `«redacted»` in logs and panic
+/// messages instead of the value. Patterns still match the value itself.
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Redacted<T>(pub T);
+
+impl<T> Debug for Redacted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("«redacted»")
+    }
+}
+
+impl<T> Deref for Redacted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Send + 'static> Redacted<T> {
+    #[doc(hidden)]
+    pub fn matcher(matcher: ArgMatcher<T>) -> ArgMatcher<Redacted<T>> {
+        match matcher {
+            ArgMatcher::Any => ArgMatcher::Any,
+            ArgMatcher::Never => ArgMatcher::Never,
+            ArgMatcher::Captures(capture) => {
+                ArgMatcher::Captures(Box::new(move |value: &Redacted<T>| capture(&value.0)))
+            }
+            matcher => ArgMatcher::Fn(Box::new(move |value: &Redacted<T>| {
+                matcher.matches(&value.0)
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hides_value() {
+        assert_eq!(
+            format!("{:?}", ("alice", Redacted("hunter2"))),
+            "(\"alice\", «redacted»)"
+        );
+    }
+
+    #[test]
+    fn matches_value() {
+        let matcher = Redacted::matcher(ArgMatcher::from("hunter2"));
+        assert!(matcher.matches(&Redacted("hunter2".to_string())));
+        assert!(!matcher.matches(&Redacted("password".to_string())));
+    }
+}
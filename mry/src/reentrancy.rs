@@ -0,0 +1,119 @@
+use std::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use crate::sync::MutexGuard;
+
+use crate::{mock::Mock, mocks::SharedMock};
+
+thread_local! {
+    /// Addresses of the mocks locked by the calls in progress on this thread, the innermost last
+    static LOCKED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Lock of a mock taken by a call, which lets a nested call of the same mock on this thread
+/// know it would deadlock
+pub(crate) struct CallLock<'a, I, O> {
+    guard: MutexGuard<'a, Mock<I, O>>,
+}
+
+impl<I, O> Drop for CallLock<'_, I, O> {
+    fn drop(&mut self) {
+        LOCKED.with(|locked| locked.borrow_mut().pop());
+    }
+}
+
+impl<I, O> Deref for CallLock<'_, I, O> {
+    type Target = Mock<I, O>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<I, O> DerefMut for CallLock<'_, I, O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+/// Locks the mock for a call.
+/// A call of the mock while its own call is in progress on this thread, such as from a `Drop`
+/// impl of a value dropped by its behavior, would deadlock; it returns `None` to run the real
+/// implementation while unwinding, since panicking again would abort, and panics otherwise.
+pub(crate) fn lock_for_call<'a, I, O>(
+    mock: &'a SharedMock<I, O>,
+    name: &str,
+) -> Option<CallLock<'a, I, O>> {
+    let address = Arc::as_ptr(mock) as usize;
+    if LOCKED.with(|locked| locked.borrow().contains(&address)) {
+        if std::thread::panicking() {
+            return None;
+        }
+        panic!(
+            "{} is called while its own call is in progress on the same thread, such as from a Drop impl of a value dropped by its behavior. Drop the value outside the behavior",
+            name
+        );
+    }
+    let guard = mock.lock();
+    LOCKED.with(|locked| locked.borrow_mut().push(address));
+    Some(CallLock { guard })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn locks_other_mocks_while_locked() {
+        let a: SharedMock<(), ()> = Arc::new(Mutex::new(Mock::new("a")));
+        let b: SharedMock<(), ()> = Arc::new(Mutex::new(Mock::new("b")));
+
+        let _a = lock_for_call(&a, "a").unwrap();
+
+        assert!(lock_for_call(&b, "b").is_some());
+    }
+
+    #[test]
+    fn unlocks_on_drop() {
+        let a: SharedMock<(), ()> = Arc::new(Mutex::new(Mock::new("a")));
+
+        drop(lock_for_call(&a, "a"));
+
+        assert!(lock_for_call(&a, "a").is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "a is called while its own call is in progress on the same thread")]
+    fn panics_on_reentrant_call() {
+        let a: SharedMock<(), ()> = Arc::new(Mutex::new(Mock::new("a")));
+
+        let _a = lock_for_call(&a, "a");
+        let _ = lock_for_call(&a, "a");
+    }
+
+    #[test]
+    fn reentrant_call_while_unwinding_returns_none() {
+        struct CallOnDrop(SharedMock<(), ()>);
+
+        impl Drop for CallOnDrop {
+            fn drop(&mut self) {
+                assert!(lock_for_call(&self.0, "a").is_none());
+            }
+        }
+
+        let a: SharedMock<(), ()> = Arc::new(Mutex::new(Mock::new("a")));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _a = lock_for_call(&a, "a");
+            let _call_on_drop = CallOnDrop(a.clone());
+            panic!("behavior panicked");
+        }));
+
+        assert!(result.is_err());
+        assert!(lock_for_call(&a, "a").is_some());
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt::Debug;
 
-use parking_lot::Mutex;
+use crate::sync::Mutex;
+
+use crate::MockLocator;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum Output<O> {
@@ -8,6 +10,14 @@ pub(crate) enum Output<O> {
     /// called once already called
     ErrorCalledOnce,
     Found(O),
+    /// falls through to the next rule
+    NotFound,
+    /// panics with the message after recording the call
+    Panics(String),
+    /// gave a future to the async method to await
+    Awaits,
+    /// gave a future to a method which is not async
+    ErrorNotAsync,
 }
 
 /// Behavior of mock
@@ -17,27 +27,37 @@ pub enum Behavior<I, O> {
         clone: fn(&I) -> I,
         call: Box<dyn FnMut(I) -> O + Send + 'static>,
     },
+    /// Behaves with a function borrowing the arguments
+    FunctionRef(Box<dyn FnMut(&I) -> O + Send + 'static>),
+    /// Looks up the output, falling through to the next rule if there is none
+    #[allow(clippy::type_complexity)]
+    Lookup(Box<dyn FnMut(&I) -> Option<O> + Send + 'static>),
     /// Returns a constant value
     Const(Mutex<Box<dyn Iterator<Item = O> + Send + 'static>>),
-    /// Once
-    Once(Mutex<Option<O>>),
+    /// Returns the value once, kept behind `Send` so the output type itself needn't be
+    Once(Mutex<Option<Box<dyn FnOnce() -> O + Send + 'static>>>),
     /// Calls real implementation instead of mock
     CallsRealImpl,
+    /// Panics with the message
+    Panics(String),
+    /// Gives a future to the async method to await, returning false if the method is not async
+    Async(Box<dyn FnMut(&I) -> bool + Send + 'static>),
 }
 
 impl<I: Debug, O: Debug> std::fmt::Debug for Behavior<I, O> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Function { .. } => f.debug_tuple("Function(_)").finish(),
+            Self::FunctionRef(_) => f.debug_tuple("FunctionRef(_)").finish(),
+            Self::Lookup(_) => f.debug_tuple("Lookup(_)").finish(),
             Self::Const(cons) => f
                 .debug_tuple("Const")
                 .field(&cons.lock().next().unwrap())
                 .finish(),
-            Self::Once(once) => f
-                .debug_tuple("Once")
-                .field(&once.lock().as_ref().unwrap())
-                .finish(),
+            Self::Once(_) => f.debug_tuple("Once(_)").finish(),
             Self::CallsRealImpl => write!(f, "CallsRealImpl"),
+            Self::Panics(message) => f.debug_tuple("Panics").field(message).finish(),
+            Self::Async(_) => f.debug_tuple("Async(_)").finish(),
         }
     }
 }
@@ -46,15 +66,28 @@ impl<I, O> Behavior<I, O> {
     pub(crate) fn called(&mut self, input: &I) -> Output<O> {
         match self {
             Behavior::Function { clone, call } => Output::Found(call(clone(input))),
+            Behavior::FunctionRef(call) => Output::Found(call(input)),
+            Behavior::Lookup(lookup) => match lookup(input) {
+                Some(output) => Output::Found(output),
+                None => Output::NotFound,
+            },
             Behavior::Const(cons) => Output::Found(cons.get_mut().next().unwrap()),
             Behavior::Once(once) => {
                 if let Some(ret) = once.lock().take() {
-                    Output::Found(ret)
+                    Output::Found(ret())
                 } else {
                     Output::ErrorCalledOnce
                 }
             }
             Behavior::CallsRealImpl => Output::CallsRealImpl,
+            Behavior::Panics(message) => Output::Panics(message.clone()),
+            Behavior::Async(stash) => {
+                if stash(input) {
+                    Output::Awaits
+                } else {
+                    Output::ErrorNotAsync
+                }
+            }
         }
     }
 }
@@ -79,6 +112,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn function_ref() {
+        assert_eq!(
+            Behavior::FunctionRef(Box::new(|(a,): &(String,)| a.len())).called(&("aaa".into(),)),
+            Output::Found(3)
+        );
+    }
+
     #[test]
     fn const_value() {
         assert_eq!(
@@ -0,0 +1,153 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ArgMatcher;
+
+/// Expected JSON built by `mry::json_eq!`, in which matchers such as `mry::any()` stand for values
+#[doc(hidden)]
+pub enum JsonPattern {
+    Value(Value),
+    Matcher(ArgMatcher<Value>),
+    Array(Vec<JsonPattern>),
+    Object(Vec<(String, JsonPattern)>),
+}
+
+impl JsonPattern {
+    fn matches(&self, actual: &Value) -> bool {
+        match (self, actual) {
+            (JsonPattern::Value(expected), actual) => expected == actual,
+            (JsonPattern::Matcher(matcher), actual) => matcher.matches(actual),
+            (JsonPattern::Array(patterns), Value::Array(values)) => {
+                patterns.len() == values.len()
+                    && patterns
+                        .iter()
+                        .zip(values)
+                        .all(|(pattern, value)| pattern.matches(value))
+            }
+            (JsonPattern::Object(fields), Value::Object(map)) => {
+                fields.len() == map.len()
+                    && fields.iter().all(|(key, pattern)| {
+                        map.get(key).is_some_and(|value| pattern.matches(value))
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            JsonPattern::Value(value) => value.to_string(),
+            JsonPattern::Matcher(matcher) => matcher.describe(),
+            JsonPattern::Array(patterns) => format!(
+                "[{}]",
+                patterns
+                    .iter()
+                    .map(JsonPattern::describe)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            JsonPattern::Object(fields) => format!(
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|(key, pattern)| format!("{:?}: {}", key, pattern.describe()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Values and matchers placed in `mry::json_eq!`
+#[doc(hidden)]
+pub trait IntoJsonPattern {
+    fn into_json_pattern(self) -> JsonPattern;
+}
+
+impl<T: Serialize> IntoJsonPattern for T {
+    fn into_json_pattern(self) -> JsonPattern {
+        JsonPattern::Value(
+            serde_json::to_value(self).expect("the value in json_eq! is not serializable to JSON"),
+        )
+    }
+}
+
+impl IntoJsonPattern for ArgMatcher<Value> {
+    fn into_json_pattern(self) -> JsonPattern {
+        JsonPattern::Matcher(self)
+    }
+}
+
+#[doc(hidden)]
+pub fn __json_eq<T: Serialize>(pattern: JsonPattern) -> ArgMatcher<T> {
+    ArgMatcher::Named {
+        name: format!("json_eq!({})", pattern.describe()),
+        predicate: Box::new(move |input: &T| {
+            serde_json::to_value(input).is_ok_and(|actual| pattern.matches(&actual))
+        }),
+        render: Some(|input: &T| match serde_json::to_value(input) {
+            Ok(actual) => crate::truncate::debug(&format_args!("{}", actual)),
+            Err(error) => format!("<not serializable: {}>", error),
+        }),
+    }
+}
+
+/// Matches an argument serialized to the JSON structurally, such as
+/// `mry::json_eq!({"id": 1, "ts": mry::any()})`. Matchers of `serde_json::Value` stand for the
+/// values to ignore or check loosely, and objects match regardless of the order of the keys.
+///
+/// Arguments are `serde_json::Value` or any `Serialize` payload. Requires the `json` feature.
+#[macro_export]
+macro_rules! json_eq {
+    ($($json:tt)+) => {
+        $crate::__json_eq($crate::__json_pattern!($($json)+))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_pattern {
+    (@array [$($elements:expr,)*]) => {
+        ::std::vec![$($elements,)*]
+    };
+    (@array [$($elements:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@array [$($elements,)* $crate::__json_pattern!(null),] $($($rest)*)?)
+    };
+    (@array [$($elements:expr,)*] [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@array [$($elements,)* $crate::__json_pattern!([$($array)*]),] $($($rest)*)?)
+    };
+    (@array [$($elements:expr,)*] {$($object:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@array [$($elements,)* $crate::__json_pattern!({$($object)*}),] $($($rest)*)?)
+    };
+    (@array [$($elements:expr,)*] $next:expr $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@array [$($elements,)* $crate::__json_pattern!($next),] $($($rest)*)?)
+    };
+    (@object [$($fields:expr,)*]) => {
+        ::std::vec![$($fields,)*]
+    };
+    (@object [$($fields:expr,)*] $key:literal : null $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@object [$($fields,)* (::std::string::ToString::to_string($key), $crate::__json_pattern!(null)),] $($($rest)*)?)
+    };
+    (@object [$($fields:expr,)*] $key:literal : [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@object [$($fields,)* (::std::string::ToString::to_string($key), $crate::__json_pattern!([$($array)*])),] $($($rest)*)?)
+    };
+    (@object [$($fields:expr,)*] $key:literal : {$($object:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@object [$($fields,)* (::std::string::ToString::to_string($key), $crate::__json_pattern!({$($object)*})),] $($($rest)*)?)
+    };
+    (@object [$($fields:expr,)*] $key:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__json_pattern!(@object [$($fields,)* (::std::string::ToString::to_string($key), $crate::__json_pattern!($value)),] $($($rest)*)?)
+    };
+    (null) => {
+        // `()` is serialized to `null`
+        $crate::IntoJsonPattern::into_json_pattern(())
+    };
+    ([$($array:tt)*]) => {
+        $crate::JsonPattern::Array($crate::__json_pattern!(@array [] $($array)*))
+    };
+    ({$($object:tt)*}) => {
+        $crate::JsonPattern::Object($crate::__json_pattern!(@object [] $($object)*))
+    };
+    ($value:expr) => {
+        $crate::IntoJsonPattern::into_json_pattern($value)
+    };
+}
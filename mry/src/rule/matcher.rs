@@ -1,8 +1,11 @@
 #[cfg(test)]
-use parking_lot::Mutex;
+use crate::sync::Mutex;
 #[cfg(test)]
 use std::sync::Arc;
 
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::fmt::Debug;
+
 use crate::mockable::MockableArg;
 
 /// An enum describes what arguments are expected
@@ -17,22 +20,55 @@ impl<I> Matcher<I> {
     pub(crate) fn matches(&self, input: &I) -> bool {
         self.0.matches(input)
     }
-}
 
-#[cfg(test)]
-impl<I> Matcher<I> {
-    pub(crate) fn from_match(matcher: impl Match<I> + Send + 'static) -> Self {
-        Self(Box::new(matcher))
+    pub(crate) fn explain(&self, input: &I) -> Vec<String> {
+        self.0.explain(input)
+    }
+
+    pub(crate) fn capture(&self, input: &I) {
+        self.0.capture(input)
+    }
+
+    /// Whether the other matcher has the same pattern, for detecting duplicate rules
+    pub(crate) fn same_pattern(&self, other: &Matcher<I>) -> bool {
+        self.0.same_pattern(&*other.0)
+    }
+
+    /// Rendering of the pattern for panic messages, if it has combined or named matchers
+    pub(crate) fn describe(&self) -> Option<String> {
+        self.0.describe()
+    }
+
+    /// Rendering of the actual arguments, if the pattern has matchers of `Debug` values
+    pub(crate) fn render(&self, input: &I) -> Option<String> {
+        self.0.render(input)
     }
 
+    /// Whether the pattern matches any arguments, as `mry::Any` for every argument
+    pub(crate) fn is_any(&self) -> bool {
+        self.0.is_any()
+    }
+
+    /// Matcher of any arguments
     pub(crate) fn any() -> Self {
         struct Any;
         impl<I> Match<I> for Any {
             fn matches(&self, _: &I) -> bool {
                 true
             }
+
+            fn is_any(&self) -> bool {
+                true
+            }
         }
-        Self::from_match(Any)
+        Self(Box::new(Any))
+    }
+}
+
+#[cfg(test)]
+impl<I> Matcher<I> {
+    pub(crate) fn from_match(matcher: impl Match<I> + Send + 'static) -> Self {
+        Self(Box::new(matcher))
     }
 
     pub(crate) fn never() -> Self {
@@ -48,8 +84,62 @@ impl<I> Matcher<I> {
 
 pub trait Match<I> {
     fn matches(&self, input: &I) -> bool;
+
+    #[doc(hidden)]
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        None
+    }
+
+    /// Whether the other matcher has the same pattern. Unknown matchers are never the same
+    #[doc(hidden)]
+    fn same_pattern(&self, _other: &dyn Match<I>) -> bool {
+        false
+    }
+
+    /// Describes why the input matches or not, one line per argument.
+    fn explain(&self, input: &I) -> Vec<String> {
+        if self.matches(input) {
+            vec!["matches".to_string()]
+        } else {
+            vec!["does not match".to_string()]
+        }
+    }
+
+    /// Rendering of the pattern for panic messages. Only patterns with combined or named matchers
+    /// are rendered
+    #[doc(hidden)]
+    fn describe(&self) -> Option<String> {
+        None
+    }
+
+    /// Rendering of the actual arguments for panic messages, if the pattern has matchers of
+    /// `Debug` values
+    #[doc(hidden)]
+    fn render(&self, _input: &I) -> Option<String> {
+        None
+    }
+
+    /// Whether the pattern matches any arguments, for counting calls without scanning the logs
+    #[doc(hidden)]
+    fn is_any(&self) -> bool {
+        false
+    }
+
+    /// Hands the arguments to the captors of the pattern, once its rule serves the call
+    #[doc(hidden)]
+    fn capture(&self, _input: &I) {}
 }
 
+/// Matcher of an argument.
+///
+/// `Display` and `Debug` render it as in panic messages, which is stable across releases:
+///
+/// - `any` and `never`
+/// - `eq(..)` for values compared by `PartialEq`, which are not required to be `Debug`
+/// - `eq(5)`, `gt(3)`, `near(0.5, ±0.01)` and so on for the matchers of `Debug` values
+/// - `predicate` for `mry::pred`, and the name for named predicates
+/// - `all![..]`, `any_of![..]` and `not(..)` wrapping the rendering of the inner matchers
+/// - the description of a `DescribeMatcher` for `mry::described`
 pub enum ArgMatcher<I> {
     Fn(Box<dyn Fn(&I) -> bool + Send + 'static>),
     Eq {
@@ -58,6 +148,52 @@ pub enum ArgMatcher<I> {
     },
     Any,
     Never,
+    /// Predicate rendered by its name in panic messages, such as `gt(3)`
+    Named {
+        name: String,
+        predicate: Box<dyn Fn(&I) -> bool + Send + 'static>,
+        /// Rendering of the actual arguments in panic messages, for predicates of `Debug` values
+        render: Option<fn(&I) -> String>,
+    },
+    /// Matches if all of the matchers match, built by `mry::all!`
+    All(Vec<ArgMatcher<I>>),
+    /// Matches if any of the matchers matches, built by `mry::any_of!`
+    AnyOf(Vec<ArgMatcher<I>>),
+    /// Matches if the matcher does not match, built by `mry::not`
+    Not(Box<ArgMatcher<I>>),
+    /// Matcher type of the user, built by `mry::described`
+    Described(Box<dyn DescribeMatcher<I> + Send + 'static>),
+    /// Matches anything and hands the argument to the closure when the whole rule serves the
+    /// call, built from `mry::Captor`
+    Captures(Box<dyn Fn(&I) + Send + 'static>),
+}
+
+/// Matcher type with its own description in panic messages, for domain-specific matchers
+/// reading like the business rule, such as `an adult customer`. `mry::described` makes it an
+/// `ArgMatcher`.
+pub trait DescribeMatcher<I> {
+    fn matches(&self, input: &I) -> bool;
+
+    /// Expectation text rendered in the pattern of panic messages
+    fn describe(&self) -> String;
+
+    /// Rendering of the actual argument in panic messages, such as `customer #3 aged 17`.
+    /// Arguments are rendered as `_` without it
+    fn render(&self, _input: &I) -> Option<String> {
+        None
+    }
+}
+
+impl<I> std::fmt::Display for ArgMatcher<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl<I> Debug for ArgMatcher<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.describe())
+    }
 }
 
 impl<I> ArgMatcher<I> {
@@ -65,19 +201,419 @@ impl<I> ArgMatcher<I> {
     where
         I: PartialEq + MockableArg,
     {
-        ArgMatcher::Fn(Box::new(move |input| *input == value))
+        ArgMatcher::Eq {
+            value,
+            partial_eq: PartialEq::eq,
+        }
     }
 
-    pub(crate) fn matches(&self, input: &I) -> bool {
+    /// Matches a value equal to the expected one by the comparator instead of `PartialEq`,
+    /// such as `ArgMatcher::eq_by(1.0, |a: &f64, b| (a - b).abs() < 1e-9)`.
+    pub fn eq_by(value: I, eq: fn(&I, &I) -> bool) -> Self
+    where
+        I: MockableArg,
+    {
+        ArgMatcher::Eq {
+            value,
+            partial_eq: eq,
+        }
+    }
+
+    /// Labels the matcher with a human-readable name rendered in panic messages, such as
+    /// `mry::pred(|n: &u8| n.is_multiple_of(2)).named("even number")`
+    pub fn named(self, name: impl Into<String>) -> Self
+    where
+        I: 'static,
+        Self: Send,
+    {
+        let render = match &self {
+            ArgMatcher::Named { render, .. } => *render,
+            _ => None,
+        };
+        ArgMatcher::Named {
+            name: name.into(),
+            predicate: Box::new(move |input| self.matches(input)),
+            render,
+        }
+    }
+
+    /// Whether the argument matches
+    pub fn matches(&self, input: &I) -> bool {
         match self {
             ArgMatcher::Fn(f) => f(input),
             ArgMatcher::Eq { value, partial_eq } => partial_eq(value, input),
             ArgMatcher::Any => true,
             ArgMatcher::Never => false,
+            ArgMatcher::Named { predicate, .. } => predicate(input),
+            ArgMatcher::All(matchers) => matchers.iter().all(|matcher| matcher.matches(input)),
+            ArgMatcher::AnyOf(matchers) => matchers.iter().any(|matcher| matcher.matches(input)),
+            ArgMatcher::Not(matcher) => !matcher.matches(input),
+            ArgMatcher::Described(matcher) => matcher.matches(input),
+            ArgMatcher::Captures(_) => true,
+        }
+    }
+
+    /// Hands the argument to the captors of the matcher, once its rule serves the call
+    pub(crate) fn capture(&self, input: &I) {
+        match self {
+            ArgMatcher::Captures(capture) => capture(input),
+            ArgMatcher::All(matchers) => matchers.iter().for_each(|matcher| matcher.capture(input)),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn same_pattern(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ArgMatcher::Any, ArgMatcher::Any) | (ArgMatcher::Never, ArgMatcher::Never) => true,
+            (
+                ArgMatcher::Eq { value, partial_eq },
+                ArgMatcher::Eq {
+                    value: other_value, ..
+                },
+            ) => partial_eq(value, other_value),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn explain(&self, input: &I) -> &'static str {
+        match (self, self.matches(input)) {
+            (ArgMatcher::Fn(_), true) => "satisfies the predicate",
+            (ArgMatcher::Fn(_), false) => "does not satisfy the predicate",
+            (ArgMatcher::Eq { .. }, true) => "equals the expected value",
+            (ArgMatcher::Eq { .. }, false) => "does not equal the expected value",
+            (ArgMatcher::Any, _) => "matches anything",
+            (ArgMatcher::Never, _) => "matches nothing",
+            (ArgMatcher::Named { .. }, true) => "satisfies the named predicate",
+            (ArgMatcher::Named { .. }, false) => "does not satisfy the named predicate",
+            (ArgMatcher::All(_) | ArgMatcher::AnyOf(_) | ArgMatcher::Not(_), true) => {
+                "satisfies the combined matchers"
+            }
+            (ArgMatcher::All(_) | ArgMatcher::AnyOf(_) | ArgMatcher::Not(_), false) => {
+                "does not satisfy the combined matchers"
+            }
+            (ArgMatcher::Described(_), true) => "satisfies the described matcher",
+            (ArgMatcher::Described(_), false) => "does not satisfy the described matcher",
+            (ArgMatcher::Captures(_), _) => "matches anything and captures it",
+        }
+    }
+
+    /// Whether the matcher is rendered by `describe` in panic messages
+    pub(crate) fn is_described(&self) -> bool {
+        matches!(
+            self,
+            ArgMatcher::Named { .. }
+                | ArgMatcher::All(_)
+                | ArgMatcher::AnyOf(_)
+                | ArgMatcher::Not(_)
+                | ArgMatcher::Described(_)
+        )
+    }
+
+    /// Rendering such as `all![gt(3), lt(10)]`. Values compared by `PartialEq` are rendered as
+    /// `eq(..)`, since arguments are not required to be `Debug`
+    pub(crate) fn describe(&self) -> String {
+        let join = |matchers: &[ArgMatcher<I>]| {
+            matchers
+                .iter()
+                .map(ArgMatcher::describe)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            ArgMatcher::Fn(_) => "predicate".to_string(),
+            ArgMatcher::Eq { .. } => "eq(..)".to_string(),
+            ArgMatcher::Any => "any".to_string(),
+            ArgMatcher::Never => "never".to_string(),
+            ArgMatcher::Named { name, .. } => name.clone(),
+            ArgMatcher::All(matchers) => format!("all![{}]", join(matchers)),
+            ArgMatcher::AnyOf(matchers) => format!("any_of![{}]", join(matchers)),
+            ArgMatcher::Not(matcher) => format!("not({})", matcher.describe()),
+            ArgMatcher::Described(matcher) => matcher.describe(),
+            ArgMatcher::Captures(_) => "captor".to_string(),
+        }
+    }
+
+    /// Rendering of the actual argument by the first matcher of a `Debug` value
+    pub(crate) fn render(&self, input: &I) -> Option<String> {
+        match self {
+            ArgMatcher::Named {
+                render: Some(render),
+                ..
+            } => Some(render(input)),
+            ArgMatcher::All(matchers) | ArgMatcher::AnyOf(matchers) => {
+                matchers.iter().find_map(|matcher| matcher.render(input))
+            }
+            ArgMatcher::Not(matcher) => matcher.render(input),
+            ArgMatcher::Described(matcher) => matcher.render(input),
+            _ => None,
         }
     }
 }
 
+/// `ArgMatcher::Any` with an explicit type, for places where the type of `mry::Any` cannot be
+/// inferred, such as `let count = mry::any::<usize>();`.
+pub fn any<I>() -> ArgMatcher<I> {
+    ArgMatcher::Any
+}
+
+/// `ArgMatcher::Never` with an explicit type, which matches no value.
+/// A rule with it never serves a call, so calls of the shape are forbidden unless another rule
+/// accepts them.
+pub fn never<I>() -> ArgMatcher<I> {
+    ArgMatcher::Never
+}
+
+/// Matches a value equal to the expected one, rendered as `eq(5)` in panic messages
+pub fn eq<I: PartialEq + Debug + MockableArg>(value: I) -> ArgMatcher<I> {
+    named_debug(format!("eq({:?})", value), move |input| *input == value)
+}
+
+/// Matches a value greater than the bound, such as `mry::gt(3)`
+pub fn gt<I: PartialOrd + Debug + MockableArg>(bound: I) -> ArgMatcher<I> {
+    named_debug(format!("gt({:?})", bound), move |input| *input > bound)
+}
+
+/// Matches a value greater than or equal to the bound
+pub fn ge<I: PartialOrd + Debug + MockableArg>(bound: I) -> ArgMatcher<I> {
+    named_debug(format!("ge({:?})", bound), move |input| *input >= bound)
+}
+
+/// Matches a value less than the bound, such as `mry::lt(10)`
+pub fn lt<I: PartialOrd + Debug + MockableArg>(bound: I) -> ArgMatcher<I> {
+    named_debug(format!("lt({:?})", bound), move |input| *input < bound)
+}
+
+/// Matches a value less than or equal to the bound
+pub fn le<I: PartialOrd + Debug + MockableArg>(bound: I) -> ArgMatcher<I> {
+    named_debug(format!("le({:?})", bound), move |input| *input <= bound)
+}
+
+/// Matches a float within the tolerance of the expected value, such as `mry::near(1.0, 1e-6)`,
+/// rendered as `near(1.0, ±1e-6)` with the actual arguments in panic messages
+pub fn near<F: Into<f64> + Copy + Debug + MockableArg>(expected: F, tolerance: F) -> ArgMatcher<F> {
+    named_debug(
+        format!("near({:?}, ±{:?})", expected, tolerance),
+        move |input: &F| ((*input).into() - expected.into()).abs() <= tolerance.into(),
+    )
+}
+
+/// Matches an argument satisfying the predicate, such as `mry::pred(|n: &u8| n.is_multiple_of(2))`.
+/// `named` gives it a name rendered in panic messages
+pub fn pred<I>(predicate: impl Fn(&I) -> bool + Send + 'static) -> ArgMatcher<I> {
+    ArgMatcher::Fn(Box::new(predicate))
+}
+
+/// Predicate rendered by the name in panic messages
+pub fn named<I>(
+    name: impl Into<String>,
+    predicate: impl Fn(&I) -> bool + Send + 'static,
+) -> ArgMatcher<I> {
+    ArgMatcher::Named {
+        name: name.into(),
+        predicate: Box::new(predicate),
+        render: None,
+    }
+}
+
+/// Matches by the matcher type of the user, rendered by its description in panic messages
+pub fn described<I>(matcher: impl DescribeMatcher<I> + Send + 'static) -> ArgMatcher<I> {
+    ArgMatcher::Described(Box::new(matcher))
+}
+
+/// Named predicate of a `Debug` value, which also renders the actual arguments
+fn named_debug<I: Debug>(
+    name: String,
+    predicate: impl Fn(&I) -> bool + Send + 'static,
+) -> ArgMatcher<I> {
+    ArgMatcher::Named {
+        name,
+        predicate: Box::new(predicate),
+        render: Some(|input| crate::truncate::debug(input)),
+    }
+}
+
+/// Matches if the matcher does not match, such as `mry::not(mry::eq(5))`
+pub fn not<I>(matcher: impl Into<ArgMatcher<I>>) -> ArgMatcher<I> {
+    ArgMatcher::Not(Box::new(matcher.into()))
+}
+
+/// Matches `Some` of a value the inner matcher matches, such as `mry::some(mry::gt(0))`
+pub fn some<T: Send + 'static>(matcher: impl Into<ArgMatcher<T>>) -> ArgMatcher<Option<T>> {
+    let matcher = matcher.into();
+    named(
+        format!("some({})", matcher.describe()),
+        move |input: &Option<T>| input.as_ref().is_some_and(|value| matcher.matches(value)),
+    )
+}
+
+/// Matches `None`, such as `mry::none::<u8>()`
+pub fn none<T>() -> ArgMatcher<Option<T>> {
+    named("none()", |input: &Option<T>| input.is_none())
+}
+
+/// Matches `Ok` of a value the inner matcher matches, such as `mry::ok(mry::Any)`
+pub fn ok<T: Send + 'static, E>(matcher: impl Into<ArgMatcher<T>>) -> ArgMatcher<Result<T, E>> {
+    let matcher = matcher.into();
+    named(
+        format!("ok({})", matcher.describe()),
+        move |input: &Result<T, E>| input.as_ref().is_ok_and(|value| matcher.matches(value)),
+    )
+}
+
+/// Matches `Err` of an error the inner matcher matches, such as `mry::err("not found")`
+pub fn err<T, E: Send + 'static>(matcher: impl Into<ArgMatcher<E>>) -> ArgMatcher<Result<T, E>> {
+    let matcher = matcher.into();
+    named(
+        format!("err({})", matcher.describe()),
+        move |input: &Result<T, E>| input.as_ref().is_err_and(|error| matcher.matches(error)),
+    )
+}
+
+/// Arguments searched by `mry::contains`, which are collections of the items and strings
+pub trait Contains<N> {
+    fn contains_needle(&self, needle: &N) -> bool;
+}
+
+impl<T: PartialEq> Contains<T> for Vec<T> {
+    fn contains_needle(&self, needle: &T) -> bool {
+        self.contains(needle)
+    }
+}
+
+impl<T: PartialEq> Contains<T> for VecDeque<T> {
+    fn contains_needle(&self, needle: &T) -> bool {
+        self.contains(needle)
+    }
+}
+
+impl<T: PartialEq, S> Contains<T> for HashSet<T, S> {
+    fn contains_needle(&self, needle: &T) -> bool {
+        self.iter().any(|item| item == needle)
+    }
+}
+
+impl<T: PartialEq> Contains<T> for BTreeSet<T> {
+    fn contains_needle(&self, needle: &T) -> bool {
+        self.iter().any(|item| item == needle)
+    }
+}
+
+impl<N: AsRef<str>> Contains<N> for String {
+    fn contains_needle(&self, needle: &N) -> bool {
+        self.contains(needle.as_ref())
+    }
+}
+
+/// Matches a collection such as `Vec` or `HashSet` containing the item, such as
+/// `mry::contains(3)`, or a string containing the substring, such as `mry::contains("error")`
+pub fn contains<C, N>(needle: N) -> ArgMatcher<C>
+where
+    C: Contains<N>,
+    N: Debug + MockableArg,
+{
+    named(format!("contains({:?})", needle), move |input: &C| {
+        input.contains_needle(&needle)
+    })
+}
+
+/// Matches a string starting with the prefix, such as `mry::starts_with("GET ")`
+pub fn starts_with(prefix: impl Into<String>) -> ArgMatcher<String> {
+    let prefix = prefix.into();
+    named_debug(
+        format!("starts_with({:?})", prefix),
+        move |input: &String| input.starts_with(&prefix),
+    )
+}
+
+/// Matches a string ending with the suffix, such as `mry::ends_with(".json")`
+pub fn ends_with(suffix: impl Into<String>) -> ArgMatcher<String> {
+    let suffix = suffix.into();
+    named_debug(format!("ends_with({:?})", suffix), move |input: &String| {
+        input.ends_with(&suffix)
+    })
+}
+
+/// Matches a string equal to the expected one ignoring case, such as `mry::eq_ignore_case("HELLO")`
+pub fn eq_ignore_case(expected: impl Into<String>) -> ArgMatcher<String> {
+    let expected = expected.into();
+    let lowercase = expected.to_lowercase();
+    named_debug(
+        format!("eq_ignore_case({:?})", expected),
+        move |input: &String| input.to_lowercase() == lowercase,
+    )
+}
+
+/// Matches a string equal to the expected one with runs of whitespace collapsed and leading and
+/// trailing whitespace trimmed, such as `mry::eq_ignoring_whitespace("SELECT * FROM cats")`
+pub fn eq_ignoring_whitespace(expected: impl Into<String>) -> ArgMatcher<String> {
+    let expected = expected.into();
+    let normalized = collapse_whitespace(&expected);
+    named_debug(
+        format!("eq_ignoring_whitespace({:?})", expected),
+        move |input: &String| collapse_whitespace(input) == normalized,
+    )
+}
+
+fn collapse_whitespace(string: &str) -> String {
+    string.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Matches a collection with the number of items, such as `mry::has_len(3)`
+pub fn has_len<C>(len: usize) -> ArgMatcher<C>
+where
+    for<'a> &'a C: IntoIterator,
+{
+    named(format!("has_len({})", len), move |input: &C| {
+        input.into_iter().count() == len
+    })
+}
+
+/// Matches an empty collection
+pub fn is_empty<C>() -> ArgMatcher<C>
+where
+    for<'a> &'a C: IntoIterator,
+{
+    named("is_empty()", |input: &C| input.into_iter().next().is_none())
+}
+
+/// Matches a collection with the same items in any order, such as `mry::unordered_eq([2, 1])`.
+/// Duplicated items are counted
+pub fn unordered_eq<C, T>(items: impl IntoIterator<Item = T>) -> ArgMatcher<C>
+where
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+    T: PartialEq + Debug + MockableArg,
+{
+    let items: Vec<T> = items.into_iter().collect();
+    named(format!("unordered_eq({:?})", items), move |input: &C| {
+        let mut unmatched: Vec<&T> = items.iter().collect();
+        input.into_iter().all(
+            |element| match unmatched.iter().position(|item| *item == element) {
+                Some(index) => {
+                    unmatched.swap_remove(index);
+                    true
+                }
+                None => false,
+            },
+        ) && unmatched.is_empty()
+    })
+}
+
+/// Matches if all of the matchers match, such as `mry::all![mry::gt(3), mry::lt(10)]`
+#[macro_export]
+macro_rules! all {
+    ($($matcher:expr),* $(,)?) => {
+        $crate::ArgMatcher::All(::std::vec![$(::core::convert::Into::into($matcher)),*])
+    };
+}
+
+/// Matches if any of the matchers matches, such as `mry::any_of![1, mry::gt(10)]`
+#[macro_export]
+macro_rules! any_of {
+    ($($matcher:expr),* $(,)?) => {
+        $crate::ArgMatcher::AnyOf(::std::vec![$(::core::convert::Into::into($matcher)),*])
+    };
+}
+
 impl<I: PartialEq + MockableArg> From<I> for ArgMatcher<I> {
     fn from(value: I) -> Self {
         ArgMatcher::new_eq(value)
@@ -117,6 +653,206 @@ mod tests {
         assert!(!matcher.matches(&"B".to_string()));
     }
 
+    #[test]
+    fn typed_any_and_never() {
+        assert!(any::<u8>().matches(&3));
+        assert!(!never::<u8>().matches(&3));
+        assert_eq!(never::<u8>().explain(&3), "matches nothing");
+    }
+
+    #[test]
+    fn combinators() {
+        let matcher: ArgMatcher<u8> = crate::all![gt(3), lt(10), not(eq(5))];
+        assert!(matcher.matches(&4));
+        assert!(!matcher.matches(&5));
+        assert!(!matcher.matches(&10));
+        assert_eq!(matcher.describe(), "all![gt(3), lt(10), not(eq(5))]");
+
+        let matcher: ArgMatcher<u8> = crate::any_of![1, ge(10)];
+        assert!(matcher.matches(&1));
+        assert!(matcher.matches(&10));
+        assert!(!matcher.matches(&2));
+        assert_eq!(matcher.describe(), "any_of![eq(..), ge(10)]");
+        assert_eq!(
+            matcher.explain(&2),
+            "does not satisfy the combined matchers"
+        );
+    }
+
+    #[test]
+    fn names_any_matcher() {
+        let matcher: ArgMatcher<u8> =
+            pred(|n: &u8| *n > 0 && n.is_multiple_of(2)).named("positive even number");
+        assert!(matcher.matches(&2));
+        assert!(!matcher.matches(&3));
+        assert_eq!(matcher.describe(), "positive even number");
+
+        let matcher = crate::all![gt(1u8), lt(5)].named("small");
+        assert!(matcher.matches(&3));
+        assert_eq!(matcher.describe(), "small");
+
+        let matcher = gt(1u8).named("more than one");
+        assert_eq!(matcher.render(&3), Some("3".to_string()));
+    }
+
+    #[test]
+    fn described_matchers() {
+        struct Adult;
+
+        impl DescribeMatcher<u8> for Adult {
+            fn matches(&self, age: &u8) -> bool {
+                *age >= 18
+            }
+
+            fn describe(&self) -> String {
+                "an adult".to_string()
+            }
+
+            fn render(&self, age: &u8) -> Option<String> {
+                Some(format!("aged {}", age))
+            }
+        }
+
+        let matcher = described(Adult);
+        assert!(matcher.matches(&20));
+        assert!(!matcher.matches(&17));
+        assert_eq!(matcher.describe(), "an adult");
+        assert_eq!(matcher.render(&17), Some("aged 17".to_string()));
+        assert_eq!(
+            crate::all![described(Adult), lt(65)].describe(),
+            "all![an adult, lt(65)]"
+        );
+    }
+
+    #[test]
+    fn formats_as_panic_messages() {
+        let matcher: ArgMatcher<u8> = crate::any_of![1, not(gt(3))];
+        assert_eq!(matcher.to_string(), "any_of![eq(..), not(gt(3))]");
+        assert_eq!(format!("{:?}", matcher), "any_of![eq(..), not(gt(3))]");
+        assert_eq!(any::<u8>().to_string(), "any");
+    }
+
+    #[test]
+    fn near_floats() {
+        let matcher = near(1.0, 1e-6);
+        assert!(matcher.matches(&(1.0 + 1e-7)));
+        assert!(!matcher.matches(&1.1));
+        assert!(!matcher.matches(&f64::NAN));
+        assert_eq!(matcher.describe(), "near(1.0, ±1e-6)");
+        assert_eq!(matcher.render(&1.1), Some("1.1".to_string()));
+        assert!(near(0.5f32, 0.1).matches(&0.55));
+    }
+
+    #[test]
+    fn renders_arguments_of_debug_matchers() {
+        let matcher: Matcher<(u8, u8)> = Matcher::from_match((ArgMatcher::Any, gt(3)));
+        assert_eq!(matcher.render(&(1, 2)), Some("(_, 2)".to_string()));
+
+        let plain: Matcher<(u8,)> = Matcher::from_match((named("odd", |n: &u8| n % 2 == 1),));
+        assert_eq!(plain.render(&(1,)), None);
+    }
+
+    #[test]
+    fn options_and_results() {
+        let matcher: ArgMatcher<Option<u8>> = some(gt(0));
+        assert!(matcher.matches(&Some(1)));
+        assert!(!matcher.matches(&Some(0)));
+        assert!(!matcher.matches(&None));
+        assert_eq!(matcher.describe(), "some(gt(0))");
+        assert!(none::<u8>().matches(&None));
+        assert!(!none().matches(&Some(1)));
+
+        let matcher: ArgMatcher<Result<u8, String>> = ok(ArgMatcher::Any);
+        assert!(matcher.matches(&Ok(1)));
+        assert!(!matcher.matches(&Err("a".into())));
+        assert_eq!(matcher.describe(), "ok(any)");
+
+        let matcher: ArgMatcher<Result<u8, String>> = err("a");
+        assert!(matcher.matches(&Err("a".into())));
+        assert!(!matcher.matches(&Err("b".into())));
+        assert!(!matcher.matches(&Ok(1)));
+        assert_eq!(matcher.describe(), "err(eq(..))");
+    }
+
+    #[test]
+    fn collections() {
+        let matcher: ArgMatcher<Vec<u8>> = contains(3);
+        assert!(matcher.matches(&vec![1, 3]));
+        assert!(!matcher.matches(&vec![1, 2]));
+        assert_eq!(matcher.describe(), "contains(3)");
+
+        let matcher: ArgMatcher<HashSet<u8>> = has_len(2);
+        assert!(matcher.matches(&HashSet::from([1, 2])));
+        assert!(!matcher.matches(&HashSet::from([1])));
+        assert_eq!(matcher.describe(), "has_len(2)");
+
+        let matcher: ArgMatcher<Vec<u8>> = is_empty();
+        assert!(matcher.matches(&vec![]));
+        assert!(!matcher.matches(&vec![1]));
+        assert_eq!(matcher.describe(), "is_empty()");
+    }
+
+    #[test]
+    fn strings() {
+        let matcher: ArgMatcher<String> = crate::all![starts_with("GET "), ends_with(".json")];
+        assert!(matcher.matches(&"GET /a.json".to_string()));
+        assert!(!matcher.matches(&"POST /a.json".to_string()));
+        assert!(!matcher.matches(&"GET /a.html".to_string()));
+        assert_eq!(
+            matcher.describe(),
+            "all![starts_with(\"GET \"), ends_with(\".json\")]"
+        );
+
+        let matcher: ArgMatcher<String> = contains("rr");
+        assert!(matcher.matches(&"error".to_string()));
+        assert!(!matcher.matches(&"warning".to_string()));
+        assert_eq!(matcher.describe(), "contains(\"rr\")");
+    }
+
+    #[test]
+    fn unordered_eq_counts_duplicates() {
+        let matcher: ArgMatcher<Vec<u8>> = unordered_eq([1, 2, 1]);
+        assert!(matcher.matches(&vec![2, 1, 1]));
+        assert!(!matcher.matches(&vec![2, 1, 2]));
+        assert!(!matcher.matches(&vec![1, 2]));
+        assert!(!matcher.matches(&vec![1, 1, 2, 1]));
+        assert_eq!(matcher.describe(), "unordered_eq([1, 2, 1])");
+    }
+
+    #[test]
+    fn describes_patterns_with_combinators() {
+        let plain: Matcher<(u8, u8)> = Matcher::from_match((1.into(), ArgMatcher::Any));
+        assert_eq!(plain.describe(), None);
+
+        let combined: Matcher<(u8, u8)> = Matcher::from_match((1.into(), le(3)));
+        assert_eq!(combined.describe(), Some("(eq(..), le(3))".to_string()));
+    }
+
+    #[test]
+    fn eq_by() {
+        let matcher = ArgMatcher::eq_by(1.0, |a: &f64, b| (a - b).abs() < 0.01);
+        assert!(matcher.matches(&1.001));
+        assert!(!matcher.matches(&1.1));
+        assert_eq!(matcher.explain(&1.001), "equals the expected value");
+    }
+
+    #[test]
+    fn explain_each_argument() {
+        let matcher: Matcher<(u8, u16, u32)> = Matcher::from_match((
+            3u8.into(),
+            ArgMatcher::Fn(Box::new(|n: &u16| *n > 2)),
+            ArgMatcher::Any,
+        ));
+        assert_eq!(
+            matcher.explain(&(1, 3, 0)),
+            vec![
+                "argument #1: does not equal the expected value",
+                "argument #2: satisfies the predicate",
+                "argument #3: matches anything",
+            ]
+        );
+    }
+
     #[test]
     fn matcher_two_values() {
         let matcher: Matcher<(u8, u16)> = Matcher::from_match((3u8.into(), 2u16.into()));
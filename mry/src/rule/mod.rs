@@ -1,22 +1,50 @@
 mod behavior;
+#[cfg(feature = "json")]
+mod json;
 mod matcher;
 
 use std::sync::Arc;
 
+use crate::sync::Mutex;
 pub use behavior::*;
+#[cfg(feature = "json")]
+pub use json::*;
 pub use matcher::*;
-use parking_lot::Mutex;
 
 pub(crate) struct Rule<I, O> {
     pub matcher: Arc<Mutex<Matcher<I>>>,
     pub behavior: Behavior<I, O>,
+    /// How many calls this rule has served
+    pub hits: usize,
+    /// Name of the thread configuring the rule, which is the test under `cargo test`
+    pub test: Option<String>,
+    /// Token of the `#[mry::test]` configuring the rule
+    pub token: Option<u64>,
+    /// Scope of `mry::scope()` configuring the rule, which removes it when dropped
+    pub scope: Option<usize>,
 }
 
 impl<I, O> Rule<I, O> {
+    pub fn new(matcher: Arc<Mutex<Matcher<I>>>, behavior: Behavior<I, O>) -> Self {
+        Self {
+            matcher,
+            behavior,
+            hits: 0,
+            test: std::thread::current().name().map(ToString::to_string),
+            token: crate::test_token::current(),
+            scope: crate::scope::current(),
+        }
+    }
+
     pub fn matches(&self, input: &I) -> bool {
         self.matcher.lock().matches(input)
     }
     pub fn call_behavior(&mut self, input: &I) -> Output<O> {
-        self.behavior.called(input)
+        let output = self.behavior.called(input);
+        if !matches!(output, Output::NotFound) {
+            self.hits += 1;
+            self.matcher.lock().capture(input);
+        }
+        output
     }
 }
@@ -0,0 +1,103 @@
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Removes the rules of the scope from a mock
+type Remover = Box<dyn FnOnce()>;
+
+struct Entered {
+    id: usize,
+    /// Addresses of the mocks touched in the scope and their removers
+    mocks: Vec<(usize, Remover)>,
+}
+
+thread_local! {
+    /// Scopes alive on this thread, the innermost last
+    static SCOPES: RefCell<Vec<Entered>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Guard returned by `mry::scope()`, which removes every rule configured on this thread while it
+/// lives when dropped
+#[must_use = "the scope removes its rules when dropped"]
+pub struct Scope {
+    id: usize,
+}
+
+/// Starts a scope of rules. Every rule configured on this thread until the returned guard drops,
+/// on any object or static function, is removed on the drop. Scopes nest, and rules belong to the
+/// innermost one.
+///
+/// ```
+/// #[mry::mry]
+/// #[derive(Default)]
+/// struct Cat {}
+///
+/// #[mry::mry]
+/// impl Cat {
+///     fn meow(&self) -> String {
+///         "meow".into()
+///     }
+/// }
+///
+/// let mut cat = Cat::default();
+/// cat.mock_meow().returns("Called".into());
+/// {
+///     let _scope = mry::scope();
+///     cat.mock_meow().returns("Scoped".into());
+///     assert_eq!(cat.meow(), "Called");
+/// }
+/// cat.mock_meow().assert_called(1);
+/// ```
+pub fn scope() -> Scope {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    SCOPES.with(|scopes| {
+        scopes.borrow_mut().push(Entered {
+            id,
+            mocks: Vec::new(),
+        })
+    });
+    Scope { id }
+}
+
+/// Id of the innermost scope, which owns the rules configured now
+pub(crate) fn current() -> Option<usize> {
+    SCOPES.with(|scopes| scopes.borrow().last().map(|scope| scope.id))
+}
+
+/// Lets the innermost scope remove its rules from the mock at `address` with `remover`
+pub(crate) fn register(address: usize, remover: impl FnOnce(usize) + 'static) {
+    SCOPES.with(|scopes| {
+        let mut scopes = scopes.borrow_mut();
+        let Some(scope) = scopes.last_mut() else {
+            return;
+        };
+        if scope
+            .mocks
+            .iter()
+            .any(|(registered, _)| *registered == address)
+        {
+            return;
+        }
+        let id = scope.id;
+        scope.mocks.push((address, Box::new(move || remover(id))));
+    });
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let Some(entered) = SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            let index = scopes.iter().position(|scope| scope.id == self.id)?;
+            Some(scopes.remove(index))
+        }) else {
+            return;
+        };
+        // Removed outside the borrow, as `Drop` impls of the rules may configure other mocks
+        for (_, remove) in entered.mocks {
+            remove();
+        }
+    }
+}
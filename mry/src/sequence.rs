@@ -0,0 +1,213 @@
+use std::sync::{Arc, Weak};
+
+use crate::sync::Mutex;
+
+use crate::Matcher;
+
+#[derive(Default)]
+pub(crate) struct SequenceState {
+    steps: Vec<&'static str>,
+    position: usize,
+}
+
+/// Sequences the methods of an object take part in, shared by its mocks so that the calls of
+/// the methods without steps are checked too
+pub(crate) type ObjectSequences = Arc<Mutex<Vec<Weak<Mutex<SequenceState>>>>>;
+
+/// Expected order of calls across mocks, usually created by `mry::sequence!`.
+/// A call out of order, or a call of another method of the objects in the sequence, fails
+/// immediately, and missing calls fail when this is dropped.
+#[must_use]
+#[derive(Default)]
+pub struct Sequence(Arc<Mutex<SequenceState>>);
+
+impl Sequence {
+    pub(crate) fn push_step(&self, name: &'static str) -> (Arc<Mutex<SequenceState>>, usize) {
+        let mut state = self.0.lock();
+        state.steps.push(name);
+        (self.0.clone(), state.steps.len() - 1)
+    }
+
+    pub(crate) fn downgrade(&self) -> Weak<Mutex<SequenceState>> {
+        Arc::downgrade(&self.0)
+    }
+
+    /// Assert all calls in the sequence are done.
+    pub fn assert_done(&self) {
+        let state = self.0.lock();
+        if let Some(name) = state.steps.get(state.position) {
+            panic!(
+                "Expected the sequence to be completed, but call #{} ({}) is missing",
+                state.position + 1,
+                name,
+            );
+        }
+    }
+}
+
+impl Drop for Sequence {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            self.assert_done();
+        }
+    }
+}
+
+pub(crate) struct SequenceStep<I> {
+    pub state: Arc<Mutex<SequenceState>>,
+    pub index: usize,
+    pub matcher: Arc<Mutex<Matcher<I>>>,
+}
+
+/// Advances every sequence the mock takes part in, or panics if the call is not the next step.
+/// Panics as well if a sequence of the object is running but the mock has no step in it.
+pub(crate) fn advance<I>(
+    steps: &[SequenceStep<I>],
+    sequences: &ObjectSequences,
+    name: &str,
+    input: &I,
+) {
+    let mut checked: Vec<&Arc<Mutex<SequenceState>>> = Vec::new();
+    for step in steps {
+        if checked.iter().any(|state| Arc::ptr_eq(state, &step.state)) {
+            continue;
+        }
+        checked.push(&step.state);
+        let mut state = step.state.lock();
+        let position = state.position;
+        let Some(expected) = state.steps.get(position) else {
+            panic!("{} was called after the end of the sequence", name);
+        };
+        let is_next = steps.iter().any(|other| {
+            Arc::ptr_eq(&other.state, &step.state)
+                && other.index == position
+                && other.matcher.lock().matches(input)
+        });
+        if !is_next {
+            if *expected == name {
+                panic!(
+                    "Expected call #{} of the sequence to be {}, but it was called with other arguments",
+                    position + 1,
+                    expected,
+                );
+            }
+            panic!(
+                "Expected call #{} of the sequence to be {}, but {} was called",
+                position + 1,
+                expected,
+                name,
+            );
+        }
+        state.position += 1;
+    }
+    for sequence in sequences.lock().iter().filter_map(Weak::upgrade) {
+        if checked.iter().any(|state| Arc::ptr_eq(state, &sequence)) {
+            continue;
+        }
+        let state = sequence.lock();
+        if let Some(expected) = state.steps.get(state.position) {
+            panic!(
+                "Expected call #{} of the sequence to be {}, but {} was called",
+                state.position + 1,
+                expected,
+                name,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(sequence: &Sequence, name: &'static str, matcher: Matcher<u8>) -> SequenceStep<u8> {
+        let (state, index) = sequence.push_step(name);
+        SequenceStep {
+            state,
+            index,
+            matcher: matcher.wrapped(),
+        }
+    }
+
+    fn no_sequences() -> ObjectSequences {
+        Default::default()
+    }
+
+    #[test]
+    fn advances_in_order() {
+        let sequence = Sequence::default();
+        let open = [step(&sequence, "open", Matcher::any())];
+        let read = [step(&sequence, "read", Matcher::any())];
+
+        advance(&open, &no_sequences(), "open", &0);
+        advance(&read, &no_sequences(), "read", &0);
+        sequence.assert_done();
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected call #1 of the sequence to be open, but read was called")]
+    fn panics_on_wrong_order() {
+        let sequence = Sequence::default();
+        let _open = [step(&sequence, "open", Matcher::any())];
+        let read = [step(&sequence, "read", Matcher::any())];
+
+        advance(&read, &no_sequences(), "read", &0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected call #1 of the sequence to be read, but it was called with other arguments"
+    )]
+    fn panics_on_other_arguments() {
+        let sequence = Sequence::default();
+        let read = [step(&sequence, "read", Matcher::new_eq(1))];
+
+        advance(&read, &no_sequences(), "read", &2);
+    }
+
+    #[test]
+    #[should_panic(expected = "read was called after the end of the sequence")]
+    fn panics_after_end() {
+        let sequence = Sequence::default();
+        let read = [step(&sequence, "read", Matcher::any())];
+
+        advance(&read, &no_sequences(), "read", &0);
+        advance(&read, &no_sequences(), "read", &0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected call #2 of the sequence to be read, but close was called")]
+    fn panics_on_other_method_of_object() {
+        let sequence = Sequence::default();
+        let open = [step(&sequence, "open", Matcher::any())];
+        let _read = [step(&sequence, "read", Matcher::any())];
+        let sequences = no_sequences();
+        sequences.lock().push(sequence.downgrade());
+
+        advance(&open, &sequences, "open", &0);
+        advance::<u8>(&[], &sequences, "close", &0);
+    }
+
+    #[test]
+    fn allows_other_method_after_end() {
+        let sequence = Sequence::default();
+        let open = [step(&sequence, "open", Matcher::any())];
+        let sequences = no_sequences();
+        sequences.lock().push(sequence.downgrade());
+
+        advance(&open, &sequences, "open", &0);
+        advance::<u8>(&[], &sequences, "close", &0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected the sequence to be completed, but call #2 (read) is missing"
+    )]
+    fn panics_on_missing_call() {
+        let sequence = Sequence::default();
+        let open = [step(&sequence, "open", Matcher::any())];
+        let _read = [step(&sequence, "read", Matcher::any())];
+
+        advance(&open, &no_sequences(), "open", &0);
+    }
+}
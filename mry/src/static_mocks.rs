@@ -1,11 +1,24 @@
+use crate::sync::Mutex;
 use crate::{
-    mock::Mock,
     mockable::{MockableArg, MockableRet},
+    mocks::SharedMock,
     MockGetter, Mocks,
 };
+#[cfg(feature = "async")]
 use async_recursion::async_recursion;
-use parking_lot::Mutex;
-use std::{any::TypeId, collections::HashMap, future::Future, ops::Deref, pin::Pin, sync::Arc};
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::HashMap,
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
+#[cfg(feature = "async")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 thread_local! {
     pub static STATIC_MOCKS: Arc<Mutex<StaticMocks>> = Arc::new(Mutex::new(StaticMocks::default()));
@@ -15,9 +28,81 @@ thread_local! {
     pub static STATIC_MOCK_LOCKS: Mutex<HashMap<TypeId, Arc<Mutex<()>>>> = Mutex::new(HashMap::new());
 }
 
+thread_local! {
+    static ISOLATION_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+struct IsolatedMocks {
+    mocks: Arc<Mutex<StaticMocks>>,
+    scopes: usize,
+}
+
+/// Static mocks of `isolate` scopes keyed by the id
+static ISOLATED_MOCKS: OnceLock<Mutex<HashMap<String, IsolatedMocks>>> = OnceLock::new();
+
+fn isolated_mocks() -> &'static Mutex<HashMap<String, IsolatedMocks>> {
+    ISOLATED_MOCKS.get_or_init(Default::default)
+}
+
+fn is_isolated() -> bool {
+    ISOLATION_ID.with(|id| id.borrow().is_some())
+}
+
 #[doc(hidden)]
 pub fn get_static_mocks() -> Arc<Mutex<StaticMocks>> {
-    STATIC_MOCKS.with(Clone::clone)
+    ISOLATION_ID
+        .with(|id| {
+            id.borrow()
+                .as_ref()
+                .map(|id| isolated_mocks().lock()[id].mocks.clone())
+        })
+        .unwrap_or_else(|| STATIC_MOCKS.with(Clone::clone))
+}
+
+struct IsolationGuard {
+    id: String,
+    previous: Option<String>,
+}
+
+impl Drop for IsolationGuard {
+    fn drop(&mut self) {
+        ISOLATION_ID.with(|id| *id.borrow_mut() = self.previous.take());
+        let mut isolated = isolated_mocks().lock();
+        let entry = isolated.get_mut(&self.id).unwrap();
+        entry.scopes -= 1;
+        if entry.scopes == 0 {
+            isolated.remove(&self.id);
+        }
+    }
+}
+
+/// Runs the function with static mocks isolated for the current test,
+/// identified by the name of the current thread which is the test name under `cargo test`.
+/// Static functions can be mocked without `mry::lock` inside, and the mocks are removed after that.
+pub fn isolate<T>(f: impl FnOnce() -> T) -> T {
+    let thread = std::thread::current();
+    let id = thread
+        .name()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| format!("{:?}", thread.id()));
+    isolate_as(id, f)
+}
+
+/// Same as `isolate`, but with an explicit id.
+/// Threads spawned by a test can share its static mocks by using the same id.
+pub fn isolate_as<T>(id: impl Into<String>, f: impl FnOnce() -> T) -> T {
+    let id = id.into();
+    isolated_mocks()
+        .lock()
+        .entry(id.clone())
+        .or_insert_with(|| IsolatedMocks {
+            mocks: Default::default(),
+            scopes: 0,
+        })
+        .scopes += 1;
+    let previous = ISOLATION_ID.with(|current| current.replace(Some(id.clone())));
+    let _guard = IsolationGuard { id, previous };
+    f()
 }
 
 #[doc(hidden)]
@@ -26,11 +111,27 @@ pub fn static_record_call_and_find_mock_output<I: MockableArg, O: MockableRet>(
     name: &'static str,
     input: I,
 ) -> Option<O> {
-    STATIC_MOCKS.with(|mocks| {
-        mocks
-            .lock()
-            .record_call_and_find_mock_output(key, name, input)
-    })
+    let mock = get_static_mocks().lock().get_if_locked::<I, O>(key, name)?;
+    let mut mock = crate::reentrancy::lock_for_call(&mock, name)?;
+    mock.detect_poisoning(&input);
+    mock.record_call_and_find_mock_output(input)
+}
+
+#[doc(hidden)]
+pub fn static_record_call_and_find_mock_output_or_input<I: MockableArg, O: MockableRet>(
+    key: TypeId,
+    name: &'static str,
+    input: I,
+    clone: Option<fn(&I) -> I>,
+) -> Result<O, I> {
+    let Some(mock) = get_static_mocks().lock().get_if_locked::<I, O>(key, name) else {
+        return Err(input);
+    };
+    let Some(mut mock) = crate::reentrancy::lock_for_call(&mock, name) else {
+        return Err(input);
+    };
+    mock.detect_poisoning(&input);
+    mock.record_call_and_find_mock_output_or_input(input, clone)
 }
 
 #[doc(hidden)]
@@ -49,8 +150,9 @@ pub struct StaticMockLock<'a> {
 
 impl<'a> Drop for StaticMockLock<'a> {
     fn drop(&mut self) {
-        let mocks = STATIC_MOCKS.with(Clone::clone);
-        if mocks.lock().0.remove(&self.key).is_none() {
+        let mocks = get_static_mocks();
+        // Panicking again while unwinding would abort the whole test process.
+        if mocks.lock().0.remove(&self.key).is_none() && !std::thread::panicking() {
             panic!(
                 "{} is locked but no used. Remove {} from mry::lock",
                 self.name, self.name
@@ -63,7 +165,12 @@ impl<'a> Drop for StaticMockLock<'a> {
 #[derive(Default)]
 pub struct StaticMocks(Mocks);
 
+/// Whether `mock_*` may configure the function, which `mry::lock` or `mry::isolate` allows
 fn check_locked(key: &TypeId) -> bool {
+    is_isolated() || is_locked(key)
+}
+
+fn is_locked(key: &TypeId) -> bool {
     STATIC_MOCK_LOCKS.with(|locks| {
         locks
             .lock()
@@ -74,7 +181,7 @@ fn check_locked(key: &TypeId) -> bool {
 }
 
 impl<I: MockableArg, O: MockableRet> MockGetter<I, O> for StaticMocks {
-    fn get(&self, key: &TypeId, name: &'static str) -> Option<&Mock<I, O>> {
+    fn get(&self, key: &TypeId, name: &'static str) -> Option<SharedMock<I, O>> {
         if !check_locked(key) {
             panic!(
                 "the lock of `{name}` is not acquired. Try `mry::lock({name})`.",
@@ -84,7 +191,7 @@ impl<I: MockableArg, O: MockableRet> MockGetter<I, O> for StaticMocks {
         self.0.get(key, name)
     }
 
-    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> &mut Mock<I, O> {
+    fn get_mut_or_create(&mut self, key: TypeId, name: &'static str) -> SharedMock<I, O> {
         if !check_locked(&key) {
             panic!(
                 "the lock of `{name}` is not acquired. Try `mry::lock({name})`.",
@@ -96,14 +203,16 @@ impl<I: MockableArg, O: MockableRet> MockGetter<I, O> for StaticMocks {
 }
 
 impl StaticMocks {
-    pub fn record_call_and_find_mock_output<I: MockableArg, O: MockableRet>(
+    pub(crate) fn get_if_locked<I: MockableArg, O: MockableRet>(
         &mut self,
         key: TypeId,
         name: &'static str,
-        input: I,
-    ) -> Option<O> {
-        if check_locked(&key) {
-            self.0.record_call_and_find_mock_output(key, name, input)
+    ) -> Option<SharedMock<I, O>> {
+        if is_locked(&key) {
+            Some(self.0.get_mut_or_create(key, name))
+        } else if is_isolated() {
+            // Functions not configured in the isolated scope keep running their real implementations
+            self.0.get(&key, name)
         } else {
             None
         }
@@ -130,7 +239,7 @@ pub fn __mutexes(mut keys: Vec<(TypeId, String)>) -> Vec<StaticMockMutex> {
 }
 
 #[doc(hidden)]
-pub fn __lock_and_run<T>(mut mutexes: Vec<StaticMockMutex>, function: fn() -> T) -> T {
+pub fn __lock_and_run<T>(mut mutexes: Vec<StaticMockMutex>, function: impl FnOnce() -> T) -> T {
     if let Some(mutex) = mutexes.pop() {
         let _lock = StaticMockLock {
             key: mutex.key,
@@ -143,17 +252,45 @@ pub fn __lock_and_run<T>(mut mutexes: Vec<StaticMockMutex>, function: fn() -> T)
     }
 }
 
+/// Returns pending once so that the executor polls other tasks
+#[cfg(feature = "async")]
+struct YieldNow(bool);
+
+#[cfg(feature = "async")]
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
 #[doc(hidden)]
+#[cfg(feature = "async")]
 #[async_recursion(?Send)]
 pub async fn __async_lock_and_run<T>(
     mut mutexes: Vec<StaticMockMutex>,
     function: fn() -> Pin<Box<dyn Future<Output = T>>>,
 ) -> T {
     if let Some(mutex) = mutexes.pop() {
+        // Yields to other tasks instead of blocking the thread, which would wedge single-threaded
+        // executors and deterministic simulators if another task holds the lock.
+        let lock = loop {
+            if let Some(lock) = mutex.mutex.try_lock() {
+                break lock;
+            }
+            YieldNow(false).await;
+        };
         let _lock = StaticMockLock {
             key: mutex.key,
             name: mutex.name,
-            lock: Box::new(mutex.mutex.lock()),
+            lock: Box::new(lock),
         };
         __async_lock_and_run(mutexes, function).await
     } else {
@@ -176,16 +313,11 @@ mod tests {
             Arc::new(Default::default()),
         );
 
-        assert_eq!(
-            STATIC_MOCKS.with(
-                |mocks| mocks.lock().record_call_and_find_mock_output::<(), ()>(
-                    returns_none_if_not_mocked.type_id(),
-                    "meow",
-                    ()
-                )
-            ),
-            None
-        );
+        assert!(STATIC_MOCKS
+            .with(|mocks| mocks
+                .lock()
+                .get_if_locked::<(), ()>(returns_none_if_not_mocked.type_id(), "meow"))
+            .is_none());
     }
 
     #[test]
@@ -193,6 +325,7 @@ mod tests {
         let mut mocks = Mocks::default();
         mocks
             .get_mut_or_create(returns_some_if_mocked.type_id(), "meow")
+            .lock()
             .returns(Matcher::new_eq(()).wrapped(), ());
         let mut static_mocks = StaticMocks(mocks);
 
@@ -202,11 +335,11 @@ mod tests {
         insert_lock(returns_some_if_mocked.type_id(), mutex.clone());
 
         assert_eq!(
-            static_mocks.record_call_and_find_mock_output::<(), ()>(
-                returns_some_if_mocked.type_id(),
-                "meow",
-                ()
-            ),
+            static_mocks
+                .get_if_locked::<(), ()>(returns_some_if_mocked.type_id(), "meow")
+                .unwrap()
+                .lock()
+                .record_call_and_find_mock_output(()),
             Some(())
         );
     }
@@ -380,10 +513,94 @@ mod tests {
         assert!(MockGetter::<usize, usize>::get(&mocks.lock().0, &b.type_id(), "b").is_none());
     }
 
+    #[test]
+    fn isolate_does_not_require_lock() {
+        fn a() {}
+        isolate(|| {
+            MockGetter::<(), ()>::get_mut_or_create(
+                &mut get_static_mocks().lock(),
+                a.type_id(),
+                "a",
+            )
+            .lock()
+            .returns(Matcher::new_eq(()).wrapped(), ());
+
+            assert_eq!(
+                static_record_call_and_find_mock_output::<(), ()>(a.type_id(), "a", ()),
+                Some(())
+            );
+        });
+    }
+
+    #[test]
+    fn isolate_removes_mocks_on_exit() {
+        fn a() {}
+        isolate_as("isolate_removes_mocks_on_exit", || {
+            MockGetter::<(), ()>::get_mut_or_create(
+                &mut get_static_mocks().lock(),
+                a.type_id(),
+                "a",
+            );
+        });
+
+        assert!(!isolated_mocks()
+            .lock()
+            .contains_key("isolate_removes_mocks_on_exit"));
+        assert!(!is_isolated());
+    }
+
+    #[test]
+    fn isolate_as_shares_mocks_by_id() {
+        fn a() {}
+        isolate_as("isolate_as_shares_mocks_by_id", || {
+            MockGetter::<(), ()>::get_mut_or_create(
+                &mut get_static_mocks().lock(),
+                a.type_id(),
+                "a",
+            )
+            .lock()
+            .returns(Matcher::new_eq(()).wrapped(), ());
+
+            std::thread::spawn(|| {
+                isolate_as("isolate_as_shares_mocks_by_id", || {
+                    assert_eq!(
+                        static_record_call_and_find_mock_output::<(), ()>(a.type_id(), "a", ()),
+                        Some(())
+                    );
+                })
+            })
+            .join()
+            .unwrap();
+        });
+    }
+
     fn insert_mock<I: MockableArg, O: MockableRet>(key: TypeId, mock: Mock<I, O>) {
         STATIC_MOCKS.with(|mocks| mocks.lock().0.insert(key, mock));
     }
 
+    #[test]
+    #[cfg(feature = "async")]
+    fn async_lock_yields_while_locked_by_another_task() {
+        fn locked() {}
+        let key = locked.type_id();
+        let other_task = __mutexes(vec![(key, "locked".into())]).remove(0).mutex;
+        let guard = other_task.lock();
+
+        let mut future = __async_lock_and_run(__mutexes(vec![(key, "locked".into())]), || {
+            Box::pin(async {
+                get_static_mocks()
+                    .lock()
+                    .get_if_locked::<(), ()>(locked.type_id(), "locked");
+            })
+        });
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        drop(guard);
+        assert!(future.as_mut().poll(&mut cx).is_ready());
+        cleanup_static_mock_lock(key);
+    }
+
     fn insert_lock(key: TypeId, lock: Arc<Mutex<()>>) {
         STATIC_MOCK_LOCKS.with(|locks| {
             locks.lock().insert(key, lock);
@@ -0,0 +1,166 @@
+//! Locks of mry, which are those of `parking_lot`, or those of the standard library with the `sim`
+//! feature. The fairness of `parking_lot` is timed by the real clock, which deterministic
+//! simulators don't control, while the locks of the standard library read no time.
+
+#[cfg(not(feature = "sim"))]
+pub use parking_lot::Mutex;
+#[cfg(not(feature = "sim"))]
+pub(crate) use parking_lot::{Condvar, MutexGuard, RwLock};
+
+#[cfg(feature = "sim")]
+pub use self::sim::Mutex;
+#[cfg(feature = "sim")]
+pub(crate) use self::sim::{Condvar, MutexGuard, RwLock};
+
+/// Locks of the standard library with the API of `parking_lot`, ignoring poisoning as
+/// `parking_lot` does. Built without the `sim` feature too, to be tested along the others.
+#[cfg_attr(not(feature = "sim"), allow(dead_code))]
+mod sim {
+    use std::{
+        fmt::Debug,
+        ops::{Deref, DerefMut},
+        sync::{self, PoisonError, TryLockError},
+    };
+
+    /// Mutual exclusion lock, a `std::sync::Mutex` which ignores poisoning
+    #[derive(Default)]
+    pub struct Mutex<T: ?Sized>(sync::Mutex<T>);
+
+    impl<T> Mutex<T> {
+        pub const fn new(value: T) -> Self {
+            Self(sync::Mutex::new(value))
+        }
+
+        pub fn into_inner(self) -> T {
+            self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl<T: ?Sized> Mutex<T> {
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            MutexGuard(Some(self.0.lock().unwrap_or_else(PoisonError::into_inner)))
+        }
+
+        pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+            match self.0.try_lock() {
+                Ok(guard) => Some(MutexGuard(Some(guard))),
+                Err(TryLockError::Poisoned(poisoned)) => {
+                    Some(MutexGuard(Some(poisoned.into_inner())))
+                }
+                Err(TryLockError::WouldBlock) => None,
+            }
+        }
+
+        pub fn get_mut(&mut self) -> &mut T {
+            self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl<T: ?Sized + Debug> Debug for Mutex<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    /// Guard of `Mutex`, which `Condvar` takes out while waiting
+    pub struct MutexGuard<'a, T: ?Sized>(Option<sync::MutexGuard<'a, T>>);
+
+    impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.0.as_ref().expect("the guard is held")
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            self.0.as_mut().expect("the guard is held")
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Condvar(sync::Condvar);
+
+    impl Condvar {
+        pub const fn new() -> Self {
+            Self(sync::Condvar::new())
+        }
+
+        pub fn wait<T>(&self, guard: &mut MutexGuard<'_, T>) {
+            let held = guard.0.take().expect("the guard is held");
+            guard.0 = Some(self.0.wait(held).unwrap_or_else(PoisonError::into_inner));
+        }
+
+        pub fn notify_all(&self) {
+            self.0.notify_all();
+        }
+    }
+
+    /// Reader-writer lock, a `std::sync::RwLock` which ignores poisoning
+    #[derive(Debug, Default)]
+    pub struct RwLock<T: ?Sized>(sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        pub const fn new(value: T) -> Self {
+            Self(sync::RwLock::new(value))
+        }
+    }
+
+    impl<T: ?Sized> RwLock<T> {
+        pub fn read(&self) -> sync::RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        pub fn write(&self) -> sync::RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::{sync::Arc, thread};
+
+        use super::*;
+
+        #[test]
+        fn ignores_poisoning() {
+            let mutex = Arc::new(Mutex::new(1));
+            let poisoning = mutex.clone();
+            thread::spawn(move || {
+                let _guard = poisoning.lock();
+                panic!("poisons the lock");
+            })
+            .join()
+            .unwrap_err();
+
+            *mutex.lock() += 1;
+
+            assert_eq!(*mutex.try_lock().unwrap(), 2);
+        }
+
+        #[test]
+        fn waits_on_condvar() {
+            let state = Arc::new((Mutex::new(false), Condvar::new()));
+            let notifier = state.clone();
+            thread::spawn(move || {
+                *notifier.0.lock() = true;
+                notifier.1.notify_all();
+            });
+
+            let mut ready = state.0.lock();
+            while !*ready {
+                state.1.wait(&mut ready);
+            }
+
+            assert!(state.0.try_lock().is_none());
+        }
+
+        #[test]
+        fn coerces_to_unsized() {
+            let debug: Arc<Mutex<dyn Debug + Send>> = Arc::new(Mutex::new(3));
+
+            assert_eq!(format!("{:?}", &*debug.lock()), "3");
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+    task::{Context, Poll},
+};
+
+use crate::sync::Mutex;
+
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Token of the `#[mry::test]` running on this thread
+    static CURRENT: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+#[derive(Default)]
+struct Tests {
+    /// Names of the tests by the tokens, kept after the tests to name the owners of leaked state
+    names: HashMap<u64, &'static str>,
+    /// State of other tests touched by the running tests
+    violations: HashMap<u64, Vec<String>>,
+}
+
+static TESTS: OnceLock<Mutex<Tests>> = OnceLock::new();
+
+fn tests() -> &'static Mutex<Tests> {
+    TESTS.get_or_init(Default::default)
+}
+
+/// Token of the `#[mry::test]` running on this thread, which tags the mocks and rules it creates
+pub(crate) fn current() -> Option<u64> {
+    CURRENT.with(Cell::get)
+}
+
+/// Records a violation if the running test touches state tagged with the token of another test
+pub(crate) fn touch(owner: Option<u64>, what: impl FnOnce() -> String) {
+    let (Some(owner), Some(current)) = (owner, current()) else {
+        return;
+    };
+    if owner == current {
+        return;
+    }
+    let mut tests = tests().lock();
+    let violation = format!(
+        "{} of the test {}",
+        what(),
+        tests.names.get(&owner).copied().unwrap_or("<unknown>")
+    );
+    let violations = tests.violations.entry(current).or_default();
+    if !violations.contains(&violation) {
+        violations.push(violation);
+    }
+}
+
+/// Test running with a new token, which verifies the isolation when dropped
+struct Running {
+    token: u64,
+}
+
+impl Running {
+    fn start(name: &'static str) -> Self {
+        let token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+        tests().lock().names.insert(token, name);
+        Self { token }
+    }
+
+    /// Sets the token on this thread until the returned value is dropped
+    fn enter(&self) -> Entered {
+        Entered {
+            previous: CURRENT.with(|current| current.replace(Some(self.token))),
+        }
+    }
+}
+
+struct Entered {
+    previous: Option<u64>,
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        let mut tests = tests().lock();
+        let name = tests.names[&self.token];
+        let violations = tests.violations.remove(&self.token).unwrap_or_default();
+        drop(tests);
+        if !violations.is_empty() && !std::thread::panicking() {
+            panic!(
+                "the test {} touched mock state of other tests:\n{}",
+                name,
+                violations
+                    .iter()
+                    .map(|violation| format!("  - {}", violation))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn __run_test<T>(name: &'static str, test: impl FnOnce() -> T) -> T {
+    let running = Running::start(name);
+    let _entered = running.enter();
+    test()
+}
+
+#[doc(hidden)]
+pub async fn __run_async_test<T>(name: &'static str, test: Pin<Box<dyn Future<Output = T>>>) -> T {
+    RunningFuture {
+        running: Running::start(name),
+        test,
+    }
+    .await
+}
+
+/// Future of a test setting its token while it is polled, on whichever thread polls it
+struct RunningFuture<T> {
+    running: Running,
+    test: Pin<Box<dyn Future<Output = T>>>,
+}
+
+impl<T> Future for RunningFuture<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let _entered = self.running.enter();
+        self.test.as_mut().poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touching_own_state() {
+        __run_test("a", || touch(current(), || "Cat::meow".to_string()));
+    }
+
+    #[test]
+    fn touching_untagged_state() {
+        __run_test("a", || touch(None, || "Cat::meow".to_string()));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the test b touched mock state of other tests:\n  - the mock of Cat::meow of the test a"
+    )]
+    fn touching_state_of_other_tests() {
+        let token = __run_test("a", current);
+        __run_test("b", || {
+            touch(token, || "the mock of Cat::meow".to_string());
+            touch(token, || "the mock of Cat::meow".to_string());
+        });
+    }
+}
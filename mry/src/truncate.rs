@@ -0,0 +1,99 @@
+use std::{cell::Cell, fmt::Debug};
+
+thread_local! {
+    static DEBUG_LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+    /// Limit of the object whose calls are being rendered, set by `Mry::set_debug_limit`
+    static OBJECT_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Limits the length of arguments rendered in panic messages, for arguments such as
+/// multi-kilobyte blobs. Longer renderings are truncated with a note of the total length.
+///
+/// The setting is of the current thread, which is the current test under `cargo test`. Pass
+/// `usize::MAX` to render arguments in full again.
+pub fn set_debug_limit(limit: usize) {
+    DEBUG_LIMIT.with(|current| current.set(limit));
+}
+
+/// Runs `f` rendering by the limit of an object in place of the one of the thread, if it has one
+pub(crate) fn with_object_limit<T>(limit: Option<usize>, f: impl FnOnce() -> T) -> T {
+    /// Restores the outer limit even if `f` panics with the rendered message
+    struct Restore(Option<usize>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OBJECT_LIMIT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(OBJECT_LIMIT.with(|current| current.replace(limit)));
+    f()
+}
+
+/// Renders the value by `Debug` within the limit of the object or the one set by
+/// `set_debug_limit`
+pub(crate) fn debug(value: &impl Debug) -> String {
+    let rendered = format!("{:?}", value);
+    let limit = OBJECT_LIMIT
+        .with(Cell::get)
+        .unwrap_or_else(|| DEBUG_LIMIT.with(Cell::get));
+    match rendered.char_indices().nth(limit) {
+        Some((end, _)) => format!(
+            "{}... ({} chars in total)",
+            &rendered[..end],
+            rendered.chars().count()
+        ),
+        None => rendered,
+    }
+}
+
+/// Joins the lines with elision of repeated identical lines as `×N`
+pub(crate) fn elide_repeats(lines: impl IntoIterator<Item = String>) -> String {
+    let mut elided: Vec<(String, usize)> = Vec::new();
+    for line in lines {
+        match elided.last_mut() {
+            Some((last, count)) if *last == line => *count += 1,
+            _ => elided.push((line, 1)),
+        }
+    }
+    elided
+        .into_iter()
+        .map(|(line, count)| match count {
+            1 => format!("\n    {}", line),
+            count => format!("\n    {} ×{}", line, count),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elides_repeats() {
+        assert_eq!(
+            elide_repeats(["1", "2", "2", "1"].map(String::from)),
+            "\n    1\n    2 ×2\n    1"
+        );
+    }
+
+    #[test]
+    fn truncates_long_debug() {
+        set_debug_limit(5);
+        let truncated = debug(&"abcdefgh");
+        let short = debug(&"ab");
+
+        assert_eq!(truncated, "\"abcd... (10 chars in total)");
+        assert_eq!(short, "\"ab\"");
+    }
+
+    #[test]
+    fn object_limit_takes_precedence() {
+        set_debug_limit(5);
+        let truncated = with_object_limit(Some(3), || debug(&"abcdefgh"));
+        let restored = debug(&"abcdefgh");
+
+        assert_eq!(truncated, "\"ab... (10 chars in total)");
+        assert_eq!(restored, "\"abcd... (10 chars in total)");
+    }
+}
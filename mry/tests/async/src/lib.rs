@@ -0,0 +1 @@
+//! Enables the `async` feature of mry for its own tests, as a dev-dependency of mry
@@ -0,0 +1,47 @@
+//! Replaces the clock of the whole process, so it runs apart from the other integration tests
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use mry::{Any, ClockSource};
+
+/// Clock advancing by 10 ms each time it is read
+struct SteppingClock {
+    start: Instant,
+    reads: AtomicU64,
+}
+
+impl ClockSource for SteppingClock {
+    fn now(&self) -> Instant {
+        self.start + Duration::from_millis(10 * self.reads.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn nap(&self, millis: u64) -> u64 {
+        millis
+    }
+}
+
+#[test]
+fn measures_durations_by_the_clock() {
+    mry::set_clock(SteppingClock {
+        start: Instant::now(),
+        reads: AtomicU64::new(0),
+    });
+    let mut cat = Cat::default();
+    cat.mock_nap(Any).calls_real_impl().measure_durations();
+
+    cat.nap(0);
+
+    assert_eq!(
+        cat.mock_nap(Any).durations(),
+        vec![Duration::from_millis(10)]
+    );
+}
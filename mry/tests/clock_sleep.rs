@@ -0,0 +1,72 @@
+//! Replaces the clock of the whole process, so it runs apart from the other integration tests
+#![cfg(feature = "async")]
+use std::{
+    sync::{Mutex, Once},
+    time::{Duration, Instant},
+};
+
+use mry::{BoxFuture, ClockSource};
+
+/// Virtual clock of a simulation, which sleeps by advancing at once
+struct VirtualClock {
+    start: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl ClockSource for VirtualClock {
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) -> Option<BoxFuture<()>> {
+        *self.elapsed.lock().unwrap() += duration;
+        Some(Box::pin(std::future::ready(())))
+    }
+}
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    async fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+/// Sets the virtual clock once for the tests of this process
+fn simulate() {
+    static CLOCK: Once = Once::new();
+    CLOCK.call_once(|| {
+        mry::set_clock(VirtualClock {
+            start: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        })
+    });
+}
+
+#[async_std::test]
+async fn returns_after_virtual_delay() {
+    simulate();
+    let mut cat = Cat::default();
+    cat.mock_meow(2)
+        .returns_after(Duration::from_secs(60), "late".to_string());
+    let start = Instant::now();
+
+    assert_eq!(cat.meow(2).await, "late".to_string());
+    assert!(start.elapsed() < Duration::from_secs(60));
+}
+
+#[async_std::test]
+#[should_panic(
+    expected = "Expected Cat::meow to be called 1 times within 60s, but it was called 0 times"
+)]
+async fn times_out_on_virtual_time() {
+    simulate();
+    let mut cat = Cat::default();
+
+    cat.mock_meow(mry::Any)
+        .assert_called_eventually(1, Duration::from_secs(60))
+        .await;
+}
@@ -0,0 +1,5 @@
+//! Builds the misuse cases with trybuild, apart from the other integration tests
+#[test]
+fn misuse_fails_to_compile() {
+    mry::assert_compile_fails("tests/compile_fail/*.rs");
+}
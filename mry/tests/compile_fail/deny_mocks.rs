@@ -0,0 +1,4 @@
+// Built as a binary without cfg(test), where the default gate of the mocks holds
+mry::deny_mocks!();
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+fn main() {
+    let mut cat = Cat::default();
+    cat.mock_meow("three").returns("meow".to_string());
+}
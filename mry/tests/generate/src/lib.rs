@@ -0,0 +1,17 @@
+use quote::ToTokens;
+use syn::{parse_macro_input, parse_quote, ItemImpl};
+
+/// Adds `fn greet(&self) -> String` to the impl block, keeping the other attributes on it
+#[proc_macro_attribute]
+pub fn greeting(
+    _attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut item = parse_macro_input!(input as ItemImpl);
+    item.items.push(parse_quote! {
+        fn greet(&self) -> String {
+            "hello".to_string()
+        }
+    });
+    item.into_token_stream().into()
+}
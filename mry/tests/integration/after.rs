@@ -0,0 +1,28 @@
+use mry_test_generate::greeting;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry(after(greeting))]
+#[greeting]
+impl Cat {
+    fn meow(&self) -> String {
+        "meow".to_string()
+    }
+}
+
+#[test]
+fn mocks_generated_methods() {
+    let mut cat = Cat::default();
+    cat.mock_greet().returns("nyan".to_string());
+    cat.mock_meow().returns("mew".to_string());
+
+    assert_eq!(cat.greet(), "nyan".to_string());
+    assert_eq!(cat.meow(), "mew".to_string());
+}
+
+#[test]
+fn calls_generated_methods() {
+    assert_eq!(Cat::default().greet(), "hello".to_string());
+}
@@ -24,3 +24,21 @@ async fn meow_called2() {
 
     let _ = Cat::meow2(&cat, 2).await;
 }
+
+#[async_std::test]
+async fn local_meow_called() {
+    let mut cat = MockLocalCat::default();
+
+    cat.mock_meow(2).returns("Called");
+
+    assert_eq!(LocalCat::meow(&cat, 2).await, "Called");
+}
+
+#[async_std::test]
+async fn local_meow_called2() {
+    let mut cat = MockLocalCat::default();
+
+    cat.mock_meow2(2).returns_once(NonClone);
+
+    let _ = LocalCat::meow2(&cat, 2).await;
+}
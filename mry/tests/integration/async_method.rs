@@ -14,6 +14,10 @@ impl Cat {
         sleep(Duration::from_secs(1)).await;
         format!("{}: {}", self.name, "meow".repeat(count))
     }
+
+    fn purr(&self, count: usize) -> String {
+        "purr".repeat(count)
+    }
 }
 
 #[async_std::test]
@@ -27,3 +31,65 @@ async fn meow_returns_with() {
 
     assert_eq!(cat.meow(2).await, "Called with 2".to_string());
 }
+
+#[async_std::test]
+async fn meow_returns_with_async() {
+    let mut cat: Cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    let (sender, receiver) = async_std::channel::unbounded::<String>();
+    cat.mock_meow(mry::Any).returns_with_async(move |count| {
+        let receiver = receiver.clone();
+        async move { receiver.recv().await.unwrap().repeat(count) }
+    });
+
+    sender.send("nyan".into()).await.unwrap();
+
+    assert_eq!(cat.meow(2).await, "nyannyan".to_string());
+    cat.mock_meow(2).assert_called(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Cat::purr is not an async method, but it has a behavior of returns_with_async"
+)]
+fn purr_returns_with_async() {
+    let mut cat = Cat::default();
+    cat.mock_purr(2)
+        .returns_with_async(|count| async move { "nyan".repeat(count) });
+
+    cat.purr(2);
+}
+
+// Sleeps on the timer thread of mry, which the sim feature removes
+#[cfg(not(feature = "sim"))]
+#[async_std::test]
+async fn meow_returns_after() {
+    let mut cat = Cat::default();
+    cat.mock_meow(2)
+        .returns_after(Duration::from_millis(10), "late".to_string());
+
+    let start = std::time::Instant::now();
+
+    assert_eq!(cat.meow(2).await, "late".to_string());
+    assert!(start.elapsed() >= Duration::from_millis(10));
+}
+
+#[tokio::test(start_paused = true)]
+async fn meow_returns_after_paused() {
+    let mut cat = Cat::default();
+    cat.mock_meow(2)
+        .returns_after(Duration::from_secs(60), "late".to_string());
+
+    let start = tokio::time::Instant::now();
+
+    assert!(tokio::time::timeout(Duration::from_secs(30), cat.meow(2))
+        .await
+        .is_err());
+    assert_eq!(
+        tokio::time::timeout(Duration::from_secs(90), cat.meow(2)).await,
+        Ok("late".to_string())
+    );
+    assert_eq!(start.elapsed(), Duration::from_secs(90));
+}
@@ -12,3 +12,15 @@ async fn meow_called() {
 
     assert_eq!(cat.meow(2).await, "Called");
 }
+
+#[async_std::test]
+async fn meow_returns_with_async() {
+    let mut cat = MockCat::default();
+
+    cat.mock_meow(2).returns_with_async(|_| async {
+        async_std::task::yield_now().await;
+        "Called"
+    });
+
+    assert_eq!(cat.meow(2).await, "Called");
+}
@@ -0,0 +1,157 @@
+use mry::{Any, Callback};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Config {
+    retries: u8,
+    verbose: bool,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Store {}
+
+#[mry::mry]
+impl Store {
+    fn update(&self, f: impl FnOnce(&mut Config)) -> Config {
+        let mut config = Config::default();
+        f(&mut config);
+        config
+    }
+
+    fn update_each(&self, names: Vec<String>, mut f: impl FnMut(&mut Config)) -> usize {
+        let mut config = Config::default();
+        for _ in &names {
+            f(&mut config);
+        }
+        usize::from(config.retries)
+    }
+}
+
+#[mry::mry]
+trait Settings {
+    fn edit(&self, f: impl Fn(&mut Config)) -> bool;
+}
+
+struct Unit<'a> {
+    store: &'a Store,
+}
+
+impl Unit<'_> {
+    fn enable_retries(&self, retries: u8) {
+        self.store.update(|config| config.retries = retries);
+    }
+}
+
+#[test]
+fn behavior_runs_callback_on_its_own_value() {
+    let mut store = Store::default();
+    store.mock_update(Any).returns_with(|f: Callback<Config>| {
+        f.applied_to(Config {
+            verbose: true,
+            ..Default::default()
+        })
+    });
+
+    assert_eq!(
+        store.update(|config| config.retries = 3),
+        Config {
+            retries: 3,
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn behavior_observes_mutation() {
+    let mut store = Store::default();
+    store.mock_update(Any).returns_with(|f: Callback<Config>| {
+        let config = f.applied_to(Config::default());
+        assert_eq!(config.retries, 3);
+        config
+    });
+
+    Unit { store: &store }.enable_retries(3);
+
+    store.mock_update(Any).assert_called(1);
+}
+
+#[test]
+fn runs_fn_mut_callback_several_times() {
+    let mut store = Store::default();
+    store
+        .mock_update_each(Any, Any)
+        .returns_with(|names: Vec<String>, f: Callback<Config>| {
+            let mut config = Config::default();
+            for _ in &names {
+                f.call(&mut config);
+            }
+            usize::from(config.retries)
+        });
+
+    assert_eq!(
+        store.update_each(vec!["a".into(), "b".into()], |config| config.retries += 1),
+        2
+    );
+}
+
+#[test]
+fn pattern_runs_callback() {
+    let mut settings = MockSettings::default();
+    settings
+        .mock_edit(mry::pred(|f: &Callback<Config>| {
+            f.applied_to(Config::default()).verbose
+        }))
+        .returns(true);
+    settings.mock_edit(Any).returns(false);
+
+    assert!(settings.edit(|config| config.verbose = true));
+    assert!(!settings.edit(|config| config.retries = 1));
+}
+
+#[test]
+fn real_impl_gets_callback() {
+    let mut store = Store::default();
+    store.mock_update(Any).calls_real_impl();
+
+    assert_eq!(store.update(|config| config.retries = 1).retries, 1);
+}
+
+#[test]
+fn no_mock_calls_real_impl() {
+    let store = Store::default();
+
+    assert_eq!(
+        store.update_each(vec!["a".into()], |config| config.retries += 2),
+        2
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "the callback is lent for the call only, but it is run after the call returns"
+)]
+fn callback_is_lent_for_call_only() {
+    let mut store = Store::default();
+    let captor = mry::Captor::new();
+    store.mock_update(captor.clone()).returns(Config::default());
+
+    store.update(|config| config.retries = 1);
+
+    captor.last().unwrap().applied_to(Config::default());
+}
+
+#[test]
+#[should_panic(
+    expected = "Store::update cannot call the real implementation, because the mock ran its FnOnce callback"
+)]
+fn real_impl_after_callback_ran() {
+    let mut store = Store::default();
+    store
+        .mock_update(mry::pred(|f: &Callback<Config>| {
+            f.applied_to(Config::default()).verbose
+        }))
+        .returns(Config::default());
+    store.mock_update(Any).calls_real_impl();
+
+    store.update(|config| config.retries = 1);
+}
@@ -0,0 +1,100 @@
+use mry::{Any, Captor};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Order {
+    id: u32,
+    items: Vec<String>,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Shop {}
+
+#[mry::mry]
+impl Shop {
+    fn place(&self, order: Order, express: bool) -> bool {
+        express && !order.items.is_empty()
+    }
+}
+
+#[test]
+fn captures_each_argument() {
+    let mut shop = Shop::default();
+    let orders = Captor::new();
+    let express = Captor::new();
+    shop.mock_place(orders.clone(), express.clone())
+        .returns(true);
+
+    shop.place(
+        Order {
+            id: 1,
+            items: vec!["apple".into()],
+        },
+        true,
+    );
+    shop.place(
+        Order {
+            id: 2,
+            items: vec![],
+        },
+        false,
+    );
+
+    let ids: Vec<_> = orders.values().iter().map(|order| order.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(express.values(), vec![true, false]);
+    assert!(orders.last().unwrap().items.is_empty());
+}
+
+#[test]
+fn captures_calls_reaching_the_rule() {
+    let mut shop = Shop::default();
+    let orders = Captor::new();
+    shop.mock_place(Any, true).returns(true);
+    shop.mock_place(orders.clone(), Any).returns(false);
+
+    shop.place(
+        Order {
+            id: 1,
+            items: vec![],
+        },
+        true,
+    );
+    shop.place(
+        Order {
+            id: 2,
+            items: vec![],
+        },
+        false,
+    );
+
+    assert_eq!(orders.values().len(), 1);
+    assert_eq!(orders.last().unwrap().id, 2);
+}
+
+#[test]
+fn captures_only_calls_served_by_the_rule() {
+    let mut shop = Shop::default();
+    let orders = Captor::new();
+    shop.mock_place(orders.clone(), true).returns(true);
+    shop.mock_place(Any, false).returns(false);
+
+    shop.place(
+        Order {
+            id: 1,
+            items: vec![],
+        },
+        false,
+    );
+    shop.place(
+        Order {
+            id: 2,
+            items: vec![],
+        },
+        true,
+    );
+    shop.mock_place(orders.clone(), Any).assert_called(2);
+
+    assert_eq!(orders.values().len(), 1);
+    assert_eq!(orders.last().unwrap().id, 2);
+}
@@ -0,0 +1,56 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Repository {}
+
+#[mry::mry]
+impl Repository {
+    fn save(&self, id: u32) -> bool {
+        id > 0
+    }
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Service {
+    repository: Repository,
+}
+
+#[mry::mry]
+impl Service {
+    fn register(&self, id: u32) -> bool {
+        self.repository.save(id)
+    }
+}
+
+#[test]
+fn behavior_causes_call() {
+    let mut repository = Repository::default();
+    repository.mock_save(Any).returns(true);
+    let mut service = Service::default();
+    let delegated = repository.clone();
+    service
+        .mock_register(Any)
+        .returns_with(move |id| delegated.save(id));
+
+    assert!(service.register(1));
+
+    mry::assert_caused(&service.mock_register(Any), &repository.mock_save(1));
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Service::register to cause a call of Repository::save matching the pattern, but it did not"
+)]
+fn direct_call_is_not_caused() {
+    let mut repository = Repository::default();
+    repository.mock_save(Any).returns(true);
+    let mut service = Service::default();
+    service.mock_register(Any).returns(true);
+
+    service.register(1);
+    repository.save(1);
+
+    mry::assert_caused(&service.mock_register(Any), &repository.mock_save(1));
+}
@@ -0,0 +1,45 @@
+use mry::Any;
+
+#[mry::mry(cfg = test)]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry(cfg = any(test, doc))]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[mry::mry(cfg = test)]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[mry::mry(cfg = not(test))]
+fn bye(count: usize) -> String {
+    "bye".repeat(count)
+}
+
+#[test]
+fn method_mocked_under_cfg() {
+    let mut cat = Cat::default();
+
+    cat.mock_meow(Any).returns("Called".into());
+
+    assert_eq!(cat.meow(2), "Called");
+}
+
+#[mry::lock(hello)]
+#[test]
+fn function_mocked_under_cfg() {
+    mock_hello(Any).returns("Called".into());
+
+    assert_eq!(hello(2), "Called");
+}
+
+#[test]
+fn function_not_mocked_outside_cfg() {
+    // `mock_bye` is not generated
+    assert_eq!(bye(2), "byebye");
+}
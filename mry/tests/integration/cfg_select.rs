@@ -0,0 +1,44 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Env {}
+
+#[mry::mry]
+impl Env {
+    fn temp_dir(&self) -> String {
+        std::env::temp_dir().display().to_string()
+    }
+
+    fn line_ending(&self, count: usize) -> String {
+        "\n".repeat(count)
+    }
+}
+
+#[test]
+fn selects_behavior_of_target() {
+    let mut env = Env::default();
+    mry::cfg_select!(env.mock_temp_dir(), {
+        unix => returns("/tmp".into()),
+        windows => returns("C:\\Temp".into()),
+        _ => returns("/".into()),
+    });
+
+    #[cfg(unix)]
+    assert_eq!(env.temp_dir(), "/tmp");
+    #[cfg(windows)]
+    assert_eq!(env.temp_dir(), "C:\\Temp");
+}
+
+#[test]
+fn first_matching_arm_wins() {
+    let mut env = Env::default();
+    mry::cfg_select!(env.mock_line_ending(Any), {
+        any(unix, windows) => returns_with(|count| "\n".repeat(count)),
+        unix => returns("never".into()),
+        _ => calls_real_impl(),
+    })
+    .assert_called(0);
+
+    assert_eq!(env.line_ending(2), "\n\n");
+}
@@ -0,0 +1,57 @@
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Feed {}
+
+#[mry::mry]
+impl Feed {
+    fn next_price(&self) -> u64 {
+        todo!()
+    }
+}
+
+#[test]
+fn returns_from_channel() {
+    let mut feed = Feed::default();
+    let (sender, receiver) = channel();
+    feed.mock_next_price().returns_from_channel(receiver);
+
+    let consumer = {
+        let feed = feed.clone();
+        thread::spawn(move || (0..3).map(|_| feed.next_price()).sum::<u64>())
+    };
+    for price in [100, 110, 90] {
+        sender.send(price).unwrap();
+    }
+
+    assert_eq!(consumer.join().unwrap(), 300);
+}
+
+#[test]
+#[should_panic(expected = "the channel of Feed::next_price is closed")]
+fn panics_when_channel_is_closed() {
+    let mut feed = Feed::default();
+    let (sender, receiver) = channel();
+    feed.mock_next_price().returns_from_channel(receiver);
+    sender.send(100).unwrap();
+    drop(sender);
+
+    assert_eq!(feed.next_price(), 100);
+    feed.next_price();
+}
+
+#[test]
+#[should_panic(
+    expected = "Feed::next_price waited 10ms for a value from the channel of returns_from_channel. The mock is locked while waiting"
+)]
+fn panics_when_no_value_comes() {
+    let mut feed = Feed::default();
+    let (_sender, receiver) = channel();
+    feed.mock_next_price()
+        .returns_from_channel_within(receiver, Duration::from_millis(10));
+
+    feed.next_price();
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use mry::{contains, has_len, is_empty, unordered_eq, Any};
+
+#[mry::mry]
+#[derive(Default)]
+struct Basket {}
+
+#[mry::mry]
+impl Basket {
+    fn put(&self, fruits: Vec<String>) -> usize {
+        fruits.len()
+    }
+
+    fn tag(&self, tags: HashSet<u8>) -> bool {
+        tags.is_empty()
+    }
+}
+
+#[test]
+fn matches_collections() {
+    let mut basket = Basket::default();
+    basket.mock_put(is_empty()).returns(0);
+    basket
+        .mock_put(unordered_eq(["banana".to_string(), "apple".into()]))
+        .returns(2);
+    basket.mock_put(contains("apple".to_string())).returns(1);
+    basket.mock_put(Any).returns(9);
+
+    assert_eq!(basket.put(vec![]), 0);
+    assert_eq!(basket.put(vec!["apple".into(), "banana".into()]), 2);
+    assert_eq!(basket.put(vec!["apple".into()]), 1);
+    assert_eq!(basket.put(vec!["cherry".into()]), 9);
+
+    basket.mock_put(has_len(1)).assert_called(2);
+}
+
+#[test]
+fn matches_sets() {
+    let mut basket = Basket::default();
+    basket
+        .mock_tag(mry::all![contains(1), has_len(2)])
+        .returns(true);
+    basket.mock_tag(Any).returns(false);
+
+    assert!(basket.tag(HashSet::from([1, 2])));
+    assert!(!basket.tag(HashSet::from([1, 2, 3])));
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Router {}
+
+#[mry::mry]
+impl Router {
+    fn route(&self, path: &str, body: String) -> u16 {
+        let _ = (path, body);
+        404
+    }
+}
+
+#[test]
+fn matches_strings() {
+    let mut router = Router::default();
+    router
+        .mock_route(
+            mry::all![mry::starts_with("/api/"), mry::not(mry::ends_with(".html"))],
+            contains("token"),
+        )
+        .returns(200);
+    router.mock_route(Any, Any).returns(404);
+
+    assert_eq!(router.route("/api/users", "token=1".into()), 200);
+    assert_eq!(router.route("/api/index.html", "token=1".into()), 404);
+    assert_eq!(router.route("/api/users", "".into()), 404);
+    router
+        .mock_route(mry::starts_with("/api/"), Any)
+        .assert_called(3);
+}
+
+#[test]
+fn matches_strings_loosely() {
+    let mut router = Router::default();
+    router
+        .mock_route(
+            mry::eq_ignore_case("/API/Users"),
+            mry::eq_ignoring_whitespace("SELECT * FROM users"),
+        )
+        .returns(200);
+    router.mock_route(Any, Any).returns(404);
+
+    assert_eq!(
+        router.route("/api/users", "  SELECT *\n\tFROM   users\n".into()),
+        200
+    );
+    assert_eq!(router.route("/api/users", "SELECT * FROMusers".into()), 404);
+    assert_eq!(router.route("/api/cats", "SELECT * FROM users".into()), 404);
+}
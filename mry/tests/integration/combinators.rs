@@ -0,0 +1,150 @@
+use mry::{gt, lt, not};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[test]
+fn combines_matchers() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::all![gt(3), lt(10), not(mry::eq(5))])
+        .returns("Many".into());
+    cat.mock_meow(mry::any_of![1, 2]).returns("Few".into());
+
+    assert_eq!(cat.meow(4), "Many");
+    assert_eq!(cat.meow(2), "Few");
+
+    cat.mock_meow(mry::all![gt(3), lt(10)]).assert_called(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Cat::meow to be called 1 times, but it was called 0 times\n    pattern: (any_of![lt(2), not(gt(8))])"
+)]
+fn renders_pattern_on_failure() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::Any).returns("Called".into());
+    cat.meow(9);
+
+    cat.mock_meow(mry::any_of![lt(2), not(gt(8))])
+        .assert_called(1);
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Handler {}
+
+#[mry::mry]
+impl Handler {
+    fn handle(&self, count: Option<u8>, result: Result<u8, String>) -> bool {
+        count.is_some() && result.is_ok()
+    }
+}
+
+#[test]
+fn matches_inside_options_and_results() {
+    let mut handler = Handler::default();
+    handler
+        .mock_handle(mry::some(gt(0)), mry::ok(mry::Any))
+        .returns(true);
+    handler
+        .mock_handle(mry::none(), mry::err("not found"))
+        .returns(true);
+    handler.mock_handle(mry::Any, mry::Any).returns(false);
+
+    assert!(handler.handle(Some(1), Ok(0)));
+    assert!(!handler.handle(Some(0), Ok(0)));
+    assert!(handler.handle(None, Err("not found".into())));
+    assert!(!handler.handle(None, Err("other".into())));
+
+    handler
+        .mock_handle(mry::Any, mry::err(mry::Any))
+        .assert_called(2);
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Gauge {}
+
+#[mry::mry]
+impl Gauge {
+    fn set(&self, level: f64) {
+        let _ = level;
+    }
+}
+
+#[test]
+fn matches_near_floats() {
+    let mut gauge = Gauge::default();
+    gauge.mock_set(mry::near(0.3, 1e-9)).returns(());
+
+    gauge.set(0.1 + 0.2);
+
+    gauge.mock_set(mry::near(0.3, 1e-9)).assert_called(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Gauge::set to be called 1 times, but it was called 0 times\n    pattern: (near(0.5, ±0.01))\n    calls:\n    (0.3)\n    (0.7) ×2"
+)]
+fn renders_near_floats_with_actual_calls() {
+    let mut gauge = Gauge::default();
+    gauge.mock_set(mry::Any).returns(());
+
+    gauge.set(0.3);
+    gauge.set(0.7);
+    gauge.set(0.7);
+
+    gauge.mock_set(mry::near(0.5, 0.01)).assert_called(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Cat::meow to be called 1 times, but it was called 0 times\n    pattern: (positive even number)"
+)]
+fn renders_named_matcher() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::Any).returns("Called".into());
+    cat.meow(3);
+
+    cat.mock_meow(
+        mry::pred(|count: &usize| *count > 0 && count.is_multiple_of(2))
+            .named("positive even number"),
+    )
+    .assert_called(1);
+}
+
+struct Lots;
+
+impl mry::DescribeMatcher<usize> for Lots {
+    fn matches(&self, count: &usize) -> bool {
+        *count >= 100
+    }
+
+    fn describe(&self) -> String {
+        "lots of meows".into()
+    }
+
+    fn render(&self, count: &usize) -> Option<String> {
+        Some(format!("{} meows", count))
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Cat::meow to be called 1 times, but it was called 0 times\n    pattern: (lots of meows)\n    calls:\n    (3 meows)"
+)]
+fn renders_described_matchers_on_failure() {
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::Any).returns("Called".into());
+    cat.meow(3);
+
+    cat.mock_meow(mry::described(Lots)).assert_called(1);
+}
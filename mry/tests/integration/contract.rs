@@ -0,0 +1,74 @@
+use mry::{Any, ArgSpec, Contract};
+
+#[mry::mry]
+#[derive(Default)]
+struct Payments {}
+
+#[mry::mry]
+impl Payments {
+    fn charge(&self, user: String, amount: u32) -> Result<u32, String> {
+        let _ = user;
+        Ok(amount)
+    }
+
+    fn health(&self) -> bool {
+        true
+    }
+}
+
+fn published() -> String {
+    Contract::new()
+        .stub(
+            "Payments::charge",
+            [ArgSpec::eq("alice"), ArgSpec::Any],
+            Ok::<u32, String>(100),
+        )
+        .stub(
+            "Payments::charge",
+            [ArgSpec::eq("bob"), ArgSpec::eq(5)],
+            Err::<u32, String>("insufficient funds".into()),
+        )
+        .stub("Payments::health", [], false)
+        .to_json()
+}
+
+#[test]
+fn serves_stubs_of_contract() {
+    let contract = Contract::from_json(&published()).unwrap();
+    let mut payments = Payments::default();
+    payments
+        .mock_charge(Any, Any)
+        .returns_contract(&contract, "Payments::charge")
+        .returns(Ok(0));
+    payments
+        .mock_health()
+        .returns_contract(&contract, "Payments::health");
+
+    assert_eq!(payments.charge("alice".into(), 7), Ok(100));
+    assert_eq!(
+        payments.charge("bob".into(), 5),
+        Err("insufficient funds".into())
+    );
+    assert_eq!(payments.charge("bob".into(), 6), Ok(0));
+    assert!(!payments.health());
+}
+
+#[test]
+#[should_panic(expected = "the contract has no stubs of Payments::refund")]
+fn panics_on_unknown_method() {
+    let contract = Contract::from_json(&published()).unwrap();
+    let mut payments = Payments::default();
+    payments
+        .mock_charge(Any, Any)
+        .returns_contract(&contract, "Payments::refund");
+}
+
+#[test]
+#[should_panic(expected = "the stub of Payments::health in the contract does not return")]
+fn panics_on_other_return_types() {
+    let contract = Contract::from_json(&published()).unwrap();
+    let mut payments = Payments::default();
+    payments
+        .mock_charge(Any, Any)
+        .returns_contract(&contract, "Payments::health");
+}
@@ -0,0 +1,56 @@
+use mry::Any;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raw(u32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Domain(u32);
+
+#[mry::mry]
+impl From<Raw> for Domain {
+    fn from(raw: Raw) -> Self {
+        Domain(raw.0)
+    }
+}
+
+#[mry::mry]
+impl TryFrom<i64> for Domain {
+    type Error = String;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        u32::try_from(value)
+            .map(Domain)
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[mry::lock(<Domain as From<Raw>>::from)]
+#[test]
+fn mocks_from() {
+    Domain::mock_from(Any).returns(Domain(0));
+
+    assert_eq!(Domain::from(Raw(1)), Domain(0));
+}
+
+#[mry::lock(<Domain as TryFrom<i64>>::try_from)]
+#[test]
+fn mocks_try_from() {
+    Domain::mock_try_from(-1).returns(Ok(Domain(1)));
+
+    assert_eq!(Domain::try_from(-1i64), Ok(Domain(1)));
+}
+
+#[mry::mry(suffix = "u32")]
+impl From<u32> for Domain {
+    fn from(value: u32) -> Self {
+        Domain(value)
+    }
+}
+
+#[mry::lock(<Domain as From<u32>>::from)]
+#[test]
+fn mocks_from_with_suffix() {
+    Domain::mock_from_u32(Any).returns(Domain(0));
+
+    assert_eq!(Domain::from(1u32), Domain(0));
+}
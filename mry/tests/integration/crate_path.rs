@@ -0,0 +1,65 @@
+/// Re-exports mry like an internal test-utils crate does
+mod test_utils {
+    pub use mry::*;
+}
+
+use test_utils::Any;
+
+#[test_utils::mry(crate = "test_utils")]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[test_utils::mry(crate = "test_utils")]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        format!("{}: {}", self.name, "meow".repeat(count))
+    }
+}
+
+#[test_utils::mry(crate = "test_utils")]
+trait Dog {
+    fn bark(&self, count: usize) -> String;
+}
+
+#[test_utils::mry(crate = "test_utils")]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[test]
+fn method() {
+    let mut cat = Cat::default();
+
+    cat.mock_meow(Any).returns("Called".into());
+
+    assert_eq!(cat.meow(2), "Called");
+}
+
+#[test]
+fn trait_method() {
+    let mut dog = MockDog::default();
+
+    dog.mock_bark(2).returns("Called".into());
+
+    assert_eq!(dog.bark(2), "Called");
+}
+
+#[test_utils::lock(crate = "test_utils", hello)]
+#[test]
+fn function() {
+    mock_hello(Any).returns("Called".into());
+
+    assert_eq!(hello(2), "Called");
+}
+
+#[test]
+fn sequence() {
+    let mut dog = MockDog::default();
+
+    let _sequence =
+        test_utils::sequence!(crate = "test_utils", dog.bark(1).returns("Called".into()));
+
+    assert_eq!(dog.bark(1), "Called");
+}
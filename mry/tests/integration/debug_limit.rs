@@ -0,0 +1,49 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[mry::mry]
+#[derive(Default)]
+struct Storage {}
+
+#[mry::mry]
+impl Storage {
+    fn upload(&self, blob: Vec<u8>) -> bool {
+        todo!("{:?}", blob)
+    }
+}
+
+#[test]
+fn truncates_arguments_and_elides_repeats() {
+    let mut storage = Storage::default();
+    storage.mock_upload(mry::Any).returns(true);
+    storage.upload(vec![0; 4096]);
+    storage.upload(vec![0; 4096]);
+
+    mry::set_debug_limit(16);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        storage.mock_upload(mry::Any).assert_never_called()
+    }));
+
+    let payload = result.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "Expected Storage::upload never to be called with the pattern, but it was called 2 times:\n    ([0, 0, 0, 0, 0,... (12291 chars in total) ×2"
+    );
+}
+
+#[test]
+fn truncates_arguments_of_the_object() {
+    let mut storage = Storage::default();
+    storage.mry.set_debug_limit(8);
+    storage.mock_upload(mry::Any).returns(true);
+    storage.upload(vec![0; 4096]);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        storage.mock_upload(mry::Any).assert_never_called()
+    }));
+
+    let payload = result.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "Expected Storage::upload never to be called with the pattern, but it was called 1 times:\n    ([0, 0, ... (12291 chars in total)"
+    );
+}
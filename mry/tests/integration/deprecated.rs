@@ -0,0 +1,28 @@
+#![deny(deprecated)]
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    #[deprecated(note = "use purr")]
+    fn meow(&self) -> String {
+        "meow".into()
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn mocks_deprecated_method() {
+    let mut cat = Cat::default();
+    cat.mock_meow().returns("Called".into());
+
+    assert_eq!(cat.meow(), "Called".to_string());
+}
+
+#[test]
+fn mock_is_not_deprecated() {
+    let mut cat = Cat::default();
+    cat.mock_meow().returns("Called".into());
+}
@@ -0,0 +1,61 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use mry::{eq, Any};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Config {
+    retries: u8,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Worker {}
+
+#[mry::mry]
+impl Worker {
+    fn process(&self, #[mry(deref)] config: Arc<Config>) -> u8 {
+        config.retries
+    }
+
+    #[allow(clippy::boxed_local)]
+    fn load(&self, #[mry(deref)] config: Box<Config>, #[mry(deref)] name: Arc<str>) -> bool {
+        config.retries > 0 && !name.is_empty()
+    }
+
+    fn share(&self, #[mry(deref)] config: &Rc<Config>) -> usize {
+        Rc::strong_count(config)
+    }
+}
+
+#[test]
+fn matches_inner_values() {
+    let mut worker = Worker::default();
+    worker.mock_process(eq(Config { retries: 3 })).returns(30);
+    worker.mock_process(Any).returns(0);
+
+    assert_eq!(worker.process(Arc::new(Config { retries: 3 })), 30);
+    assert_eq!(worker.process(Arc::new(Config { retries: 4 })), 0);
+    worker.mock_process(Config { retries: 4 }).assert_called(1);
+}
+
+#[test]
+fn matches_boxed_values_and_strings() {
+    let mut worker = Worker::default();
+    worker
+        .mock_load(Config { retries: 1 }, "primary")
+        .returns(false);
+    worker.mock_load(Any, Any).calls_real_impl();
+
+    assert!(!worker.load(Box::new(Config { retries: 1 }), "primary".into()));
+    assert!(worker.load(Box::new(Config { retries: 1 }), "replica".into()));
+}
+
+#[test]
+fn matches_values_behind_rc() {
+    let mut worker = Worker::default();
+    worker.mock_share(Config::default()).returns(9);
+
+    let config = Rc::new(Config::default());
+    assert_eq!(worker.share(&config), 9);
+}
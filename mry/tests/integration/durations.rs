@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn nap(&self, millis: u64) -> u64 {
+        std::thread::sleep(Duration::from_millis(millis));
+        millis
+    }
+}
+
+#[test]
+fn measures_real_impls() {
+    let mut cat = Cat::default();
+    cat.mock_nap(0).returns(0);
+    cat.mock_nap(Any).calls_real_impl().measure_durations();
+
+    assert_eq!(cat.nap(0), 0);
+    assert_eq!(cat.nap(20), 20);
+    assert_eq!(cat.nap(10), 10);
+
+    let durations = cat.mock_nap(Any).durations();
+    assert_eq!(durations.len(), 2);
+    assert!(durations[0] >= Duration::from_millis(20));
+    assert!(durations[1] >= Duration::from_millis(10));
+}
+
+#[test]
+fn not_measured_by_default() {
+    let mut cat = Cat::default();
+    cat.mock_nap(Any).calls_real_impl();
+
+    cat.nap(1);
+
+    assert!(cat.mock_nap(Any).durations().is_empty());
+}
@@ -0,0 +1,54 @@
+use mry::Any;
+
+#[mry::mry(dyn_mocks)]
+pub trait Cat {
+    fn meow(&self, count: usize) -> String;
+
+    fn rename(&mut self, name: String);
+}
+
+struct RealCat;
+
+impl Cat for RealCat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+
+    fn rename(&mut self, _name: String) {}
+}
+
+fn build(mock: bool) -> Box<dyn Cat + Send> {
+    if mock {
+        Box::new(MockCat::default())
+    } else {
+        Box::new(RealCat)
+    }
+}
+
+#[test]
+fn mocks_through_trait_object() {
+    let mut cat: Box<dyn Cat> = Box::new(MockCat::default());
+    cat.mock_meow(Any).returns("Called".into());
+    <dyn Cat>::mock_rename(&mut *cat, "Tama").returns(());
+
+    assert_eq!(cat.meow(2), "Called");
+    cat.rename("Tama".into());
+
+    cat.mock_meow(2).assert_called(1);
+    cat.mock_rename("Tama").assert_called(1);
+}
+
+#[test]
+fn mocks_send_trait_object() {
+    let mut cat = build(true);
+    cat.mock_meow(Any).returns("Called".into());
+
+    assert_eq!(cat.meow(2), "Called");
+}
+
+#[test]
+#[should_panic(expected = "the trait object of Cat is not MockCat")]
+fn panics_on_other_implementations() {
+    let mut cat = build(false);
+    cat.mock_meow(Any).returns("Called".into());
+}
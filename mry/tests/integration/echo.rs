@@ -0,0 +1,73 @@
+use mry::Any;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct User {
+    id: u32,
+    name: String,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Repository {}
+
+#[mry::mry]
+impl Repository {
+    fn save(&self, user: User) -> User {
+        user
+    }
+
+    fn save_id(&self, user: User) -> u32 {
+        user.id
+    }
+
+    fn name(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn pair(&self, id: u32, name: String) -> (u32, String) {
+        (id, name)
+    }
+}
+
+fn user() -> User {
+    User {
+        id: 1,
+        name: "Tama".into(),
+    }
+}
+
+#[test]
+fn echoes() {
+    let mut repository = Repository::default();
+
+    repository.mock_save(Any).echoes();
+
+    assert_eq!(repository.save(user()), user());
+}
+
+#[test]
+fn echoes_str_as_string() {
+    let mut repository = Repository::default();
+
+    repository.mock_name(Any).echoes();
+
+    assert_eq!(repository.name("Tama"), "Tama");
+}
+
+#[test]
+fn echoes_field() {
+    let mut repository = Repository::default();
+
+    repository.mock_save_id(Any).echoes_field(|user| user.id);
+
+    assert_eq!(repository.save_id(user()), 1);
+}
+
+#[test]
+fn identity() {
+    let mut repository = Repository::default();
+
+    repository.mock_pair(Any, Any).identity();
+
+    assert_eq!(repository.pair(1, "Tama".into()), (1, "Tama".to_string()));
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use async_std::task::{sleep, spawn};
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Notifier {}
+
+#[mry::mry]
+impl Notifier {
+    fn send(&self, message: String) {
+        let _ = message;
+    }
+}
+
+// Sleeps on the timer thread of mry, which the sim feature removes
+#[cfg(not(feature = "sim"))]
+#[async_std::test]
+async fn waits_for_background_call() {
+    let mut notifier = Notifier::default();
+    notifier.mock_send(mry::Any).returns(());
+
+    let background = notifier.clone();
+    spawn(async move {
+        sleep(Duration::from_millis(20)).await;
+        background.send("done".into());
+    });
+
+    notifier
+        .mock_send("done")
+        .assert_called_eventually(1, Duration::from_secs(5))
+        .await;
+}
+
+// Sleeps on the timer thread of mry, which the sim feature removes
+#[cfg(not(feature = "sim"))]
+#[async_std::test]
+#[should_panic(
+    expected = "Expected Notifier::send to be called 1 times within 10ms, but it was called 0 times"
+)]
+async fn times_out() {
+    let mut notifier = Notifier::default();
+
+    notifier
+        .mock_send(mry::Any)
+        .assert_called_eventually(1, Duration::from_millis(10))
+        .await;
+}
+
+#[async_std::test]
+async fn notified_on_call() {
+    let mut notifier = Notifier::default();
+    notifier.mock_send(mry::Any).returns(());
+    let notified = notifier.mock_send("done").notified_on_call();
+
+    let background = notifier.clone();
+    spawn(async move {
+        background.send("working".into());
+        sleep(Duration::from_millis(20)).await;
+        background.send("done".into());
+    });
+    notified.await;
+
+    notifier.mock_send(mry::Any).assert_called(2);
+}
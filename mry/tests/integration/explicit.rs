@@ -0,0 +1,38 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry(explicit)]
+impl Cat {
+    #[mry::mock]
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[test]
+fn mocks_marked_methods() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("purr".into());
+
+    assert_eq!(cat.meow(2), "purr");
+    cat.mock_meow(2).assert_called(1);
+}
+
+#[test]
+fn keeps_unmarked_methods_real() {
+    let cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    assert_eq!(cat.name(), "Tama");
+}
@@ -0,0 +1,63 @@
+#[derive(Debug, Clone, PartialEq)]
+enum Error {
+    Timeout,
+    Other(String),
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Db {}
+
+#[mry::mry]
+impl Db {
+    fn save(&self, key: String) -> Result<(), Error> {
+        todo!("{}", key)
+    }
+}
+
+fn mocked_db() -> Db {
+    let mut db = Db::default();
+    db.mock_save(mry::Any)
+        .on_failpoint("failpoint.db.save", |arg| match arg.as_deref() {
+            Some("timeout") => Err(Error::Timeout),
+            other => Err(Error::Other(other.unwrap_or_default().to_string())),
+        });
+    db.mock_save(mry::Any).returns(Ok(()));
+    db
+}
+
+#[test]
+fn toggles_failure_at_runtime() {
+    let db = mocked_db();
+
+    assert_eq!(db.save("a".into()), Ok(()));
+
+    mry::set_failpoint("failpoint.db.save", "return(timeout)").unwrap();
+    let failed = db.save("a".into());
+    mry::set_failpoint("failpoint.db.save", "return").unwrap();
+    let failed_without_arg = db.save("a".into());
+    mry::remove_failpoint("failpoint.db.save");
+
+    assert_eq!(failed, Err(Error::Timeout));
+    assert_eq!(failed_without_arg, Err(Error::Other("".into())));
+    assert_eq!(db.save("a".into()), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "disk is on fire")]
+fn panics_by_failpoint() {
+    let mut db = Db::default();
+    db.mock_save(mry::Any)
+        .on_failpoint("failpoint.db.save.panic", |_| Ok(()));
+    mry::set_failpoint("failpoint.db.save.panic", "panic(disk is on fire)").unwrap();
+
+    db.save("a".into()).ok();
+}
+
+#[test]
+fn rejects_unknown_action() {
+    assert_eq!(
+        mry::set_failpoint("failpoint.db.save.unknown", "sleep(10)"),
+        Err("unknown failpoint action `sleep(10)`".to_string())
+    );
+}
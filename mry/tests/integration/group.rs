@@ -0,0 +1,58 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Auth {}
+
+#[mry::mry]
+impl Auth {
+    fn login(&self, user: String) -> bool {
+        todo!("{}", user)
+    }
+
+    fn logout(&self, user: String) {
+        todo!("{}", user)
+    }
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Audit {}
+
+#[mry::mry]
+impl Audit {
+    fn record(&self, event: String) {
+        todo!("{}", event)
+    }
+}
+
+#[test]
+fn verifies_group() {
+    let mut auth = Auth::default();
+    let mut audit = Audit::default();
+    auth.mock_login(Any).returns(true).group("authorization");
+    audit.mock_record(Any).returns(()).group("auditing");
+
+    auth.login("alice".into());
+    audit.record("login".into());
+
+    mry::verify_group("authorization");
+    mry::verify_group("auditing");
+}
+
+#[test]
+#[should_panic(
+    expected = "Expectations of the group authorization failed:\n    the rule of Auth::login was never used\n    the rule of Auth::logout was never used"
+)]
+fn reports_failures_of_group() {
+    let mut auth = Auth::default();
+    let mut audit = Audit::default();
+    auth.mock_login(Any).returns(true).group("authorization");
+    auth.mock_logout(Any).returns(()).group("authorization");
+    audit.mock_record(Any).returns(()).group("auditing");
+
+    audit.record("login".into());
+
+    mry::verify_group("auditing");
+    mry::verify_group("authorization");
+}
@@ -0,0 +1,77 @@
+use mry::{Any, AnyMockHandle};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+
+    fn purr(&self) -> bool {
+        todo!()
+    }
+}
+
+#[test]
+fn collects_mocks_of_different_signatures() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("Called".into());
+    cat.mock_purr().returns(true);
+
+    let handles: Vec<AnyMockHandle> = vec![cat.mock_meow(Any).handle(), cat.mock_purr().handle()];
+    cat.meow(2);
+    cat.purr();
+
+    for handle in &handles {
+        handle.verify();
+    }
+    assert_eq!(
+        handles.iter().map(AnyMockHandle::dump).collect::<Vec<_>>(),
+        vec![
+            "Cat::meow called 1 times with 1 rules (0 unused)",
+            "Cat::purr called 1 times with 1 rules (0 unused)",
+        ]
+    );
+}
+
+#[test]
+fn lists_handles_of_an_object() {
+    let mut cat = Cat::default();
+    cat.mock_purr().returns(true);
+    cat.mock_meow(Any).returns("Called".into());
+
+    let handles = cat.mry.handles();
+
+    assert_eq!(
+        handles.iter().map(AnyMockHandle::name).collect::<Vec<_>>(),
+        vec!["Cat::meow", "Cat::purr"]
+    );
+}
+
+#[test]
+fn resets_all() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("Called".into());
+    cat.meow(2);
+
+    for handle in cat.mry.handles() {
+        handle.reset();
+    }
+
+    assert_eq!(
+        cat.mry.handles()[0].dump(),
+        "Cat::meow called 0 times with 0 rules (0 unused)"
+    );
+}
+
+#[test]
+#[should_panic(expected = "1 rules of Cat::purr were never used")]
+fn verify_panics_on_unused_rules() {
+    let mut cat = Cat::default();
+    cat.mock_purr().returns(true);
+
+    cat.mry.handles().iter().for_each(AnyMockHandle::verify);
+}
@@ -0,0 +1,100 @@
+use mry::{Any, Harness};
+
+#[mry::mry]
+#[derive(Default)]
+struct Auth {}
+
+#[mry::mry]
+impl Auth {
+    fn login(&self, user: String) -> bool {
+        todo!("{}", user)
+    }
+
+    fn logout(&self, user: String) {
+        todo!("{}", user)
+    }
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Audit {}
+
+#[mry::mry]
+impl Audit {
+    fn record(&self, event: String) {
+        todo!("{}", event)
+    }
+}
+
+#[test]
+fn verifies_all_objects() {
+    let mut auth = Auth::default();
+    let mut audit = Audit::default();
+    let mut harness = Harness::new("login");
+    harness
+        .register("auth", &mut auth.mry)
+        .register("audit", &mut audit.mry);
+
+    auth.mock_login(Any).returns(true);
+    audit.mock_record(Any).returns(());
+    auth.login("alice".into());
+    audit.record("login".into());
+    audit.record("logout".into());
+
+    harness.verify_all();
+    assert_eq!(
+        harness.journal(),
+        "[login]\n    auth: Auth::login called 1 times\n    audit: Audit::record called 2 times"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "[logout] Unused rules:\n    auth: 1 rules of Auth::logout were never used\n    audit: 1 rules of Audit::record were never used"
+)]
+fn reports_unused_rules_of_all_objects() {
+    let mut auth = Auth::default();
+    let mut audit = Audit::default();
+    let mut harness = Harness::new("logout");
+    harness
+        .register("auth", &mut auth.mry)
+        .register("audit", &mut audit.mry);
+
+    auth.mock_login(Any).returns(true);
+    auth.mock_logout(Any).returns(());
+    audit.mock_record(Any).returns(());
+    auth.login("alice".into());
+
+    harness.verify_all();
+}
+
+#[test]
+fn resets_all_objects() {
+    let mut auth = Auth::default();
+    let mut harness = Harness::new("reset");
+    harness.register("auth", &mut auth.mry);
+
+    auth.mock_login(Any).returns(true);
+    harness.reset_all();
+    auth.mock_login(Any).returns(false);
+
+    assert!(!auth.login("alice".into()));
+    auth.mock_login(Any).assert_called(1);
+}
+
+#[test]
+fn journals_duplicate_rules() {
+    mry::set_duplicate_rule(mry::DuplicateRule::Warn);
+    let mut auth = Auth::default();
+    let mut harness = Harness::new("copy-paste");
+    harness.register("auth", &mut auth.mry);
+
+    auth.mock_login("alice").returns(true);
+    auth.mock_login("alice").returns(false);
+
+    assert!(auth.login("alice".into()));
+    assert_eq!(
+        harness.journal(),
+        "[copy-paste]\n    auth: Auth::login called 1 times\n    auth: warning: Auth::login has a rule with the same pattern registered earlier, which shadows the new one"
+    );
+}
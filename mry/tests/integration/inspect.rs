@@ -0,0 +1,53 @@
+use std::sync::mpsc::channel;
+
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[test]
+fn inspects_calls_served_by_other_behaviors() {
+    let (tx, rx) = channel();
+    let mut cat = Cat::default();
+    cat.mock_meow(Any)
+        .inspect(move |(count,)| tx.send(*count).unwrap())
+        .returns("purr".into());
+
+    assert_eq!(cat.meow(1), "purr");
+    assert_eq!(cat.meow(2), "purr");
+
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn inspects_only_matching_calls() {
+    let (tx, rx) = channel();
+    let mut cat = Cat::default();
+    cat.mock_meow(mry::gt(2))
+        .inspect(move |(count,)| tx.send(*count).unwrap());
+    cat.mock_meow(Any).calls_real_impl();
+
+    assert_eq!(cat.meow(1), "meow");
+    assert_eq!(cat.meow(3), "meowmeowmeow");
+
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![3]);
+}
+
+#[test]
+#[should_panic(expected = "too many meows")]
+fn asserts_in_inspector() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any)
+        .inspect(|(count,)| assert!(*count < 10, "too many meows"))
+        .returns_default();
+
+    cat.meow(10);
+}
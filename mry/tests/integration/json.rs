@@ -0,0 +1,76 @@
+use mry::{any, json_eq, pred, Any};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Clone, PartialEq, Serialize)]
+struct Event {
+    id: u32,
+    tags: Vec<String>,
+    ts: u64,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Client {}
+
+#[mry::mry]
+impl Client {
+    fn post(&self, body: Value) -> bool {
+        body.is_object()
+    }
+
+    fn publish(&self, event: Event) -> bool {
+        event.id > 0
+    }
+}
+
+#[test]
+fn matches_value_structurally() {
+    let mut client = Client::default();
+    client
+        .mock_post(json_eq!({"id": 1, "ts": any(), "meta": {"tags": ["a", null]}}))
+        .returns(true);
+    client.mock_post(Any).returns(false);
+
+    assert!(client.post(json!({"meta": {"tags": ["a", null]}, "ts": 99, "id": 1})));
+    assert!(!client.post(json!({"id": 2, "ts": 99, "meta": {"tags": ["a", null]}})));
+    assert!(!client.post(json!({"id": 1, "meta": {"tags": ["a", null]}})));
+    assert!(!client.post(json!({"id": 1, "ts": 99, "meta": {"tags": ["a"]}, "extra": true})));
+}
+
+#[test]
+fn matches_serializable_payload() {
+    let mut client = Client::default();
+    let tag = "urgent".to_string();
+    client
+        .mock_publish(json_eq!({
+            "id": 3,
+            "tags": [tag, pred(|value: &Value| value.is_string())],
+            "ts": any(),
+        }))
+        .returns(true);
+    client.mock_publish(Any).returns(false);
+
+    let event = |id, tags: [&str; 2]| Event {
+        id,
+        tags: tags.map(String::from).to_vec(),
+        ts: 1_700_000_000,
+    };
+    assert!(client.publish(event(3, ["urgent", "other"])));
+    assert!(!client.publish(event(3, ["other", "urgent"])));
+    assert!(!client.publish(event(4, ["urgent", "other"])));
+}
+
+#[test]
+#[should_panic(
+    expected = "pattern: (json_eq!({\"id\": 1, \"ts\": any}))\n    calls:\n    ({\"id\":2})"
+)]
+fn renders_pattern_and_arguments() {
+    let mut client = Client::default();
+    client.mock_post(Any).returns(true);
+    client.post(json!({"id": 2}));
+
+    client
+        .mock_post(json_eq!({"id": 1, "ts": any()}))
+        .assert_called(1);
+}
@@ -0,0 +1,147 @@
+use mry::Any;
+
+#[test]
+fn mocks_function_local_struct() {
+    #[mry::mry]
+    #[derive(Default)]
+    struct Cat {}
+
+    #[mry::mry]
+    impl Cat {
+        fn meow(&self, count: usize) -> String {
+            "meow".repeat(count)
+        }
+
+        #[mry(skip)]
+        fn purr(&self) -> String {
+            "purr".into()
+        }
+    }
+
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("Called".into());
+    assert_eq!(cat.meow(2), "Called");
+    assert_eq!(cat.purr(), "purr");
+}
+
+#[test]
+fn mocks_function_local_callback() {
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Config {
+        retries: u8,
+    }
+
+    #[mry::mry]
+    #[derive(Default)]
+    struct Store {}
+
+    #[mry::mry]
+    impl Store {
+        fn update(&self, f: impl FnOnce(&mut Config)) {
+            f(&mut Config::default())
+        }
+    }
+
+    let mut store = Store::default();
+    store
+        .mock_update(Any)
+        .returns_with(|f: mry::Callback<Config>| {
+            assert_eq!(f.applied_to(Config::default()).retries, 3);
+        });
+    store.update(|config| config.retries = 3);
+    store.mock_update(Any).assert_called(1);
+}
+
+#[test]
+fn mocks_function_local_trait() {
+    #[mry::mry]
+    trait Cat {
+        fn meow(&self, count: usize) -> String;
+    }
+
+    let mut cat = MockCat::default();
+    cat.mock_meow(Any).returns("Called".into());
+    assert_eq!(cat.meow(2), "Called");
+}
+
+#[test]
+fn mocks_function_local_static_function() {
+    #[mry::mry]
+    struct Cat {}
+
+    #[mry::mry]
+    impl Cat {
+        fn meow(count: usize) -> String {
+            "meow".repeat(count)
+        }
+    }
+
+    mry::locked!(Cat::meow => {
+        Cat::mock_meow(Any).returns("Called".into());
+        assert_eq!(Cat::meow(2), "Called");
+    });
+}
+
+#[test]
+fn mocks_function_local_function() {
+    #[mry::mry]
+    fn hello(count: usize) -> String {
+        "hello".repeat(count)
+    }
+
+    mry::locked!(hello => {
+        mock_hello(Any).returns("Called".into());
+        assert_eq!(hello(2), "Called");
+    });
+}
+
+#[test]
+fn mocks_function_local_self_types() {
+    #[mry::mry]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Cat {
+        name: String,
+    }
+
+    #[mry::mry]
+    impl Cat {
+        fn named(name: String) -> Self {
+            Self {
+                name,
+                ..Default::default()
+            }
+        }
+
+        fn adopt(&self, other: Self) -> Self {
+            other
+        }
+    }
+
+    let mut cat = Cat::default();
+    cat.mock_adopt(Any).returns_with(|other: Cat| other);
+    let tama = mry::locked!(Cat::named => {
+        Cat::mock_named(Any).calls_real_impl();
+        Cat::named("Tama".into())
+    });
+    assert_eq!(cat.adopt(tama.clone()), tama);
+}
+
+#[test]
+fn mocks_function_local_generic_struct() {
+    #[mry::mry]
+    #[derive(Default)]
+    struct Cage<T> {
+        inner: T,
+    }
+
+    #[mry::mry]
+    impl<T: Clone + Send + 'static> Cage<T> {
+        fn get(&self) -> T {
+            self.inner.clone()
+        }
+    }
+
+    let mut cage = Cage::<u8>::default();
+    cage.mock_get().returns(3);
+    assert_eq!(cage.get(), 3);
+}
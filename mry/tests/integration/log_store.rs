@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use mry::{Any, LogStore, Mutex};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+/// Keeps one entry per distinct input with the number of calls
+#[derive(Default)]
+struct DedupStore(Vec<(Arc<Mutex<(usize,)>>, usize)>);
+
+impl LogStore<(usize,)> for DedupStore {
+    fn push(&mut self, input: Arc<Mutex<(usize,)>>) {
+        let value = *input.lock();
+        match self.0.iter_mut().find(|(log, _)| *log.lock() == value) {
+            Some((_, count)) => *count += 1,
+            None => self.0.push((input, 1)),
+        }
+    }
+
+    fn count(&self, matches: &dyn Fn(&(usize,)) -> bool) -> usize {
+        self.0
+            .iter()
+            .filter(|(log, _)| matches(&log.lock()))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Arc<Mutex<(usize,)>>> + '_> {
+        Box::new(self.0.iter().map(|(log, _)| log))
+    }
+}
+
+#[test]
+fn custom_log_store_keeps_counts() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any)
+        .log_store(DedupStore::default())
+        .returns("Called".into());
+
+    for count in 0..1000 {
+        cat.meow(count % 2);
+    }
+
+    cat.mock_meow(Any).assert_called(1000);
+    cat.mock_meow(1).assert_called(500);
+}
+
+/// Keeps no calls
+struct DiscardStore;
+
+impl LogStore<(usize,)> for DiscardStore {
+    fn push(&mut self, _input: Arc<Mutex<(usize,)>>) {}
+
+    fn count(&self, _matches: &dyn Fn(&(usize,)) -> bool) -> usize {
+        0
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &Arc<Mutex<(usize,)>>> + '_> {
+        Box::new(std::iter::empty())
+    }
+}
+
+#[test]
+fn counts_calls_without_logs() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any)
+        .log_store(DiscardStore)
+        .returns("meow".into());
+
+    for count in 0..1000 {
+        cat.meow(count % 10);
+    }
+
+    assert_eq!(cat.mock_meow(Any).call_count(), 1000);
+    // Other patterns count the recorded calls
+    assert_eq!(cat.mock_meow(3).call_count(), 0);
+}
@@ -0,0 +1,125 @@
+use mry::{Any, Projected};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    id: u32,
+    body: Vec<u8>,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Archive {}
+
+#[mry::mry]
+impl Archive {
+    fn store(&self, #[mry(log_with = |report| -> u32 { report.id })] report: &Report) -> bool {
+        !report.body.is_empty()
+    }
+
+    fn replace(
+        &self,
+        #[mry(log_with = "|report| -> usize { report.body.len() }")] report: Report,
+        force: bool,
+    ) -> bool {
+        force
+    }
+
+    fn label(&self, #[mry(log_with = |name| -> usize { name.len() })] name: &str) -> String {
+        name.to_uppercase()
+    }
+}
+
+#[mry::mry]
+trait Sink {
+    fn send(&self, #[mry(log_with = |report| -> u32 { report.id })] report: Report);
+}
+
+fn report(id: u32) -> Report {
+    Report {
+        id,
+        body: vec![0; 1024],
+    }
+}
+
+#[test]
+fn matches_whole_argument() {
+    let mut archive = Archive::default();
+    archive.mock_store(report(3)).returns(true);
+    archive
+        .mock_store(mry::pred(|report: &Report| report.body.is_empty()))
+        .returns(true);
+    archive.mock_store(Any).returns(false);
+
+    assert!(archive.store(&report(3)));
+    assert!(archive.store(&Report {
+        id: 4,
+        body: Vec::new(),
+    }));
+    assert!(!archive.store(&report(5)));
+    archive.mock_store(Any).assert_called(3);
+}
+
+#[test]
+fn asserts_projection_of_expected_value() {
+    let mut archive = Archive::default();
+    archive.mock_store(Any).returns(true);
+
+    archive.store(&report(3));
+
+    archive
+        .mock_store(Report {
+            id: 3,
+            body: Vec::new(),
+        })
+        .assert_called(1);
+    archive.mock_store(report(4)).assert_never_called();
+}
+
+#[test]
+#[should_panic(
+    expected = "the pattern predicate cannot match the calls after they return, as log_with records only the projection of the argument"
+)]
+fn panics_on_predicate_after_call() {
+    let mut archive = Archive::default();
+    archive.mock_store(Any).returns(true);
+
+    archive.store(&report(3));
+
+    archive
+        .mock_store(mry::pred(|report: &Report| report.id == 3))
+        .assert_called(1);
+}
+
+#[test]
+fn matches_str_argument_as_string() {
+    let mut archive = Archive::default();
+    archive.mock_label("draft").returns("DRAFT".to_string());
+    archive.mock_label(Any).returns(String::new());
+
+    assert_eq!(archive.label("draft"), "DRAFT");
+    assert_eq!(archive.label("final"), "");
+    archive.mock_label("other").assert_called(2);
+}
+
+#[test]
+fn records_projection_of_owned_argument() {
+    let mut archive = Archive::default();
+    archive
+        .mock_replace(Any, Any)
+        .returns_with(|len: Projected<Report, usize>, force| force && *len > 0);
+
+    assert!(archive.replace(report(1), true));
+    archive.mock_replace(report(2), true).assert_called(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Sink::send never to be called with the pattern, but it was called 1 times:\n    (7,)"
+)]
+fn shows_projection_in_panic_message() {
+    let mut sink = MockSink::default();
+    sink.mock_send(Any).returns(());
+    sink.send(report(7));
+
+    sink.mock_send(Any).assert_never_called();
+}
@@ -1,17 +1,75 @@
+mod after;
 mod async_fn_in_trait;
 mod async_fn_trait_variant;
 mod async_method;
 mod async_trait;
 mod bounds;
+mod callback;
+mod captor;
+mod cause;
+mod cfg_gate;
+mod cfg_select;
+mod channel;
+mod collections;
+mod combinators;
+mod contract;
+mod conversion;
+mod crate_path;
+mod debug_limit;
+mod deprecated;
+mod deref;
+mod durations;
+mod dyn_mocks;
+mod echo;
+mod eventually;
+mod explicit;
+mod failpoint;
 mod function_style_macro;
 mod generics;
+mod group;
+mod handle;
+mod harness;
 mod impl_trait;
+mod inspect;
 mod iterator;
+mod json;
+mod local_items;
+mod log_store;
+mod log_with;
+mod many_args;
+mod memoize;
+mod method_meta;
+mod misuse;
+mod mixed_items;
 mod mock_trait;
+mod move_args;
+mod mry_match;
 mod mut_param;
 mod nested_mock;
+mod no_implicit_prelude;
 mod not_clone;
+mod panics_with;
+mod parallel_config;
 mod partial_mock;
+mod platform_cfg;
+mod recursion;
+mod redact;
+mod reentrancy;
 mod reference_and_pattern;
+mod result_option;
+mod returns_each;
+mod returns_map;
+mod returns_with_self;
+mod scope;
+mod sequence;
+mod shared;
 mod simple_case;
+mod skip;
 mod static_function;
+mod stub;
+mod test_token;
+mod trait_associated_fn;
+mod ufcs;
+mod unsafe_args;
+mod unsized_outputs;
+mod weighted;
@@ -0,0 +1,38 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Form {}
+
+#[mry::mry]
+impl Form {
+    #[allow(clippy::too_many_arguments)]
+    fn submit(
+        &self,
+        a: u8,
+        b: u8,
+        c: u8,
+        d: u8,
+        e: u8,
+        f: u8,
+        g: u8,
+        h: u8,
+        i: u8,
+        j: u8,
+        k: u8,
+        l: &str,
+    ) -> String {
+        format!("{}{}", [a, b, c, d, e, f, g, h, i, j, k].len(), l)
+    }
+}
+
+#[test]
+fn mocks_twelve_arguments() {
+    let mut form = Form::default();
+    form.mock_submit(1, Any, Any, Any, Any, Any, Any, Any, Any, Any, Any, "x")
+        .returns_with(|a, _, _, _, _, _, _, _, _, _, k, l| format!("{}{}{}", a, k, l));
+
+    assert_eq!(form.submit(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, "x"), "111x");
+    form.mock_submit(Any, Any, Any, Any, Any, Any, Any, Any, Any, Any, Any, Any)
+        .assert_called(1);
+}
@@ -0,0 +1,35 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[mry::mry]
+#[derive(Default)]
+struct Pricing {}
+
+#[mry::mry]
+impl Pricing {
+    fn price(&self, sku: String, quantity: usize) -> u64 {
+        todo!("{} x {}", sku, quantity)
+    }
+}
+
+#[test]
+fn memoizes_outputs_per_arguments() {
+    let mut pricing = Pricing::default();
+    let computed = Arc::new(AtomicUsize::new(0));
+    let counter = computed.clone();
+    pricing
+        .mock_price(mry::Any, mry::Any)
+        .memoizes(move |sku: String, quantity| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            sku.len() as u64 * quantity as u64
+        });
+
+    assert_eq!(pricing.price("apple".into(), 2), 10);
+    assert_eq!(pricing.price("apple".into(), 2), 10);
+    assert_eq!(pricing.price("kiwi".into(), 2), 8);
+    assert_eq!(pricing.price("apple".into(), 3), 15);
+    assert_eq!(pricing.price("kiwi".into(), 2), 8);
+
+    assert_eq!(computed.load(Ordering::SeqCst), 3);
+    pricing.mock_price(mry::Any, mry::Any).assert_called(5);
+}
@@ -0,0 +1,117 @@
+use mry::MethodMeta;
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry(methods)]
+impl Cat {
+    fn meow(&self, base: &str, count: usize) -> String {
+        format!("{}: {}", self.name, base.repeat(count))
+    }
+
+    fn name() -> Option<String> {
+        None
+    }
+}
+
+#[mry::mry(methods)]
+impl PartialEq for Cat {
+    fn eq(&self, other: &Cat) -> bool {
+        self.name == other.name
+    }
+}
+
+#[mry::mry]
+pub trait Dog {
+    fn bark(&self);
+}
+
+#[test]
+fn lists_methods() {
+    assert_eq!(
+        Cat::MRY_METHODS,
+        &[
+            MethodMeta {
+                name: "meow",
+                arity: 2,
+                arg_types: &["&str", "usize"],
+                return_type: "String",
+            },
+            MethodMeta {
+                name: "name",
+                arity: 0,
+                arg_types: &[],
+                return_type: "Option<String>",
+            },
+        ]
+    );
+}
+
+#[test]
+fn lists_trait_impl_methods() {
+    assert_eq!(
+        Cat::MRY_PARTIAL_EQ_METHODS,
+        &[MethodMeta {
+            name: "eq",
+            arity: 1,
+            arg_types: &["&Cat"],
+            return_type: "bool",
+        }]
+    );
+}
+
+#[mry::mry(methods = "MRY_EXTRA_METHODS")]
+impl Cat {
+    fn purr(&self) {}
+}
+
+#[test]
+fn lists_methods_with_name() {
+    assert_eq!(Cat::MRY_EXTRA_METHODS[0].name, "purr");
+}
+
+#[test]
+fn lists_trait_methods() {
+    let mut dog = MockDog::default();
+    dog.mock_bark().returns(());
+    dog.bark();
+
+    assert_eq!(
+        MockDog::MRY_METHODS,
+        &[MethodMeta {
+            name: "bark",
+            arity: 0,
+            arg_types: &[],
+            return_type: "()",
+        }]
+    );
+}
+
+#[mry::mry(methods = "MRY_PLATFORM_METHODS")]
+impl Cat {
+    #[cfg(unix)]
+    fn home(&self) -> String {
+        "/home".into()
+    }
+
+    #[cfg(not(unix))]
+    fn home(&self) -> String {
+        "C:\\Users".into()
+    }
+}
+
+#[test]
+fn lists_methods_of_each_platform_once() {
+    assert_eq!(
+        Cat::MRY_PLATFORM_METHODS,
+        &[MethodMeta {
+            name: "home",
+            arity: 0,
+            arg_types: &[],
+            return_type: "String",
+        }]
+    );
+}
@@ -0,0 +1,41 @@
+use mry::{Any, Misuse};
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[mry::mry]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[test]
+fn missing_lock() {
+    mry::assert_misuse(Misuse::MissingLock, || {
+        mock_hello(Any).returns("Called".into());
+    });
+}
+
+#[test]
+fn mock_not_found() {
+    let mut cat = Cat::default();
+    cat.mock_meow(1).returns("Called".into());
+
+    mry::assert_misuse(Misuse::MockNotFound, || cat.meow(2));
+}
+
+#[test]
+fn called_more_than_once() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns_once("Called".into());
+    cat.meow(1);
+
+    mry::assert_misuse(Misuse::CalledMoreThanOnce, || cat.meow(1));
+}
@@ -0,0 +1,71 @@
+use mry::Any;
+
+pub trait Shelter {
+    type Pet;
+    const CAPACITY: usize;
+
+    fn adopt(&mut self, name: String) -> Option<Self::Pet>;
+
+    fn release(&mut self, pet: Self::Pet) -> bool;
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    pub const MAX: usize = 3;
+    const GREETING: &'static str = "meow";
+
+    fn meow(&self, count: usize) -> String {
+        Self::GREETING.repeat(count.min(Self::MAX))
+    }
+
+    fn buffer(&self, bytes: [u8; Self::MAX]) -> usize {
+        bytes.len()
+    }
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct CatShelter {}
+
+#[mry::mry]
+impl Shelter for CatShelter {
+    type Pet = String;
+    const CAPACITY: usize = 2;
+
+    fn adopt(&mut self, name: String) -> Option<Self::Pet> {
+        (name.len() <= Self::CAPACITY).then_some(name)
+    }
+
+    fn release(&mut self, pet: Self::Pet) -> bool {
+        !pet.is_empty()
+    }
+}
+
+#[test]
+fn keeps_associated_consts() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).calls_real_impl();
+    cat.mock_buffer(Any).returns(9);
+
+    assert_eq!(cat.meow(5), "meowmeowmeow");
+    assert_eq!(cat.buffer([0; Cat::MAX]), 9);
+}
+
+#[test]
+fn keeps_associated_types_of_trait_impls() {
+    let mut shelter = CatShelter::default();
+    shelter.mock_adopt("Tama").returns(Some("Mike".into()));
+    shelter.mock_adopt(Any).calls_real_impl();
+
+    assert_eq!(shelter.adopt("Tama".into()), Some("Mike".into()));
+    assert_eq!(shelter.adopt("Mi".into()), Some("Mi".into()));
+    assert_eq!(shelter.adopt("Kuro".into()), None);
+    assert_eq!(CatShelter::CAPACITY, 2);
+
+    shelter.mock_release("Tama").returns(false);
+    assert!(!shelter.release("Tama".into()));
+}
@@ -30,3 +30,23 @@ fn with_mock() {
 
     assert_eq!(cat.meow(2), "Called with 2".to_string());
 }
+
+#[test]
+fn fully_stubbed() {
+    let mut cat = MockCat::default();
+    cat.mock_meow(mry::Any).returns("meow".into());
+    cat.mock_meow_default(mry::Any).calls_real_impl();
+
+    cat.assert_fully_stubbed();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected every method of the mock to be stubbed, but:\n    Cat::meow_default has no rule"
+)]
+fn not_fully_stubbed() {
+    let mut cat = MockCat::default();
+    cat.mock_meow(mry::Any).returns("meow".into());
+
+    cat.assert_fully_stubbed();
+}
@@ -0,0 +1,142 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use mry::Any;
+
+#[derive(Debug, Default)]
+struct Blob {
+    bytes: Vec<u8>,
+    clones: Arc<AtomicUsize>,
+}
+
+impl Clone for Blob {
+    fn clone(&self) -> Self {
+        self.clones.fetch_add(1, Ordering::Relaxed);
+        Self {
+            bytes: self.bytes.clone(),
+            clones: self.clones.clone(),
+        }
+    }
+}
+
+impl Blob {
+    fn new(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn clones(&self) -> usize {
+        self.clones.load(Ordering::Relaxed)
+    }
+}
+
+/// Argument which can't be cloned into the log
+#[derive(Debug)]
+struct Stream(Vec<u8>);
+
+#[mry::mry]
+#[derive(Default)]
+struct Uploader {}
+
+#[mry::mry]
+impl Uploader {
+    #[mry(move_args)]
+    fn upload(&self, bucket: &str, mut blob: Blob) -> usize {
+        blob.bytes.push(0);
+        bucket.len() + blob.bytes.len()
+    }
+
+    #[mry(move_args)]
+    fn verify(&self, blob: &Blob) -> bool {
+        !blob.bytes.is_empty()
+    }
+
+    #[mry(move_args(unlogged))]
+    fn archive(&self, stream: Stream) -> usize {
+        stream.0.len()
+    }
+
+    #[mry(move_args)]
+    fn checksum(blob: Blob, (seed, _): (u8, u8)) -> u8 {
+        blob.bytes
+            .iter()
+            .fold(seed, |sum, byte| sum.wrapping_add(*byte))
+    }
+}
+
+#[test]
+fn matches_without_cloning() {
+    let mut uploader = Uploader::default();
+    uploader
+        .mock_upload("photos", mry::pred(|blob: &Blob| blob.bytes.len() > 2))
+        .returns(0);
+    uploader.mock_upload(Any, Any).calls_real_impl();
+
+    let blob = Blob::new(&[1, 2, 3]);
+    let clones = blob.clones.clone();
+    assert_eq!(uploader.upload("photos", blob), 0);
+    assert_eq!(clones.load(Ordering::Relaxed), 0);
+
+    let blob = Blob::new(&[1]);
+    let clones = blob.clones.clone();
+    assert_eq!(uploader.upload("photos", blob), 8);
+    assert_eq!(clones.load(Ordering::Relaxed), 1);
+
+    uploader.mock_upload("photos", Any).assert_called(2);
+}
+
+#[test]
+#[mry::lock(Uploader::checksum)]
+fn moves_arguments_of_static_functions() {
+    Uploader::mock_checksum(Any, (1, 0)).returns(9);
+    Uploader::mock_checksum(Any, Any).calls_real_impl();
+
+    let blob = Blob::new(&[1, 2]);
+    let clones = blob.clones.clone();
+    assert_eq!(Uploader::checksum(blob, (1, 0)), 9);
+    assert_eq!(Uploader::checksum(Blob::new(&[1, 2]), (2, 0)), 5);
+    assert_eq!(clones.load(Ordering::Relaxed), 0);
+
+    let blob = Blob::new(&[1, 2]);
+    assert_eq!(Uploader::checksum(blob.clone(), (2, 0)), 5);
+    assert_eq!(blob.clones(), 2);
+}
+
+#[test]
+fn matches_borrowed_arguments_in_place() {
+    let mut uploader = Uploader::default();
+    uploader
+        .mock_verify(mry::pred(|blob: &Blob| blob.bytes.len() > 2))
+        .returns(false);
+    uploader.mock_verify(Any).calls_real_impl();
+
+    let blob = Blob::new(&[1, 2, 3]);
+    assert!(!uploader.verify(&blob));
+    assert!(uploader.verify(&Blob::new(&[1])));
+    // Only the copy into the log
+    assert_eq!(blob.clones(), 1);
+
+    uploader
+        .mock_verify(mry::pred(|blob: &Blob| blob.bytes.len() > 2))
+        .assert_called(1);
+}
+
+#[test]
+fn counts_unlogged_calls_without_cloning() {
+    let mut uploader = Uploader::default();
+    uploader
+        .mock_archive(mry::pred(|stream: &Stream| stream.0.is_empty()))
+        .returns(9);
+    uploader.mock_archive(Any).calls_real_impl();
+
+    assert_eq!(uploader.archive(Stream(vec![1, 2])), 2);
+    assert_eq!(uploader.archive(Stream(Vec::new())), 9);
+
+    uploader.mock_archive(Any).assert_called(2);
+    // Only the call served by the rule is logged
+    uploader
+        .mock_archive(mry::pred(|stream: &Stream| !stream.0.is_empty()))
+        .assert_never_called();
+}
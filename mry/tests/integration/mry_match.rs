@@ -0,0 +1,135 @@
+use mry::{gt, Any, MryMatch};
+
+#[derive(Clone, Debug, PartialEq, MryMatch)]
+pub enum Command {
+    Insert { key: String, value: u32 },
+    Delete(String),
+    Clear,
+    Type(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, MryMatch)]
+pub struct Pair<T> {
+    left: T,
+    right: T,
+}
+
+#[derive(Clone, Debug, PartialEq, MryMatch)]
+pub struct User {
+    id: u32,
+    name: String,
+    age: u8,
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Store {}
+
+#[mry::mry]
+impl Store {
+    fn execute(&self, command: Command) -> bool {
+        matches!(command, Command::Clear)
+    }
+
+    fn save(&self, user: User) -> bool {
+        user.age > 0
+    }
+
+    fn swap(&self, pair: Pair<u8>) -> Pair<u8> {
+        Pair {
+            left: pair.right,
+            right: pair.left,
+        }
+    }
+}
+
+#[test]
+fn matches_variant_fields() {
+    let mut store = Store::default();
+    store
+        .mock_execute(CommandMatcher::insert(Any, 5))
+        .returns(true);
+    store.mock_execute(Any).returns(false);
+
+    assert!(store.execute(Command::Insert {
+        key: "a".into(),
+        value: 5
+    }));
+    assert!(!store.execute(Command::Insert {
+        key: "a".into(),
+        value: 6
+    }));
+    assert!(!store.execute(Command::Delete("a".into())));
+    store
+        .mock_execute(CommandMatcher::delete("a"))
+        .assert_called(1);
+    store.mock_execute(CommandMatcher::clear()).assert_called(0);
+    assert!(!store.execute(Command::Clear));
+}
+
+#[test]
+fn matches_struct_fields() {
+    let mut store = Store::default();
+    let pair = Pair { left: 1, right: 2 };
+    store
+        .mock_swap(PairMatcher::new(1, Any))
+        .returns(pair.clone());
+
+    assert_eq!(store.swap(Pair { left: 1, right: 9 }), pair);
+}
+
+#[test]
+fn matches_subset_of_fields() {
+    let mut store = Store::default();
+    store
+        .mock_save(UserMatcher {
+            id: 3.into(),
+            age: gt(20),
+            ..Default::default()
+        })
+        .returns(true);
+    store.mock_save(Any).returns(false);
+
+    let user = |id, age| User {
+        id,
+        name: "Tama".into(),
+        age,
+    };
+    assert!(store.save(user(3, 30)));
+    assert!(!store.save(user(3, 10)));
+    assert!(!store.save(user(4, 30)));
+    store
+        .mock_save(UserMatcher {
+            name: "Tama".into(),
+            ..Default::default()
+        })
+        .assert_called(3);
+}
+
+#[test]
+fn matches_generic_struct_fields() {
+    let mut store = Store::default();
+    let pair = Pair { left: 1, right: 2 };
+    store
+        .mock_swap(PairMatcher {
+            right: gt(5),
+            ..Default::default()
+        })
+        .returns(pair.clone());
+    store.mock_swap(Any).returns(Pair { left: 0, right: 0 });
+
+    assert_eq!(store.swap(Pair { left: 1, right: 9 }), pair);
+    assert_eq!(store.swap(Pair { left: 1, right: 2 }).left, 0);
+}
+
+#[test]
+fn escapes_keyword_variants() {
+    let mut store = Store::default();
+    store
+        .mock_execute(CommandMatcher::r#type(gt(3)))
+        .returns(true);
+    store.mock_execute(Any).returns(false);
+
+    assert!(store.execute(Command::Type(4)));
+    assert!(!store.execute(Command::Type(3)));
+}
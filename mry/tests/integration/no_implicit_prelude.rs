@@ -0,0 +1,54 @@
+#![no_implicit_prelude]
+
+use ::mry::Any;
+use ::std::string::{String, ToString};
+use ::std::{assert_eq, default::Default};
+
+#[::mry::mry]
+#[derive(Default)]
+struct Cat {
+    name: String,
+}
+
+#[::mry::mry]
+impl Cat {
+    fn meow(&self, base: &str, count: usize) -> String {
+        ::std::format!("{}: {}", self.name, base.repeat(count))
+    }
+}
+
+#[::mry::mry]
+trait Dog {
+    fn bark(&self, count: usize) -> String;
+}
+
+#[::mry::mry]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[::core::prelude::v1::test]
+fn method() {
+    let mut cat = Cat::default();
+
+    cat.mock_meow("meow", Any).returns("Called".to_string());
+
+    assert_eq!(cat.meow("meow", 2), "Called");
+}
+
+#[::core::prelude::v1::test]
+fn trait_method() {
+    let mut dog = MockDog::default();
+
+    dog.mock_bark(2).returns_with(|count| "bow".repeat(count));
+
+    assert_eq!(dog.bark(2), "bowbow");
+}
+
+#[::mry::lock(hello)]
+#[::core::prelude::v1::test]
+fn function() {
+    mock_hello(Any).returns("Called".to_string());
+
+    assert_eq!(hello(2), "Called");
+}
@@ -0,0 +1,36 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn panics_with_message() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).panics_with("boom");
+
+    cat.meow(1);
+}
+
+#[test]
+fn panics_only_for_matching_calls() {
+    let mut cat = Cat::default();
+    cat.mock_meow(0).panics_with("boom");
+    cat.mock_meow(Any).returns("purr".into());
+
+    let panic = catch_unwind(AssertUnwindSafe(|| cat.meow(0))).unwrap_err();
+
+    assert_eq!(panic.downcast_ref::<String>().unwrap(), "boom");
+    assert_eq!(cat.meow(1), "purr");
+    cat.mock_meow(0).assert_called(1);
+}
@@ -0,0 +1,47 @@
+use std::thread;
+
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[test]
+fn configures_from_fixture_thread() {
+    let mut cat = Cat::default();
+    let locator = cat.mock_meow(Any);
+
+    thread::spawn(move || {
+        locator.returns("Called".into());
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(cat.meow(2), "Called");
+}
+
+#[test]
+fn configures_from_many_threads() {
+    let mut cat = Cat::default();
+    let locators: Vec<_> = (0..8).map(|count| (count, cat.mock_meow(count))).collect();
+
+    thread::scope(|scope| {
+        for (count, locator) in locators {
+            scope.spawn(move || {
+                locator.returns(format!("Called {}", count));
+            });
+        }
+    });
+
+    for count in 0..8 {
+        assert_eq!(cat.meow(count), format!("Called {}", count));
+    }
+    cat.mock_meow(Any).assert_called(8);
+}
@@ -28,3 +28,62 @@ fn partial_mock() {
 
     assert_eq!(cat.meow(2), "hellohello".to_string());
 }
+
+#[test]
+fn otherwise_real() {
+    let mut cat: Cat = Cat {
+        _name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow_single().calls_real_impl();
+    cat.mock_meow(2)
+        .returns("stub".to_string())
+        .otherwise_real();
+    cat.mock_meow(3).returns("later stub".to_string());
+
+    assert_eq!(cat.meow(2), "stub".to_string());
+    assert_eq!(cat.meow(3), "later stub".to_string());
+    assert_eq!(cat.meow(1), "meow".to_string());
+}
+
+#[test]
+fn calls_real_impl_all() {
+    let mut cat: Cat = Cat {
+        _name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow_single().returns("hello".to_string());
+    cat.mry.calls_real_impl_all();
+    cat.mock_meow(3).returns("stub".to_string());
+
+    assert_eq!(cat.meow(2), "hellohello".to_string());
+    assert_eq!(cat.meow(3), "stub".to_string());
+    cat.mock_meow(2).assert_called(1);
+    cat.mock_meow_single().assert_called(1);
+}
+
+#[test]
+fn calls_real_impl_all_before_any_rule() {
+    let mut cat = Cat::default();
+    cat.mry.calls_real_impl_all();
+
+    assert_eq!(cat.meow(2), "meowmeow".to_string());
+    cat.mock_meow(mry::Any).assert_called(1);
+    cat.mock_meow_single().assert_called(1);
+}
+
+#[test]
+fn returns_once_then_real() {
+    let mut cat = Cat::default();
+
+    cat.mock_meow_single().calls_real_impl();
+    cat.mock_meow(mry::Any)
+        .returns_once_then_real("stub".to_string());
+
+    assert_eq!(cat.meow(2), "stub".to_string());
+    assert_eq!(cat.meow(2), "meowmeow".to_string());
+    assert_eq!(cat.meow(1), "meow".to_string());
+    cat.mock_meow(mry::Any).assert_called(3);
+}
@@ -0,0 +1,76 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    #[cfg(unix)]
+    fn separator(&self) -> String {
+        "/".into()
+    }
+
+    #[cfg(windows)]
+    fn separator(&self) -> String {
+        "\\".into()
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn separator(&self) -> String {
+        "".into()
+    }
+}
+
+#[mry::mry]
+#[cfg(target_os = "none")]
+impl Cat {
+    fn bare_metal(&self) -> String {
+        "bare metal".into()
+    }
+}
+
+#[mry::mry]
+#[cfg(unix)]
+fn hello() -> String {
+    "hello unix".into()
+}
+
+#[mry::mry]
+#[cfg(not(unix))]
+fn hello() -> String {
+    "hello".into()
+}
+
+#[test]
+fn method_under_cfg() {
+    let mut cat = Cat::default();
+
+    cat.mock_separator().returns("Called".into());
+
+    assert_eq!(cat.separator(), "Called");
+}
+
+#[mry::lock(hello)]
+#[test]
+fn function_under_cfg() {
+    mock_hello().returns("Called".into());
+
+    assert_eq!(hello(), "Called");
+}
+
+#[mry::mry]
+trait Dog {
+    #[cfg(unix)]
+    fn bark(&self) -> String;
+
+    #[cfg(not(unix))]
+    fn bark(&self) -> String;
+}
+
+#[test]
+fn trait_method_under_cfg() {
+    let mut dog = MockDog::default();
+
+    dog.mock_bark().returns("Called".into());
+
+    assert_eq!(dog.bark(), "Called");
+}
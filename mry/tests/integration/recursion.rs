@@ -0,0 +1,69 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        match count {
+            0 => String::new(),
+            _ => self.meow(count - 1) + "meow",
+        }
+    }
+
+    fn purr(&self) -> String {
+        self.hiss()
+    }
+
+    fn hiss(&self) -> String {
+        self.purr()
+    }
+}
+
+#[test]
+fn recursion_within_limit() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).calls_real_impl();
+
+    assert_eq!(cat.meow(3), "meowmeowmeow");
+    cat.mock_meow(Any).assert_called(4);
+}
+
+#[test]
+fn raised_limit() {
+    mry::set_recursion_limit(1000);
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).calls_real_impl();
+
+    assert_eq!(cat.meow(300).len(), 1200);
+}
+
+#[test]
+#[should_panic(
+    expected = "Cat::purr recursed through its real implementation 4 times, exceeding the limit of mry::set_recursion_limit. The cycle: Cat::purr -> Cat::hiss -> Cat::purr"
+)]
+fn cycle_through_mocks() {
+    mry::set_recursion_limit(4);
+    let mut cat = Cat::default();
+    cat.mock_purr().calls_real_impl();
+    cat.mock_hiss().calls_real_impl();
+
+    cat.purr();
+}
+
+#[mry::mry]
+fn countdown(count: usize) -> usize {
+    match count {
+        0 => 0,
+        _ => countdown(count - 1) + 1,
+    }
+}
+
+#[test]
+fn calls_not_passed_through_mock_are_not_counted() {
+    mry::set_recursion_limit(2);
+
+    assert_eq!(countdown(10), 10);
+}
@@ -0,0 +1,61 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Auth {}
+
+#[mry::mry]
+impl Auth {
+    fn login(&self, user: &str, #[mry(redact)] password: &str) -> bool {
+        user == "alice" && password == "hunter2"
+    }
+}
+
+#[mry::mry]
+trait Vault {
+    fn unlock(&self, #[mry(redact)] token: String) -> bool;
+}
+
+#[test]
+fn matches_redacted_argument() {
+    let mut auth = Auth::default();
+    auth.mock_login("alice", "hunter2").returns(true);
+    auth.mock_login(Any, Any).returns(false);
+
+    assert!(auth.login("alice", "hunter2"));
+    assert!(!auth.login("alice", "password"));
+    auth.mock_login(Any, "hunter2").assert_called(1);
+}
+
+#[test]
+fn behavior_reads_redacted_argument() {
+    let mut auth = Auth::default();
+    auth.mock_login(Any, Any)
+        .returns_with(|_, password: mry::Redacted<String>| password.len() > 4);
+
+    assert!(auth.login("alice", "hunter2"));
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Auth::login never to be called with the pattern, but it was called 1 times:\n    (\"alice\", «redacted»)"
+)]
+fn hides_redacted_argument_in_panic_message() {
+    let mut auth = Auth::default();
+    auth.mock_login(Any, Any).returns(true);
+    auth.login("alice", "hunter2");
+
+    auth.mock_login(Any, Any).assert_never_called();
+}
+
+#[test]
+fn redacts_trait_argument() {
+    let mut vault = MockVault::default();
+    vault.mock_unlock("secret").returns(true);
+
+    assert!(vault.unlock("secret".into()));
+    assert_eq!(
+        vault.mock_unlock(Any).explain((mry::Redacted("y".into()),)),
+        "Vault::unlock(«redacted»,) matches the pattern\n    argument `token`: matches anything"
+    );
+}
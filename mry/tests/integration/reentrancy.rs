@@ -0,0 +1,104 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Cat {
+    name: String,
+}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        self.just_meow().repeat(count)
+    }
+
+    fn just_meow(&self) -> String {
+        format!("{}: meow", self.name)
+    }
+}
+
+#[mry::mry]
+fn hello(count: usize) -> String {
+    "hello".repeat(count)
+}
+
+#[mry::mry]
+fn world() -> String {
+    "world".into()
+}
+
+#[test]
+fn behavior_calls_other_mocked_method() {
+    let mut cat = Cat::default();
+    cat.mock_just_meow().returns("mocked".into());
+    let shared = cat.clone();
+    cat.mock_meow(Any)
+        .returns_with(move |count| shared.just_meow().repeat(count));
+
+    assert_eq!(cat.meow(2), "mockedmocked".to_string());
+    cat.mock_just_meow().assert_called(1);
+}
+
+#[test]
+#[mry::lock(hello, world)]
+fn behavior_calls_other_mocked_function() {
+    mock_world().returns("mocked".into());
+    mock_hello(Any).returns_with(|count| world().repeat(count));
+
+    assert_eq!(hello(2), "mockedmocked".to_string());
+}
+
+struct MeowOnDrop(Cat);
+
+impl Drop for MeowOnDrop {
+    fn drop(&mut self) {
+        self.0.meow(1);
+    }
+}
+
+#[test]
+fn drop_in_behavior_calls_other_mocked_method() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("mocked".into());
+    let shared = cat.clone();
+    cat.mock_just_meow().returns_with(move || {
+        let _meow_on_drop = MeowOnDrop(shared.clone());
+        "just".into()
+    });
+
+    assert_eq!(cat.just_meow(), "just".to_string());
+    cat.mock_meow(1).assert_called(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Cat::meow is called while its own call is in progress on the same thread, such as from a Drop impl of a value dropped by its behavior"
+)]
+fn drop_in_behavior_calls_the_same_mocked_method() {
+    let mut cat = Cat::default();
+    cat.mock_just_meow().returns("mocked".into());
+    let shared = cat.clone();
+    cat.mock_meow(Any).returns_with(move |_| {
+        let _meow_on_drop = MeowOnDrop(shared.clone());
+        "mocked".into()
+    });
+
+    cat.meow(1);
+}
+
+#[test]
+fn drop_while_unwinding_from_behavior_calls_real_impl() {
+    let mut cat = Cat::default();
+    cat.mock_just_meow().returns("mocked".into());
+    let shared = cat.clone();
+    cat.mock_meow(Any).returns_with(move |_| -> String {
+        let _meow_on_drop = MeowOnDrop(shared.clone());
+        panic!("behavior panicked")
+    });
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cat.meow(1)));
+
+    assert!(result.is_err());
+    // The real implementation run by the reentrant call
+    cat.mock_just_meow().assert_called(1);
+}
@@ -0,0 +1,56 @@
+use mry::Any;
+
+#[derive(Debug, PartialEq)]
+struct NotClone;
+
+#[mry::mry]
+#[derive(Default)]
+struct Store {}
+
+#[mry::mry]
+impl Store {
+    fn fetch(&self, id: u32) -> Result<String, NotClone> {
+        todo!()
+    }
+
+    fn save(&self, name: String) -> Result<NotClone, String> {
+        todo!()
+    }
+
+    fn find(&self, id: u32) -> Option<String> {
+        todo!()
+    }
+
+    fn take(&self) -> Option<NotClone> {
+        todo!()
+    }
+}
+
+#[test]
+fn returns_ok_without_clone_of_error() {
+    let mut store = Store::default();
+    store.mock_fetch(Any).returns_ok("Tama".into());
+
+    assert_eq!(store.fetch(1), Ok("Tama".into()));
+    assert_eq!(store.fetch(2), Ok("Tama".into()));
+}
+
+#[test]
+fn returns_err_without_clone_of_value() {
+    let mut store = Store::default();
+    store.mock_save(Any).returns_err("closed".into());
+
+    assert_eq!(store.save("Tama".into()), Err("closed".into()));
+}
+
+#[test]
+fn returns_some_and_none() {
+    let mut store = Store::default();
+    store.mock_find(1).returns_some("Tama".into());
+    store.mock_find(Any).returns_none();
+    store.mock_take().returns_none();
+
+    assert_eq!(store.find(1), Some("Tama".into()));
+    assert_eq!(store.find(2), None);
+    assert_eq!(store.take(), None);
+}
@@ -0,0 +1,76 @@
+use mry::{Exhausted, Misuse};
+
+#[mry::mry]
+#[derive(Default)]
+struct Queue {}
+
+#[mry::mry]
+impl Queue {
+    fn pop(&self) -> Option<u8> {
+        todo!()
+    }
+}
+
+#[test]
+fn returns_values_one_by_one() {
+    let mut queue = Queue::default();
+    queue
+        .mock_pop()
+        .returns_each([Some(1), Some(2)], Exhausted::Return(None));
+
+    assert_eq!(queue.pop(), Some(1));
+    assert_eq!(queue.pop(), Some(2));
+    assert_eq!(queue.pop(), None);
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn repeats_last() {
+    let mut queue = Queue::default();
+    queue
+        .mock_pop()
+        .returns_each([Some(1), Some(2)], Exhausted::RepeatLast);
+
+    assert_eq!(queue.pop(), Some(1));
+    assert_eq!(queue.pop(), Some(2));
+    assert_eq!(queue.pop(), Some(2));
+}
+
+#[test]
+fn notes_policy_in_journal() {
+    let mut queue = Queue::default();
+    let mut harness = mry::Harness::new("queue");
+    harness.register("queue", &mut queue.mry);
+    queue
+        .mock_pop()
+        .returns_each([Some(1)], Exhausted::RepeatLast);
+
+    queue.pop();
+    queue.pop();
+    queue.pop();
+
+    assert_eq!(
+        harness.journal(),
+        "[queue]\n    queue: Queue::pop called 3 times\n    queue: Queue::pop ran out of the values of returns_each after 1 calls and follows the RepeatLast policy"
+    );
+}
+
+#[test]
+fn panics_when_exhausted() {
+    let mut queue = Queue::default();
+    queue.mock_pop().returns_each([Some(1)], Exhausted::Panic);
+
+    assert_eq!(queue.pop(), Some(1));
+    mry::assert_misuse(Misuse::Exhausted, || queue.pop());
+}
+
+#[test]
+#[should_panic(
+    expected = "Queue::pop ran out of the values of returns_each after 0 calls under the RepeatLast policy"
+)]
+fn repeat_last_panics_without_values() {
+    let mut queue = Queue::default();
+    queue.mock_pop().returns_each([], Exhausted::RepeatLast);
+
+    queue.pop();
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+#[mry::mry]
+#[derive(Default)]
+struct Catalog {}
+
+#[mry::mry]
+impl Catalog {
+    fn price(&self, sku: &str) -> u64 {
+        todo!("{}", sku)
+    }
+}
+
+#[test]
+fn returns_map() {
+    let mut catalog = Catalog::default();
+    catalog
+        .mock_price(mry::Any)
+        .returns_map(HashMap::from([("apple".into(), 100), ("kiwi".into(), 80)]));
+
+    assert_eq!(catalog.price("apple"), 100);
+    assert_eq!(catalog.price("kiwi"), 80);
+}
+
+#[test]
+fn unmatched_keys_fall_through() {
+    let mut catalog = Catalog::default();
+    catalog
+        .mock_price(mry::Any)
+        .returns_map([("apple".into(), 100)]);
+    catalog.mock_price(mry::Any).returns(0);
+
+    assert_eq!(catalog.price("apple"), 100);
+    assert_eq!(catalog.price("durian"), 0);
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Catalog::price")]
+fn panics_on_unmatched_key_without_other_rules() {
+    let mut catalog = Catalog::default();
+    catalog
+        .mock_price(mry::Any)
+        .returns_map([("apple".into(), 100)]);
+
+    catalog.price("durian");
+}
+
+#[test]
+fn returns_for() {
+    let mut catalog = Catalog::default();
+    catalog.mock_price(mry::Any).returns_for([
+        ("apple".into(), 100),
+        (
+            mry::ArgMatcher::Fn(Box::new(|sku: &String| sku.starts_with("apple"))),
+            90,
+        ),
+        (mry::Any, 0),
+    ]);
+
+    assert_eq!(catalog.price("apple"), 100);
+    assert_eq!(catalog.price("apple-pie"), 90);
+    assert_eq!(catalog.price("kiwi"), 0);
+}
+
+#[test]
+fn typed_any_in_table() {
+    let mut catalog = Catalog::default();
+    // The table is built before the locator, so `mry::Any` alone cannot infer its type
+    let table = vec![(mry::never::<String>(), 1), (mry::any::<String>(), 0)];
+    catalog.mock_price(mry::any::<String>()).returns_for(table);
+
+    assert_eq!(catalog.price("kiwi"), 0);
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Catalog::price")]
+fn never_serves_calls() {
+    let mut catalog = Catalog::default();
+    catalog.mock_price(mry::never::<String>()).returns(1);
+
+    catalog.price("kiwi");
+}
+
+#[test]
+fn returns_for_falls_through() {
+    let mut catalog = Catalog::default();
+    catalog.mock_price(mry::Any).returns_for([("apple", 100)]);
+    catalog.mock_price(mry::Any).returns(0);
+
+    assert_eq!(catalog.price("apple"), 100);
+    assert_eq!(catalog.price("kiwi"), 0);
+}
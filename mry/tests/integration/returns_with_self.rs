@@ -0,0 +1,42 @@
+#[mry::mry]
+#[derive(Default, Clone)]
+struct Client {
+    base_url: String,
+}
+
+#[mry::mry]
+impl Client {
+    fn fetch(&self, path: &str) -> String {
+        format!("GET {}{}", self.base_url, path)
+    }
+
+    fn into_base_url(self) -> String {
+        self.base_url
+    }
+}
+
+#[test]
+fn reads_fields_of_receiver() {
+    let mut client = mry::new!(Client {
+        base_url: "https://example.com".into(),
+    });
+    client
+        .mock_fetch(mry::Any)
+        .returns_with_self(|client: &Client, path: &String| {
+            format!("stub of {}{}", client.base_url, path)
+        });
+
+    assert_eq!(client.fetch("/cats"), "stub of https://example.com/cats");
+}
+
+#[test]
+fn receiver_by_value() {
+    let mut client = mry::new!(Client {
+        base_url: "https://example.com".into(),
+    });
+    client
+        .mock_into_base_url()
+        .returns_with_self(|client: &Client| client.base_url.replace("https", "http"));
+
+    assert_eq!(client.into_base_url(), "http://example.com");
+}
@@ -0,0 +1,93 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+#[mry::mry]
+fn now() -> u64 {
+    0
+}
+
+/// A helper adjusting the shared mocks temporarily
+fn at_midnight(cat: &mut Cat) -> mry::Scope {
+    let scope = mry::scope();
+    mock_now().returns(86400);
+    cat.mock_meow(Any).returns("Midnight".into());
+    scope
+}
+
+#[test]
+#[mry::lock(now)]
+fn removes_rules_of_objects_and_statics() {
+    let mut cat = Cat::default();
+    mock_now().returns(1);
+    cat.mock_meow(Any).returns("Called".into());
+
+    {
+        let _midnight = at_midnight(&mut cat);
+        assert_eq!(now(), 1);
+        assert_eq!(cat.meow(1), "Called");
+    }
+
+    assert_eq!(now(), 1);
+    assert_eq!(cat.meow(1), "Called");
+}
+
+#[test]
+#[mry::lock(now)]
+fn scoped_rules_serve_calls_without_earlier_rules() {
+    let mut cat = Cat::default();
+
+    {
+        let _midnight = at_midnight(&mut cat);
+        assert_eq!(now(), 86400);
+        assert_eq!(cat.meow(1), "Midnight");
+    }
+
+    mock_now().returns(1);
+    assert_eq!(now(), 1);
+}
+
+#[test]
+fn nested_scopes() {
+    let mut cat = Cat::default();
+
+    let outer = mry::scope();
+    cat.mock_meow(1).returns("Outer".into());
+    {
+        let _inner = mry::scope();
+        cat.mock_meow(2).returns("Inner".into());
+        assert_eq!(cat.meow(2), "Inner");
+    }
+    assert_eq!(cat.meow(1), "Outer");
+    cat.mock_meow(2).returns("Again".into());
+    assert_eq!(cat.meow(2), "Again");
+    drop(outer);
+
+    cat.mock_meow(Any).calls_real_impl();
+    assert_eq!(cat.meow(2), "meowmeow");
+}
+
+#[test]
+fn removes_otherwise_real() {
+    let mut cat = Cat::default();
+    cat.mock_meow(1).returns("Called".into());
+
+    {
+        let _scope = mry::scope();
+        cat.mock_meow(Any).otherwise_real();
+        assert_eq!(cat.meow(2), "meowmeow");
+    }
+
+    assert_eq!(cat.meow(1), "Called");
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cat.meow(2)));
+    assert!(result.is_err());
+}
@@ -0,0 +1,134 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default, Clone)]
+struct File {}
+
+#[mry::mry]
+impl File {
+    fn open(&self, path: &str) {
+        let _ = path;
+    }
+
+    fn read(&self, count: usize) -> Vec<u8> {
+        vec![0; count]
+    }
+
+    fn close(&self) {}
+}
+
+#[test]
+fn follows_sequence() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![
+        file.open("a").returns(()),
+        file.read(1).returns(b"x".to_vec()),
+        file.read(2).calls_real_impl(),
+        file.close().returns(()),
+    ];
+
+    file.open("a");
+    assert_eq!(file.read(1), b"x".to_vec());
+    assert_eq!(file.read(2), vec![0, 0]);
+    file.close();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected call #1 of the sequence to be File::open, but File::read was called"
+)]
+fn wrong_order() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![
+        file.open("a").returns(()),
+        file.read(Any).returns(b"x".to_vec()),
+    ];
+
+    file.read(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected call #1 of the sequence to be File::open, but it was called with other arguments"
+)]
+fn wrong_arguments() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![file.open("a").returns(())];
+    file.mock_open("b").returns(());
+
+    file.open("b");
+}
+
+#[test]
+#[should_panic(expected = "File::close was called after the end of the sequence")]
+fn unexpected_call() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![file.close().returns(())];
+
+    file.close();
+    file.close();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected the sequence to be completed, but call #2 (File::close) is missing"
+)]
+fn missing_call() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![file.open("a").returns(()), file.close().returns(())];
+
+    file.open("a");
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected call #2 of the sequence to be File::close, but File::read was called"
+)]
+fn other_method_between_steps() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![file.open("a").returns(()), file.close().returns(())];
+    file.mock_read(Any).returns(vec![]);
+
+    file.open("a");
+    file.read(1);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected call #2 of the sequence to be File::read, but File::close was called"
+)]
+fn later_step_out_of_order() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![
+        file.open("a").returns(()),
+        file.read(Any).returns(vec![]),
+        file.close().returns(()),
+    ];
+
+    file.open("a");
+    file.close();
+}
+
+#[test]
+fn other_method_after_end() {
+    let mut file = File::default();
+    let sequence = mry::sequence![file.open("a").returns(())];
+    file.mock_read(Any).returns(vec![]);
+
+    file.open("a");
+    sequence.assert_done();
+    assert!(file.read(1).is_empty());
+}
+
+#[test]
+fn chains_locator_methods() {
+    let mut file = File::default();
+    let _sequence = mry::sequence![
+        file.open(Any).otherwise_real(),
+        file.read(Any).calls_real_impl().measure_durations(),
+    ];
+
+    file.open("a");
+    assert_eq!(file.read(2), vec![0, 0]);
+    assert_eq!(file.mock_read(Any).durations().len(), 1);
+}
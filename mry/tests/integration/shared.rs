@@ -0,0 +1,51 @@
+use mry::Any;
+
+#[mry::mry(shared)]
+#[derive(Debug, PartialEq)]
+struct Counter {
+    step: u8,
+}
+
+#[mry::mry(shared)]
+impl Counter {
+    fn next(&self, count: u8) -> u8 {
+        count + self.step
+    }
+}
+
+/// Constructs the struct as production code does, without knowing it is mocked
+fn counter(step: u8) -> Counter {
+    Counter { step }
+}
+
+#[test]
+fn plain_struct_literals_compile_unchanged() {
+    let counter = Counter { step: 2 };
+
+    assert_eq!(counter, Counter { step: 2 });
+    assert_eq!(counter.next(1), 3);
+}
+
+#[test]
+fn instances_share_mocks() {
+    let mut first = Counter { step: 1 };
+    first.mock_next(1).returns(10);
+    first.mock_next(Any).calls_real_impl();
+
+    assert_eq!(counter(5).next(1), 10);
+    assert_eq!(counter(5).next(2), 7);
+    first.mock_next(Any).assert_called(2);
+}
+
+#[test]
+fn mocks_are_not_shared_with_other_threads() {
+    let mut counter = Counter { step: 1 };
+    counter.mock_next(Any).returns(10);
+
+    assert_eq!(
+        std::thread::spawn(|| Counter { step: 1 }.next(1))
+            .join()
+            .unwrap(),
+        2
+    );
+}
@@ -53,6 +53,18 @@ fn meow_returns_with() {
     assert_eq!(cat.meow(2), "Called with 2".to_string());
 }
 
+#[test]
+fn meow_returns_with_ref() {
+    let mut cat: Cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    cat.mock_meow(2)
+        .returns_with_ref(|count| format!("Called with {}", count));
+
+    assert_eq!(cat.meow(2), "Called with 2".to_string());
+}
+
 #[test]
 fn assert_called() {
     let mut cat = Cat {
@@ -167,6 +179,25 @@ fn returns_once_not_clone_value() {
     cat.mock_meow(0).assert_called(1);
 }
 
+#[test]
+fn returns_default() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns_default();
+
+    assert_eq!(cat.meow(2), "");
+    cat.mock_meow(2).assert_called(1);
+}
+
+#[test]
+#[should_panic(expected = "Cat::meow was called more than once, but the value of returns_once")]
+fn returns_once_called_twice() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns_once("Called".into());
+
+    cat.meow(1);
+    cat.meow(1);
+}
+
 #[test]
 fn assert_called_for_specific_case() {
     let mut cat = Cat {
@@ -214,3 +245,294 @@ fn str_can_be_mockable_as_owned() {
 
     cat.mock_change_name_from_str(Any).assert_called(1);
 }
+
+#[test]
+fn assert_used_counts_only_the_rule() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    let meow_3 = cat.mock_meow(3).returns("Called with 3".into());
+    let meow_any = cat.mock_meow(Any).returns("Called".into());
+
+    cat.meow(3);
+    cat.meow(3);
+    cat.meow(4);
+
+    meow_3.assert_used(2);
+    meow_any.assert_used(1);
+    cat.mock_meow(Any).assert_called(3);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected the rule of Cat::meow to be used 1 times, but it was used 0 times"
+)]
+fn assert_used_fails_when_served_by_another_rule() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow(Any).returns("Called".into());
+    let meow_3 = cat.mock_meow(3).returns("Called with 3".into());
+
+    cat.meow(3);
+
+    meow_3.assert_used(1);
+}
+
+#[test]
+fn assert_never_called_allows_other_arguments() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow(Any).returns("Called".into());
+
+    cat.meow(2);
+
+    cat.mock_meow(3).assert_never_called();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Cat::meow never to be called with the pattern, but it was called 1 times:\n    (3,)"
+)]
+fn assert_never_called_fails() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow(Any).returns("Called".into());
+
+    cat.meow(2);
+    cat.meow(3);
+
+    cat.mock_meow(3).assert_never_called();
+}
+
+#[test]
+fn within_removes_rules_after_closure() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    let called = cat
+        .mock_meow(2)
+        .returns("Called".into())
+        .within(|| cat.meow(2));
+    cat.mock_meow(Any).calls_real_impl();
+
+    assert_eq!(called, "Called".to_string());
+    assert_eq!(cat.meow(2), "Tama: meowmeow".to_string());
+}
+
+#[test]
+fn within_removes_rules_when_closure_panics() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    let locator = cat.mock_meow(2).returns("Called".into());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        locator.within(|| {
+            cat.meow(2);
+            panic!("failed in the closure");
+        })
+    }));
+    cat.mock_meow(Any).calls_real_impl();
+
+    assert!(result.is_err());
+    assert_eq!(cat.meow(2), "Tama: meowmeow".to_string());
+}
+
+#[test]
+fn within_used() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow(Any)
+        .returns("Called".into())
+        .within_used(2, || {
+            cat.meow(1);
+            cat.meow(2);
+        });
+
+    cat.mock_meow(Any).assert_called(2);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected the rule of Cat::meow to be used 1 times, but it was used 0 times"
+)]
+fn within_used_fails() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    cat.mock_meow(Any)
+        .returns("Called".into())
+        .within_used(1, || {});
+}
+
+#[test]
+fn explain() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        cat.mock_meow(3).explain((2,)),
+        "Cat::meow(2,) does not match the pattern\n    argument `count`: does not equal the expected value"
+    );
+    assert_eq!(
+        cat.mock_meow(Any).explain((2,)),
+        "Cat::meow(2,) matches the pattern\n    argument `count`: matches anything"
+    );
+}
+
+#[test]
+fn eq_by() {
+    #[derive(Clone, Debug)]
+    struct Name(String);
+
+    #[mry::mry]
+    #[derive(Default)]
+    struct Cat {}
+
+    #[mry::mry]
+    impl Cat {
+        fn greet(&self, name: Name) -> String {
+            name.0
+        }
+    }
+
+    let mut cat = Cat::default();
+    cat.mock_greet(mry::ArgMatcher::eq_by(Name("tama".into()), |a, b| {
+        a.0.eq_ignore_ascii_case(&b.0)
+    }))
+    .returns("Called".into());
+
+    assert_eq!(cat.greet(Name("Tama".into())), "Called");
+}
+
+#[test]
+fn assert_deltas() {
+    #[mry::mry]
+    #[derive(Default)]
+    struct Task {}
+
+    #[mry::mry]
+    impl Task {
+        fn set_progress(&self, pct: u8) {
+            todo!("{}", pct)
+        }
+    }
+
+    let mut task = Task::default();
+    task.mock_set_progress(Any).returns(());
+
+    task.set_progress(10);
+    task.set_progress(50);
+    task.set_progress(50);
+    task.set_progress(100);
+
+    task.mock_set_progress(Any)
+        .assert_called(4)
+        .deltas(|a, b| b >= a);
+}
+
+#[test]
+fn assert_called_once_per_key() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    cat.mock_meow(Any).returns("Called".into());
+
+    cat.meow(1);
+    cat.meow(3);
+    cat.meow(2);
+
+    let called = cat.mock_meow(Any).assert_called(3).each(1);
+    assert_eq!(called.histogram(), vec![((1,), 1), ((3,), 1), ((2,), 1)]);
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected Cat::meow to be called 2 times, but it was called 3 times\n    pattern: (gt(0))\n    calls:\n    (1)\n    (3) ×2\n    histogram:\n    (1) → 1\n    (3) → 2"
+)]
+fn assert_called_shows_histogram() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    cat.mock_meow(Any).returns("Called".into());
+
+    cat.meow(1);
+    cat.meow(3);
+    cat.meow(3);
+
+    cat.mock_meow(mry::gt(0)).assert_called(2);
+}
+
+#[test]
+fn assert_no_interactions() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    cat.mock_meow(Any).returns("Called".into());
+
+    cat.mry.assert_no_interactions();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expected no interactions with the object, but:\n    Cat::just_meow was called 1 times\n    Cat::meow was called 2 times"
+)]
+fn assert_no_interactions_fails() {
+    let mut cat = Cat {
+        name: "Tama".into(),
+        ..Default::default()
+    };
+    cat.mock_meow(Any).returns("Called".into());
+    cat.mock_just_meow().returns("Called".into());
+
+    cat.meow(1);
+    cat.meow(2);
+    cat.just_meow();
+
+    cat.mry.assert_no_interactions();
+}
+
+#[mry::mry]
+#[derive(Default)]
+struct Bank {}
+
+#[mry::mry]
+impl Bank {
+    fn transfer(&self, (from, to): (u8, u8), amount: u32) -> bool {
+        from != to && amount > 0
+    }
+}
+
+#[test]
+fn explains_by_parameter_names() {
+    let mut bank = Bank::default();
+
+    assert_eq!(
+        bank.mock_transfer(Any, 5).explain(((1, 2), 3)),
+        "Bank::transfer((1, 2), 3) does not match the pattern\n    argument `from_to`: matches anything\n    argument `amount`: does not equal the expected value"
+    );
+}
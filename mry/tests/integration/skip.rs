@@ -0,0 +1,32 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    #[mry(skip)]
+    fn meow(&self, count: usize) -> String {
+        self.name().repeat(count)
+    }
+
+    fn name(&self) -> String {
+        "Tama".into()
+    }
+}
+
+#[mry::mry]
+impl Cat {
+    #[mry(skip)]
+    fn new_kitten() -> Self {
+        Self::default()
+    }
+}
+
+#[test]
+fn calls_skipped_method_for_real() {
+    let mut cat = Cat::new_kitten();
+    cat.mock_name().returns("Mike".to_string());
+
+    assert_eq!(cat.meow(2), "MikeMike");
+    cat.mock_name().assert_called(1);
+}
@@ -78,3 +78,129 @@ fn hello_returns() {
 #[should_panic(expected = "hello is locked but no used.")]
 #[test]
 fn hello_not_used() {}
+
+#[test]
+fn isolate_without_lock() {
+    mry::isolate(|| {
+        mock_hello(Any).returns("Isolated".to_string());
+
+        assert_eq!(hello(2), "Isolated".to_string());
+    });
+}
+
+#[test]
+fn isolate_calls_real_impl_of_unstubbed_functions() {
+    mry::isolate(|| {
+        mock_hello(Any).returns("Isolated".to_string());
+
+        assert_eq!(Cat::meow(2), "meowmeow");
+        assert_eq!(hello(2), "Isolated".to_string());
+    });
+}
+
+#[test]
+fn isolate_in_parallel() {
+    mry::isolate(|| {
+        mock_hello(Any).returns("Isolated in parallel".to_string());
+        Cat::mock_meow(Any).calls_real_impl();
+
+        assert_eq!(hello(2), "Isolated in parallel".to_string());
+        assert_eq!(Cat::meow(2), "meowmeow");
+    });
+}
+
+#[test]
+fn isolate_as_shares_with_spawned_threads() {
+    mry::isolate_as("isolate_as_shares_with_spawned_threads", || {
+        mock_hello(Any).returns("Shared".to_string());
+
+        std::thread::spawn(|| {
+            mry::isolate_as("isolate_as_shares_with_spawned_threads", || hello(2))
+        })
+        .join()
+        .map(|hello| assert_eq!(hello, "Shared".to_string()))
+        .unwrap();
+    });
+}
+
+#[test]
+fn detects_rules_leaked_from_another_test() {
+    mry::isolate_as("detects_rules_leaked_from_another_test", || {
+        let in_test = |name: &str, f: fn() -> String| {
+            std::thread::Builder::new()
+                .name(name.into())
+                .spawn(move || mry::isolate_as("detects_rules_leaked_from_another_test", f))
+                .unwrap()
+                .join()
+        };
+        in_test("first_test", || {
+            mock_hello(Any).returns("Leaked".to_string());
+            hello(1)
+        })
+        .unwrap();
+
+        let panic = in_test("second_test", || {
+            mock_hello(2).returns("Own".to_string());
+            hello(2)
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            panic.downcast_ref::<String>().unwrap(),
+            "hello matched a rule configured in the test first_test, but it is called in the test second_test. The rule leaked from the other test; lock the function with mry::lock or isolate the tests with their own ids"
+        );
+    });
+}
+
+#[test]
+fn locked_block() {
+    let expected = "Called".to_string();
+
+    let actual = mry::locked!(hello => {
+        mock_hello(Any).returns(expected.clone());
+        hello(2)
+    });
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn recovers_from_panic_while_locked() {
+    let result = std::panic::catch_unwind(|| {
+        mry::locked!(hello => {
+            panic!("test failed before mocking");
+        })
+    });
+    assert!(result.is_err());
+
+    let actual = mry::locked!(hello => {
+        mock_hello(Any).returns("Called".to_string());
+        hello(2)
+    });
+
+    assert_eq!(actual, "Called".to_string());
+}
+
+#[mry::mry]
+fn now() -> u64 {
+    0
+}
+
+/// A helper adjusting the global fake temporarily
+fn at_midnight<T>(f: impl FnOnce() -> T) -> T {
+    mock_now().returns(86400).within(f)
+}
+
+#[mry::lock(now)]
+#[test]
+fn nested_within_shadows_outer_rules() {
+    mock_now().returns(1);
+
+    mock_now().returns(2).within(|| {
+        assert_eq!(now(), 2);
+        at_midnight(|| assert_eq!(now(), 86400));
+        assert_eq!(now(), 2);
+    });
+
+    assert_eq!(now(), 1);
+}
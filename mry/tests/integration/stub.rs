@@ -0,0 +1,73 @@
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+
+    fn purr(&mut self, volume: u8) {
+        let _ = volume;
+    }
+
+    fn name(&self) -> String {
+        "Tama".into()
+    }
+}
+
+#[mry::mry]
+fn greet(name: String) -> String {
+    format!("hello {}", name)
+}
+
+#[test]
+fn stubs_in_a_block() {
+    let mut cat = Cat::default();
+    mry::stub! {
+        cat.meow(3) => "m",
+        cat.meow(_) => "any",
+        cat.purr(_) => (),
+        cat.name() => calls_real,
+    };
+
+    assert_eq!(cat.meow(3), "m");
+    assert_eq!(cat.meow(1), "any");
+    cat.purr(10);
+    assert_eq!(cat.name(), "Tama");
+}
+
+#[test]
+#[mry::lock(greet)]
+fn stubs_function() {
+    mry::stub! {
+        greet("Tama".to_string()) => "nya",
+        greet(_) => calls_real,
+    };
+
+    assert_eq!(greet("Tama".into()), "nya");
+    assert_eq!(greet("Mike".into()), "hello Mike");
+}
+
+struct House {
+    cat: Cat,
+}
+
+impl House {
+    fn cat(&mut self) -> &mut Cat {
+        &mut self.cat
+    }
+}
+
+#[test]
+fn stubs_method_of_chained_receiver() {
+    let mut house = House {
+        cat: Cat::default(),
+    };
+    mry::stub! {
+        house.cat().meow(_) => "nya",
+    };
+
+    assert_eq!(house.cat().meow(2), "nya");
+}
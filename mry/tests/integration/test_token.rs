@@ -0,0 +1,54 @@
+use std::sync::Mutex;
+
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+static FIXTURE: Mutex<Option<Cat>> = Mutex::new(None);
+
+#[mry::test]
+fn configures_fixture() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("fixture".into());
+
+    assert_eq!(cat.meow(1), "fixture");
+    *FIXTURE.lock().unwrap() = Some(cat);
+}
+
+#[mry::test]
+#[should_panic(
+    expected = "::touches_fixture_of_other_test touched mock state of other tests:\n  - the mock of Cat::meow of the test "
+)]
+fn touches_fixture_of_other_test() {
+    configures_fixture();
+
+    let fixture = FIXTURE.lock().unwrap();
+    fixture.as_ref().unwrap().meow(1);
+}
+
+#[mry::test]
+fn own_state() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("purr".into());
+
+    assert_eq!(cat.meow(1), "purr");
+}
+
+#[mry::test]
+#[async_std::test]
+async fn async_own_state() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("purr".into());
+    async_std::task::yield_now().await;
+
+    assert_eq!(cat.meow(1), "purr");
+}
@@ -0,0 +1,24 @@
+#[mry::mry]
+pub trait Cat {
+    fn create() -> Self;
+    fn meow(&self, count: usize) -> String;
+}
+
+#[test]
+#[mry::lock(<MockCat as Cat>::create)]
+fn create_returns_mock() {
+    let mut cat = MockCat::default();
+    cat.mock_meow(2).returns("Called with 2".to_string());
+    MockCat::mock_create().returns(cat);
+
+    let cat = MockCat::create();
+
+    assert_eq!(cat.meow(2), "Called with 2".to_string());
+    MockCat::mock_create().assert_called(1);
+}
+
+#[test]
+#[should_panic(expected = "mock not found for Cat")]
+fn create_without_lock() {
+    MockCat::create();
+}
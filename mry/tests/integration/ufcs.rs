@@ -0,0 +1,78 @@
+use mry::Any;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn meow(&self, count: usize) -> String {
+        "meow".repeat(count)
+    }
+}
+
+pub trait Pet {
+    fn feed(&self, amount: u8) -> bool;
+
+    fn adopt() -> Self
+    where
+        Self: Sized;
+}
+
+#[mry::mry]
+impl Pet for Cat {
+    fn feed(&self, amount: u8) -> bool {
+        amount > 0
+    }
+
+    fn adopt() -> Self {
+        Cat::default()
+    }
+}
+
+fn feed_generic<P: Pet>(pet: &P, amount: u8) -> bool {
+    pet.feed(amount)
+}
+
+fn feed_dyn(pet: &dyn Pet, amount: u8) -> bool {
+    pet.feed(amount)
+}
+
+#[test]
+fn inherent_method_in_any_syntax() {
+    let mut cat = Cat::default();
+    cat.mock_meow(Any).returns("Called".into());
+
+    assert_eq!(cat.meow(1), "Called");
+    assert_eq!(Cat::meow(&cat, 2), "Called");
+    assert_eq!(<Cat>::meow(&cat, 3), "Called");
+
+    cat.mock_meow(Any).assert_called(3);
+}
+
+#[test]
+fn trait_method_in_any_syntax() {
+    let mut cat = Cat::default();
+    cat.mock_feed(1).returns(false);
+
+    assert!(!cat.feed(1));
+    assert!(!Cat::feed(&cat, 1));
+    assert!(!<Cat as Pet>::feed(&cat, 1));
+    assert!(!Pet::feed(&cat, 1));
+    assert!(!feed_generic(&cat, 1));
+    assert!(!feed_dyn(&cat, 1));
+
+    cat.mock_feed(1).assert_called(6);
+}
+
+#[test]
+#[mry::lock(<Cat as Pet>::adopt)]
+fn trait_associated_fn_in_any_syntax() {
+    Cat::mock_adopt().returns_with(Cat::default);
+
+    let _: Cat = Cat::adopt();
+    let _: Cat = <Cat as Pet>::adopt();
+    let _: Cat = Pet::adopt();
+
+    Cat::mock_adopt().assert_called(3);
+}
@@ -0,0 +1,58 @@
+use std::{mem::MaybeUninit, ptr::NonNull};
+
+use mry::{Address, Any, ArgMatcher};
+
+#[mry::mry]
+#[derive(Default)]
+struct Buffer {}
+
+#[mry::mry(unsafe_args)]
+impl Buffer {
+    fn write(&self, ptr: *mut u8, len: usize) -> usize {
+        len
+    }
+
+    fn read(&self, ptr: NonNull<u8>) -> u8 {
+        unsafe { *ptr.as_ptr() }
+    }
+
+    fn init(&self, _value: MaybeUninit<u64>) -> bool {
+        false
+    }
+}
+
+#[test]
+fn matches_raw_pointer_with_any() {
+    let mut buffer = Buffer::default();
+    let mut bytes = [0u8; 4];
+
+    buffer.mock_write(Any, 4).returns(0);
+
+    assert_eq!(buffer.write(bytes.as_mut_ptr(), 4), 0);
+    buffer.mock_write(Any, Any).assert_called(1);
+}
+
+#[test]
+fn matches_address_with_predicate() {
+    let mut buffer = Buffer::default();
+    let mut bytes = [1u8; 4];
+    let ptr = NonNull::new(bytes.as_mut_ptr()).unwrap();
+    let address = ptr.as_ptr() as usize;
+
+    buffer
+        .mock_read(ArgMatcher::Fn(Box::new(move |ptr: &Address| {
+            ptr.get() == address
+        })))
+        .returns(2);
+
+    assert_eq!(buffer.read(ptr), 2);
+}
+
+#[test]
+fn maybe_uninit() {
+    let mut buffer = Buffer::default();
+
+    buffer.mock_init(Any).returns(true);
+
+    assert!(buffer.init(MaybeUninit::uninit()));
+}
@@ -0,0 +1,42 @@
+use std::rc::Rc;
+
+#[mry::mry]
+#[derive(Default)]
+struct Cat {}
+
+#[mry::mry]
+impl Cat {
+    fn name(&self) -> Box<str> {
+        "Tama".into()
+    }
+
+    fn bytes(&self) -> Box<[u8]> {
+        vec![1, 2].into_boxed_slice()
+    }
+
+    fn callback(&self) -> Rc<dyn Fn() -> u8> {
+        Rc::new(|| 1)
+    }
+}
+
+#[test]
+fn boxed_str() {
+    let mut cat = Cat::default();
+    cat.mock_name().returns("Mike".into());
+    assert_eq!(&*cat.name(), "Mike");
+}
+
+#[test]
+fn boxed_slice() {
+    let mut cat = Cat::default();
+    cat.mock_bytes().returns_with(|| vec![3].into_boxed_slice());
+    assert_eq!(&*cat.bytes(), &[3]);
+}
+
+#[test]
+fn rc_dyn_fn() {
+    let mut cat = Cat::default();
+    cat.mock_callback()
+        .returns_with(|| -> Rc<dyn Fn() -> u8> { Rc::new(|| 2) });
+    assert_eq!((cat.callback())(), 2);
+}
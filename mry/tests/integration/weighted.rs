@@ -0,0 +1,61 @@
+#[mry::mry]
+#[derive(Default)]
+struct Client {}
+
+#[mry::mry]
+impl Client {
+    fn fetch(&self) -> Result<u8, String> {
+        Ok(0)
+    }
+}
+
+fn outputs(seed: u64) -> Vec<Result<u8, String>> {
+    let mut client = Client::default();
+    client
+        .mock_fetch()
+        .returns_weighted([(0.7, Ok(1)), (0.3, Err("timeout".to_string()))], seed);
+    (0..50).map(|_| client.fetch()).collect()
+}
+
+#[test]
+fn same_seed_picks_same_values() {
+    let outputs = outputs(42);
+    assert_eq!(outputs, self::outputs(42));
+    assert!(outputs.contains(&Ok(1)));
+    assert!(outputs.contains(&Err("timeout".to_string())));
+}
+
+#[test]
+fn never_picks_zero_weight() {
+    let mut client = Client::default();
+    client
+        .mock_fetch()
+        .returns_weighted([(1.0, Ok(1)), (0.0, Err("timeout".to_string()))], 7);
+
+    assert!((0..50).all(|_| client.fetch() == Ok(1)));
+}
+
+#[test]
+fn notes_seed_in_journal() {
+    let mut client = Client::default();
+    let mut harness = mry::Harness::new("retries");
+    harness.register("client", &mut client.mry);
+    client.mock_fetch().returns_weighted([(1.0, Ok(1))], 42);
+    client.fetch().unwrap();
+
+    assert_eq!(
+        harness.journal(),
+        "[retries]\n    client: Client::fetch called 1 times\n    client: Client::fetch picks weighted values with seed 42"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "the weights of returns_weighted of Client::fetch must not be negative and must be positive in total"
+)]
+fn panics_on_negative_weight() {
+    let mut client = Client::default();
+    client
+        .mock_fetch()
+        .returns_weighted([(2.0, Ok(1)), (-1.0, Ok(2))], 7);
+}
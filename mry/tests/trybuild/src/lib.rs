@@ -0,0 +1 @@
+//! Enables the `trybuild` feature of mry for its own tests, as a dev-dependency of mry
@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{parse_quote, Attribute, Item, Path};
+
+/// Moves `#[mry::mry(after(...))]` below the listed attribute macros still on the item, so it
+/// expands again on their output and mocks the methods they generate. `None` once they have
+/// expanded or if none of them is there
+pub(crate) fn defer(
+    krate: &Path,
+    args: &TokenStream,
+    after: &[Path],
+    mut item: Item,
+) -> Option<TokenStream> {
+    let attrs = attrs_mut(&mut item)?;
+    let last = attrs
+        .iter()
+        .rposition(|attr| after.iter().any(|path| same_macro(attr.path(), path)))?;
+    attrs.insert(last + 1, parse_quote!(#[#krate::mry(#args)]));
+    Some(item.into_token_stream())
+}
+
+fn attrs_mut(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Trait(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+/// Attribute macros are told apart by the last segment of their paths, as they may be imported
+fn same_macro(a: &Path, b: &Path) -> bool {
+    a.segments.last().map(|segment| &segment.ident)
+        == b.segments.last().map(|segment| &segment.ident)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn moves_below_listed_macros() {
+        let item: Item = parse2(quote! {
+            #[getters]
+            #[derive_more::with_name]
+            #[allow(dead_code)]
+            impl Cat {}
+        })
+        .unwrap();
+
+        assert_eq!(
+            defer(
+                &parse_quote!(::mry),
+                &quote![after(getters, with_name)],
+                &[parse_quote!(getters), parse_quote!(with_name)],
+                item,
+            )
+            .unwrap()
+            .to_string(),
+            quote! {
+                #[getters]
+                #[derive_more::with_name]
+                #[::mry::mry(after(getters, with_name))]
+                #[allow(dead_code)]
+                impl Cat {}
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn expands_after_listed_macros() {
+        let item: Item = parse2(quote! {
+            #[allow(dead_code)]
+            impl Cat {}
+        })
+        .unwrap();
+
+        assert!(defer(
+            &parse_quote!(::mry),
+            &quote![after(getters)],
+            &[parse_quote!(getters)],
+            item,
+        )
+        .is_none());
+    }
+}
@@ -1,7 +1,11 @@
 use std::ops::Range;
 
+/// Arities of the generated matchers and behaviors, up to 12 as the tuples of std implement
+/// `Debug`, `Clone` and `PartialEq`
+pub(crate) const ARITIES: Range<usize> = 0..13;
+
 pub(crate) fn alphabets(range: Range<usize>) -> impl Iterator<Item = Vec<&'static str>> {
-    let alphabet = ["A", "B", "C", "D", "E", "F", "G", "H"];
+    let alphabet = ["A", "B", "C", "D", "E", "F", "G", "H", "J", "K", "L", "M"];
     range
         .into_iter()
         .map(move |index| alphabet[0..index].to_vec())
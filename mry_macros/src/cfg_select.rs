@@ -0,0 +1,129 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{braced, parse::Parse, punctuated::Punctuated, Expr, Meta, Token};
+
+/// `unix => returns("/tmp".into())`, or `_ => ...` for the other targets
+struct Arm {
+    predicate: Option<Meta>,
+    behavior: Expr,
+}
+
+impl Parse for Arm {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let predicate = if input.peek(Token![_]) {
+            input.parse::<Token![_]>()?;
+            None
+        } else {
+            Some(input.parse()?)
+        };
+        input.parse::<Token![=>]>()?;
+        Ok(Self {
+            predicate,
+            behavior: input.parse()?,
+        })
+    }
+}
+
+pub struct CfgSelect {
+    locator: Expr,
+    arms: Vec<Arm>,
+}
+
+impl Parse for CfgSelect {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let locator = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let content;
+        braced!(content in input);
+        let arms = Punctuated::<Arm, Token![,]>::parse_terminated(&content)?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            locator,
+            arms: arms.into_iter().collect(),
+        })
+    }
+}
+
+pub(crate) fn transform(input: CfgSelect) -> TokenStream {
+    let locator = &input.locator;
+    let mut earlier = Vec::new();
+    let mut arms = Vec::new();
+    for arm in &input.arms {
+        if earlier.len() < arms.len() {
+            return syn::Error::new_spanned(&arm.behavior, "arms after `_` are never selected")
+                .to_compile_error();
+        }
+        // The first arm matching the target wins, as `cfg_if!`
+        let cfg = match &arm.predicate {
+            Some(predicate) => quote![all(#predicate, not(any(#(#earlier),*)))],
+            None => quote![not(any(#(#earlier),*))],
+        };
+        let behavior = &arm.behavior;
+        arms.push(quote! {
+            #[cfg(#cfg)]
+            let __mry_locator = __mry_locator.#behavior;
+        });
+        if let Some(predicate) = &arm.predicate {
+            earlier.push(predicate);
+        }
+    }
+    quote! {
+        {
+            let __mry_locator = #locator;
+            #(#arms)*
+            __mry_locator
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn selects_first_matching_arm() {
+        let input: CfgSelect = parse2(quote! {
+            cat.mock_temp_dir(), {
+                unix => returns("/tmp".into()),
+                windows => returns("C:\\Temp".into()).times(1),
+                _ => calls_real_impl(),
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input).to_string(),
+            quote! {
+                {
+                    let __mry_locator = cat.mock_temp_dir();
+                    #[cfg(all(unix, not(any())))]
+                    let __mry_locator = __mry_locator.returns("/tmp".into());
+                    #[cfg(all(windows, not(any(unix))))]
+                    let __mry_locator = __mry_locator.returns("C:\\Temp".into()).times(1);
+                    #[cfg(not(any(unix, windows)))]
+                    let __mry_locator = __mry_locator.calls_real_impl();
+                    __mry_locator
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_arms_after_wildcard() {
+        let input: CfgSelect = parse2(quote! {
+            cat.mock_temp_dir(), {
+                _ => calls_real_impl(),
+                unix => returns("/tmp".into()),
+            }
+        })
+        .unwrap();
+
+        assert!(transform(input)
+            .to_string()
+            .contains("arms after `_` are never selected"));
+    }
+}
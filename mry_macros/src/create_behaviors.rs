@@ -2,10 +2,10 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::Ident;
 
-use crate::alphabets::alphabets;
+use crate::alphabets::{alphabets, ARITIES};
 
 pub fn create() -> TokenStream {
-    let items = alphabets(0..6).map(|args| {
+    let items = alphabets(ARITIES).map(|args| {
         let (args, types): (Vec<_>, Vec<_>) = args
             .iter()
             .map(|name| {
@@ -37,6 +37,63 @@ pub fn create() -> TokenStream {
                     }
                 }
             }
+
+            impl<#(#types,)* O, Behave> MockLocator<(#(#types,)*), O, Behave>
+            where
+                #(#types: 'static,)*
+                O: 'static,
+                Behave: Into<Behavior<(#(#types,)*), O>>,
+            {
+                /// Same as `returns_with`, but the closure borrows the arguments, so they are not
+                /// cloned for each call.
+                #[allow(unused_variables)]
+                pub fn returns_with_ref(
+                    self,
+                    mut behavior: impl FnMut(#(&#types),*) -> O + Send + 'static,
+                ) -> Self {
+                    self.behaves(Behavior::FunctionRef(Box::new(
+                        move |(#(#args,)*): &(#(#types,)*)| behavior(#(#args),*),
+                    )))
+                }
+
+                /// Same as `returns_with_ref`, but the closure also borrows the receiver of the
+                /// call, such as `|cat: &Cat, count| ...`.
+                /// Panics if the method is called on a generic type or a type with lifetimes,
+                /// which do not lend their receivers.
+                #[allow(unused_variables)]
+                pub fn returns_with_self<This: 'static>(
+                    self,
+                    mut behavior: impl FnMut(&This, #(&#types),*) -> O + Send + 'static,
+                ) -> Self {
+                    let name = self.name;
+                    self.behaves(Behavior::FunctionRef(Box::new(
+                        move |(#(#args,)*): &(#(#types,)*)| {
+                            crate::receiver::with_current_receiver(name, |this| {
+                                behavior(this, #(#args),*)
+                            })
+                        },
+                    )))
+                }
+
+                /// Same as `returns_with`, but the closure returns a future the mocked async
+                /// method awaits, such as `|count| async move { rx.recv().await.unwrap() }`.
+                /// Panics if the method is not async.
+                #[allow(unused_variables)]
+                pub fn returns_with_async<Fut>(
+                    self,
+                    mut behavior: impl FnMut(#(#types),*) -> Fut + Send + 'static,
+                ) -> Self
+                where
+                    #(#types: Clone,)*
+                    Fut: std::future::Future<Output = O> + Send + 'static,
+                {
+                    self.behaves(Behavior::Async(Box::new(
+                        move |(#(#args,)*): &(#(#types,)*)| {
+                            crate::async_output::stash(Box::pin(behavior(#(Clone::clone(#args)),*)))
+                        },
+                    )))
+                }
+            }
         }
     });
     quote![#(#items)*]
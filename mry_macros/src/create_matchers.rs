@@ -2,10 +2,10 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{Ident, Index};
 
-use crate::alphabets::alphabets;
+use crate::alphabets::{alphabets, ARITIES};
 
 pub(crate) fn create() -> TokenStream {
-    let items = alphabets(0..6).map(|args| {
+    let items = alphabets(ARITIES).map(|args| {
         let (args, types): (Vec<_>, Vec<_>) = args
             .iter()
             .map(|name| {
@@ -22,12 +22,72 @@ pub(crate) fn create() -> TokenStream {
             let index = Index::from(index);
             quote![self.#index.matches(#arg)]
         });
+        let explains = args.iter().enumerate().map(|(index, arg)| {
+            let position = index + 1;
+            let index = Index::from(index);
+            quote![format!("argument #{}: {}", #position, self.#index.explain(#arg))]
+        });
+        let indexes: Vec<_> = (0..args.len()).map(Index::from).collect();
+        let same_patterns = (0..args.len()).map(|index| {
+            let index = Index::from(index);
+            quote![self.#index.same_pattern(&other.#index)]
+        });
+        let captures = args.iter().enumerate().map(|(index, arg)| {
+            let index = Index::from(index);
+            quote![self.#index.capture(#arg);]
+        });
+        let renders = args.iter().enumerate().map(|(index, arg)| {
+            let index = Index::from(index);
+            quote![self.#index.render(#arg)]
+        });
         let args = quote![#(#args,)*];
         quote! {
             impl<#(#trait_bounds),*> Match<(#(#types,)*)> for (#matchers) {
                 fn matches(&self, (#args): &(#(#types,)*)) -> bool {
                     #(#matches &&)* true
                 }
+
+                fn explain(&self, (#args): &(#(#types,)*)) -> Vec<String> {
+                    vec![#(#explains),*]
+                }
+
+                fn describe(&self) -> Option<String> {
+                    if #(!self.#indexes.is_described() &&)* true {
+                        return None;
+                    }
+                    Some(format!("({})", <[String]>::join(&[#(self.#indexes.describe()),*], ", ")))
+                }
+
+                fn render(&self, (#args): &(#(#types,)*)) -> Option<String> {
+                    let rendered: Vec<Option<String>> = vec![#(#renders),*];
+                    if rendered.iter().all(Option::is_none) {
+                        return None;
+                    }
+                    let rendered: Vec<String> = rendered
+                        .into_iter()
+                        .map(|rendered| rendered.unwrap_or_else(|| "_".to_string()))
+                        .collect();
+                    Some(format!("({})", rendered.join(", ")))
+                }
+
+                fn is_any(&self) -> bool {
+                    #(matches!(self.#indexes, ArgMatcher::Any) &&)* true
+                }
+
+                fn capture(&self, (#args): &(#(#types,)*)) {
+                    #(#captures)*
+                }
+
+                fn as_any(&self) -> Option<&dyn std::any::Any> {
+                    Some(self)
+                }
+
+                fn same_pattern(&self, other: &dyn Match<(#(#types,)*)>) -> bool {
+                    other
+                        .as_any()
+                        .and_then(|other| other.downcast_ref::<Self>())
+                        .is_some_and(|other| #(#same_patterns &&)* true)
+                }
             }
 
             impl<#(#trait_bounds),*> From<(#matchers)> for Matcher<(#(#types,)*)> {
@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::MryAttr;
+
+/// Compile error in non-test builds where the mocks gated by `attr` are generated, such as every
+/// debug build without a `cfg` narrowing the gate, or release builds with the `bench` feature
+pub(crate) fn transform(attr: &MryAttr) -> TokenStream {
+    let cfg = attr.cfg();
+    quote! {
+        #[cfg(all(#cfg, not(test)))]
+        ::core::compile_error!("mocks of mry are compiled into this non-test build; narrow their gate by `cfg = ...` on #[mry::mry] and pass the same to mry::deny_mocks!");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn error_in_debug_builds() {
+        assert_eq!(
+            transform(&MryAttr::default()).to_string(),
+            quote! {
+                #[cfg(all(debug_assertions, not(test)))]
+                ::core::compile_error!("mocks of mry are compiled into this non-test build; narrow their gate by `cfg = ...` on #[mry::mry] and pass the same to mry::deny_mocks!");
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn error_under_the_narrowed_gate() {
+        let attr = MryAttr {
+            cfg: Some(parse_quote!(feature = "mocks")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(&attr).to_string(),
+            quote! {
+                #[cfg(all(all(debug_assertions, feature = "mocks"), not(test)))]
+                ::core::compile_error!("mocks of mry are compiled into this non-test build; narrow their gate by `cfg = ...` on #[mry::mry] and pass the same to mry::deny_mocks!");
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn error_in_release_builds_with_bench() {
+        let attr = MryAttr {
+            bench: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(&attr).to_string(),
+            quote! {
+                #[cfg(all(all(), not(test)))]
+                ::core::compile_error!("mocks of mry are compiled into this non-test build; narrow their gate by `cfg = ...` on #[mry::mry] and pass the same to mry::deny_mocks!");
+            }
+            .to_string()
+        );
+    }
+}
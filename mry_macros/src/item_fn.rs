@@ -2,14 +2,17 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::ItemFn;
 
-use crate::method;
+use crate::{method, MryAttr};
 
-pub(crate) fn transform(input: ItemFn) -> TokenStream {
+pub(crate) fn transform(input: ItemFn, attr: &MryAttr) -> TokenStream {
+    let krate = attr.krate();
     let (original, mock) = method::transform(
-        quote![mry::get_static_mocks()],
+        attr,
+        quote![#krate::get_static_mocks()],
         Default::default(),
         "",
-        quote![mry::static_record_call_and_find_mock_output],
+        quote![#krate::static_record_call_and_find_mock_output],
+        false,
         Some(&input.vis),
         &input.attrs,
         &input.sig,
@@ -25,7 +28,7 @@ pub(crate) fn transform(input: ItemFn) -> TokenStream {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use syn::parse2;
+    use syn::{parse2, parse_quote};
 
     use super::*;
 
@@ -39,13 +42,15 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 fn meow(count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = mry::static_record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&meow), "meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&meow), "meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("meow");
                     {
                         "meow".repeat(count)
                     }
@@ -53,12 +58,13 @@ mod test {
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
-                        mry::get_static_mocks(),
-                        std::any::Any::type_id(&meow),
+                pub fn mock_meow(count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
+                        ::mry::get_static_mocks(),
+                        ::core::any::Any::type_id(&meow),
                         "meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -76,13 +82,15 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 fn _meow(count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = mry::static_record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&_meow), "_meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&_meow), "_meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("_meow");
                     {
                         "meow".repeat(count)
                     }
@@ -91,12 +99,191 @@ mod test {
                 #[cfg(debug_assertions)]
                 #[allow(non_snake_case)]
                 #[must_use]
-                pub fn mock__meow(count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
-                        mry::get_static_mocks(),
-                        std::any::Any::type_id(&_meow),
+                pub fn mock__meow(count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
+                        ::mry::get_static_mocks(),
+                        ::core::any::Any::type_id(&_meow),
                         "_meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn renamed_crate() {
+        let input: ItemFn = parse2(quote! {
+            fn meow(count: usize) -> String {
+                "meow".repeat(count)
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            krate: Some(parse_quote!(my_mry)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(input, &attr).to_string(),
+            quote! {
+                fn meow(count: usize) -> String {
+                    #[cfg(debug_assertions)]
+                    if let ::core::option::Option::Some(out) = my_mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&meow), "meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
+                        return out;
+                    }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = my_mry::__enter_real_impl("meow");
+                    {
+                        "meow".repeat(count)
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                #[must_use]
+                pub fn mock_meow(count: impl ::core::convert::Into<my_mry::ArgMatcher<usize>>) -> my_mry::MockLocator<(usize,), String, my_mry::Behavior1<(usize,), String> > {
+                    my_mry::MockLocator::new(
+                        my_mry::get_static_mocks(),
+                        ::core::any::Any::type_id(&meow),
+                        "meow",
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn custom_cfg() {
+        let input: ItemFn = parse2(quote! {
+            fn meow(count: usize) -> String {
+                "meow".repeat(count)
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            cfg: Some(parse_quote!(any(test, feature = "contract-tests"))),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(input, &attr).to_string(),
+            quote! {
+                fn meow(count: usize) -> String {
+                    #[cfg(all(debug_assertions, any(test, feature = "contract-tests")))]
+                    if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&meow), "meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
+                        return out;
+                    }
+                    #[cfg(all(debug_assertions, any(test, feature = "contract-tests")))]
+                    let _mry_real_impl = ::mry::__enter_real_impl("meow");
+                    {
+                        "meow".repeat(count)
+                    }
+                }
+
+                #[cfg(all(debug_assertions, any(test, feature = "contract-tests")))]
+                #[must_use]
+                pub fn mock_meow(count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
+                        ::mry::get_static_mocks(),
+                        ::core::any::Any::type_id(&meow),
+                        "meow",
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn allows_deprecated() {
+        let input: ItemFn = parse2(quote! {
+            #[deprecated]
+            fn meow(count: usize) -> String {
+                "meow".repeat(count)
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input, &MryAttr::default()).to_string(),
+            quote! {
+                #[deprecated]
+                fn meow(count: usize) -> String {
+                    #[cfg(debug_assertions)]
+                    if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&meow), "meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
+                        return out;
+                    }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("meow");
+                    {
+                        "meow".repeat(count)
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                #[allow(deprecated)]
+                #[must_use]
+                pub fn mock_meow(count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
+                        ::mry::get_static_mocks(),
+                        ::core::any::Any::type_id(&meow),
+                        "meow",
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn deprecated_mocks() {
+        let input: ItemFn = parse2(quote! {
+            #[deprecated(note = "use purr")]
+            fn meow() -> String {
+                "meow".into()
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            deprecated_mocks: darling::util::Flag::present(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(input, &attr).to_string(),
+            quote! {
+                #[deprecated(note = "use purr")]
+                fn meow() -> String {
+                    #[cfg(debug_assertions)]
+                    if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&meow), "meow", ()) {
+                        return out;
+                    }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("meow");
+                    {
+                        "meow".into()
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                #[deprecated(note = "use purr")]
+                #[must_use]
+                pub fn mock_meow() -> ::mry::MockLocator<(), String, ::mry::Behavior0<(), String> > {
+                    ::mry::MockLocator::new(
+                        ::mry::get_static_mocks(),
+                        ::core::any::Any::type_id(&meow),
+                        "meow",
+                        &[],
+                        ::core::convert::Into::into(()),
                     )
                 }
             }
@@ -1,9 +1,10 @@
-use crate::method;
+use crate::{method, MryAttr};
+use darling::util::Override;
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 use syn::visit::Visit;
 use syn::visit_mut::VisitMut;
-use syn::{parse2, FnArg, Ident, ImplItem, ItemImpl, Path};
+use syn::{parse2, Attribute, FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, Path};
 
 #[derive(Default)]
 struct TypeParameterVisitor(Vec<String>);
@@ -49,7 +50,23 @@ impl VisitMut for QualifiesAssociatedTypes {
     }
 }
 
-pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
+pub(crate) fn transform(mut input: ItemImpl, attr: &MryAttr) -> TokenStream {
+    let krate = attr.krate();
+    let cfg = attr.cfg();
+    // Whether each item is a method to mock, which are the ones with `#[mry::mock]` in the
+    // explicit mode
+    let instrumented: Vec<bool> = input
+        .items
+        .iter_mut()
+        .map(|item| match item {
+            ImplItem::Fn(method) => {
+                let marked = method.attrs.iter().any(|attr| is_mock(attr, &krate));
+                method.attrs.retain(|attr| !is_mock(attr, &krate));
+                marked || !attr.explicit.is_present()
+            }
+            _ => false,
+        })
+        .collect();
     if let Some((_, path, _)) = input.trait_.clone() {
         let ty = path.clone();
         let associated_types: Vec<_> = input
@@ -100,6 +117,10 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
         }
     }
 
+    // `TypeId` of the receiver lent to `returns_with_self` needs it to be `'static`
+    let lends_receiver = input.generics.params.is_empty()
+        && !struct_type.to_token_stream().to_string().contains('\'');
+
     let mut anonimized_struct = struct_type.clone();
     LifetimeAnonymizer.visit_type_mut(&mut anonimized_struct);
 
@@ -115,24 +136,33 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
         quote![<#anonimized_struct>]
     };
 
-    // Pretty print type name
-    let type_name = type_name
-        .replace(" ,", ",")
-        .replace(" >", ">")
-        .replace(" <", "<")
-        .replace("< ", "<");
+    let type_name = pretty_type_name(&type_name);
+
+    // Instances of a struct with `#[mry::mry(shared)]` share the mocks of the type on the thread
+    let (mocks, mry) = if attr.shared.is_present() {
+        let struct_name = pretty_type_name(&struct_type.to_token_stream().to_string());
+        (
+            quote![#krate::shared_mocks(#struct_name)],
+            quote![#krate::shared_mry(#struct_name)],
+        )
+    } else {
+        (quote![self.mry.mocks()], quote![self.mry])
+    };
 
     let (members, impl_members): (Vec<_>, Vec<_>) = input
         .items
         .iter()
-        .map(|item| {
-            if let ImplItem::Fn(method) = item {
+        .zip(&instrumented)
+        .map(|(item, instrumented)| match item {
+            ImplItem::Fn(method) if *instrumented => {
                 if let Some(FnArg::Receiver(_)) = method.sig.inputs.first() {
                     method::transform(
-                        quote![self.mry.mocks()],
+                        attr,
+                        mocks.clone(),
                         quote![#qualified_type::],
                         &(type_name.clone() + "::"),
-                        quote![self.mry.record_call_and_find_mock_output],
+                        quote![#mry.record_call_and_find_mock_output],
+                        lends_receiver,
                         Some(&method.vis),
                         &method.attrs,
                         &method.sig,
@@ -146,10 +176,12 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
                     )
                 } else {
                     method::transform(
-                        quote![mry::get_static_mocks()],
+                        attr,
+                        quote![#krate::get_static_mocks()],
                         quote![#qualified_type::],
                         &(type_name.clone() + "::"),
-                        quote![mry::static_record_call_and_find_mock_output],
+                        quote![#krate::static_record_call_and_find_mock_output],
+                        false,
                         Some(&method.vis),
                         &method.attrs,
                         &method.sig,
@@ -162,29 +194,115 @@ pub(crate) fn transform(mut input: ItemImpl) -> TokenStream {
                         ),
                     )
                 }
-            } else {
-                (item.to_token_stream(), TokenStream::default())
             }
+            _ => (item.to_token_stream(), TokenStream::default()),
         })
         .unzip();
 
+    let methods = attr.methods.as_ref().map(|methods| {
+        let metas = methods_to_mock(&input, &instrumented)
+            .map(|method| method::meta(&krate, &method.attrs, &method.sig));
+        let ident = match (methods, trait_name) {
+            (Override::Explicit(name), _) => format_ident!("{}", name),
+            // Trait impls have their own table not to conflict with the inherent impl
+            (Override::Inherit, Some(path)) => format_ident!(
+                "MRY_{}_METHODS",
+                upper_snake_case(&path.segments.last().unwrap().ident.to_string())
+            ),
+            (Override::Inherit, None) => format_ident!("MRY_METHODS"),
+        };
+        quote! {
+            #[cfg(#cfg)]
+            pub const #ident: &'static [#krate::MethodMeta] = &[#(#metas),*];
+        }
+    });
+
+    let skipped_markers = methods_to_mock(&input, &instrumented).map(|method| {
+        method::skipped_marker(
+            attr,
+            &(type_name.clone() + "::"),
+            &method.attrs,
+            &method.sig,
+        )
+    });
+    let callback_markers = methods_to_mock(&input, &instrumented).map(|method| {
+        method::callback_marker(
+            attr,
+            &(type_name.clone() + "::"),
+            &method.attrs,
+            &method.sig,
+        )
+    });
+
     let impl_generics = if impl_generics.is_empty() {
         TokenStream::default()
     } else {
         quote!( <#(#impl_generics),*>)
     };
 
+    let attrs = &input.attrs;
+    let cfgs = method::cfg_attrs(&input.attrs);
+
     quote! {
+        #(#attrs)*
         impl #generics #trait_ #struct_type {
             #(#members)*
         }
 
+        #(#cfgs)*
         impl #impl_generics #struct_type {
             #(#impl_members)*
+            #methods
         }
+
+        #(#skipped_markers)*
+        #(#callback_markers)*
     }
 }
 
+pub(crate) fn upper_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, char) in name.char_indices() {
+        if index > 0 && char.is_uppercase() {
+            snake.push('_');
+        }
+        snake.push(char.to_ascii_uppercase());
+    }
+    snake
+}
+
+fn methods_to_mock<'a>(
+    input: &'a ItemImpl,
+    instrumented: &'a [bool],
+) -> impl Iterator<Item = &'a ImplItemFn> {
+    input
+        .items
+        .iter()
+        .zip(instrumented)
+        .filter_map(|(item, instrumented)| match item {
+            ImplItem::Fn(method) if *instrumented => Some(method),
+            _ => None,
+        })
+}
+
+/// `#[mry::mock]` on a method, which opts it in to mocks in the explicit mode
+fn is_mock(attr: &Attribute, krate: &Path) -> bool {
+    let path = attr.path();
+    let crate_name = &krate.segments.last().unwrap().ident;
+    path.segments.len() == 2
+        && (path.segments[0].ident == "mry" || path.segments[0].ident == *crate_name)
+        && path.segments[1].ident == "mock"
+}
+
+/// Type name printed without the spaces of the tokens around `<`, `>` and `,`
+fn pretty_type_name(type_name: &str) -> String {
+    type_name
+        .replace(" ,", ",")
+        .replace(" >", ">")
+        .replace(" <", "<")
+        .replace("< ", "<")
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -206,16 +324,18 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 impl Cat {
                     #[meow]
                     #[meow]
                     fn meow(#[a] &self, #[b] count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<Cat>::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
                             return out;
                         }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                         "meow".repeat(count)
                     }
                 }
@@ -223,12 +343,163 @@ mod test {
                 impl Cat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
+                            self.mry.mocks(),
+                            ::core::any::Any::type_id(&<Cat>::meow),
+                            "Cat::meow",
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
+                        )
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_associated_items() {
+        let input: ItemImpl = parse2(quote! {
+            impl Iterator for Cat {
+                type Item = usize;
+                const MAX: usize = 3;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    None
+                }
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input, &MryAttr::default()).to_string(),
+            quote! {
+                impl Iterator for Cat {
+                    type Item = usize;
+                    const MAX: usize = 3;
+
+                    fn next(&mut self) -> Option< <Self as Iterator>::Item> {
+                        #[cfg(debug_assertions)]
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, Option< <Self as Iterator>::Item> >(::core::any::Any::type_id(&<Cat as Iterator>::next), "<Cat as Iterator>::next", ())) {
+                            return out;
+                        }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("<Cat as Iterator>::next");
+                        None
+                    }
+                }
+
+                impl Cat {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_next(&mut self,) -> ::mry::MockLocator<(), Option< <Self as Iterator>::Item>, ::mry::Behavior0<(), Option< <Self as Iterator>::Item> > > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<Cat>::meow),
+                            ::core::any::Any::type_id(&<Cat as Iterator>::next),
+                            "<Cat as Iterator>::next",
+                            &[],
+                            ::core::convert::Into::into(()),
+                        )
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_cfg() {
+        let input: ItemImpl = parse2(quote! {
+            #[cfg(unix)]
+            #[allow(dead_code)]
+            impl Cat {
+                #[cfg(target_os = "linux")]
+                fn meow(&self) -> String {
+                    "meow".into()
+                }
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input, &MryAttr::default()).to_string(),
+            quote! {
+                #[cfg(unix)]
+                #[allow(dead_code)]
+                impl Cat {
+                    #[cfg(target_os = "linux")]
+                    fn meow(&self) -> String {
+                        #[cfg(debug_assertions)]
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<Cat>::meow), "Cat::meow", ())) {
+                            return out;
+                        }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
+                        "meow".into()
+                    }
+                }
+
+                #[cfg(unix)]
+                impl Cat {
+                    #[cfg(debug_assertions)]
+                    #[cfg(target_os = "linux")]
+                    #[must_use]
+                    pub fn mock_meow(&mut self,) -> ::mry::MockLocator<(), String, ::mry::Behavior0<(), String> > {
+                        ::mry::MockLocator::new(
+                            self.mry.mocks(),
+                            ::core::any::Any::type_id(&<Cat>::meow),
                             "Cat::meow",
-                            (count.into(),).into(),
+                            &[],
+                            ::core::convert::Into::into(()),
+                        )
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn suffix() {
+        let input: ItemImpl = parse2(quote! {
+            impl From<Raw> for Domain {
+                fn from(raw: Raw) -> Self {
+                    Domain(raw.0)
+                }
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            suffix: Some("raw".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(input, &attr).to_string(),
+            quote! {
+                impl From<Raw> for Domain {
+                    fn from(raw: Raw) -> Self {
+                        #[cfg(debug_assertions)]
+                        if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, Self>(::core::any::Any::type_id(&<Domain as From<Raw> >::from), "<Domain as From<Raw>>::from", (<Raw as ::core::clone::Clone>::clone(&raw),)) {
+                            return out;
+                        }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("<Domain as From<Raw>>::from");
+                        Domain(raw.0)
+                    }
+                }
+
+                impl Domain {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_from_raw(raw: impl ::core::convert::Into<::mry::ArgMatcher<Raw>>) -> ::mry::MockLocator<(Raw,), Self, ::mry::Behavior1<(Raw,), Self> > {
+                        ::mry::MockLocator::new(
+                            ::mry::get_static_mocks(),
+                            ::core::any::Any::type_id(&<Domain as From<Raw> >::from),
+                            "<Domain as From<Raw>>::from",
+                            &["raw"],
+                            ::core::convert::Into::into((::core::convert::Into::into(raw),)),
                         )
                     }
                 }
@@ -249,14 +520,16 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 impl<'a, A: Clone> Cat<'a, A> {
                     fn meow<'a, B>(&'a self, count: usize) -> B {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, B>(std::any::Any::type_id(&<Cat<'_, A> >::meow), "Cat<'a, A>::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, B>(::core::any::Any::type_id(&<Cat<'_, A> >::meow), "Cat<'a, A>::meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                             return out;
                         }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat<'a, A>::meow");
                         "meow".repeat(count)
                     }
                 }
@@ -264,12 +537,13 @@ mod test {
                 impl <'a, A: Clone> Cat<'a, A> {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), B, mry::Behavior1<(usize,), B> > {
-                        mry::MockLocator::new(
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), B, ::mry::Behavior1<(usize,), B> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<Cat<'_, A> >::meow),
+                            ::core::any::Any::type_id(&<Cat<'_, A> >::meow),
                             "Cat<'a, A>::meow",
-                            (count.into(),).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
                 }
@@ -290,14 +564,16 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 impl<A: Clone> Animal<A> for Cat {
                     fn name(&self) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<Cat as Animal<A> >::name), "<Cat as Animal<A>>::name", ()) {
+                        if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<Cat as Animal<A> >::name), "<Cat as Animal<A>>::name", ()) {
                             return out;
                         }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("<Cat as Animal<A>>::name");
                         self.name
                     }
                 }
@@ -305,12 +581,13 @@ mod test {
                 impl Cat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_name(&mut self,) -> mry::MockLocator<(), String, mry::Behavior0<(), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock_name(&mut self,) -> ::mry::MockLocator<(), String, ::mry::Behavior0<(), String> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&< Cat as Animal < A > >::name),
+                            ::core::any::Any::type_id(&< Cat as Animal < A > >::name),
                             "<Cat as Animal<A>>::name",
-                            ().into(),
+                            &[],
+                            ::core::convert::Into::into(()),
                         )
                     }
                 }
@@ -332,15 +609,17 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 impl Iterator for Cat {
                     type Item = String;
                     fn next(&self) -> Option< <Self as Iterator>::Item> {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, Option< <Self as Iterator>::Item> >(std::any::Any::type_id(&<Cat as Iterator>::next), "<Cat as Iterator>::next", ()) {
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, Option< <Self as Iterator>::Item> >(::core::any::Any::type_id(&<Cat as Iterator>::next), "<Cat as Iterator>::next", ())) {
                             return out;
                         }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("<Cat as Iterator>::next");
                         Some(self.name)
                     }
                 }
@@ -348,12 +627,13 @@ mod test {
                 impl Cat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_next(&mut self,) -> mry::MockLocator<(), Option< <Self as Iterator>::Item >, mry::Behavior0<(), Option< <Self as Iterator>::Item> > > {
-                        mry::MockLocator::new(
+                    pub fn mock_next(&mut self,) -> ::mry::MockLocator<(), Option< <Self as Iterator>::Item >, ::mry::Behavior0<(), Option< <Self as Iterator>::Item> > > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<Cat as Iterator>::next),
+                            ::core::any::Any::type_id(&<Cat as Iterator>::next),
                             "<Cat as Iterator>::next",
-                            ().into(),
+                            &[],
+                            ::core::convert::Into::into(()),
                         )
                     }
                 }
@@ -374,14 +654,16 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 impl Cat {
                     fn meow(count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = mry::static_record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<Cat>::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                             return out;
                         }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                         "meow".repeat(count)
                     }
                 }
@@ -389,12 +671,13 @@ mod test {
                 impl Cat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
-                            mry::get_static_mocks(),
-                            std::any::Any::type_id(&<Cat>::meow),
+                    pub fn mock_meow(count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
+                            ::mry::get_static_mocks(),
+                            ::core::any::Any::type_id(&<Cat>::meow),
                             "Cat::meow",
-                            (count.into(), ).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
                 }
@@ -402,4 +685,227 @@ mod test {
             .to_string()
         );
     }
+
+    #[test]
+    fn methods_table() {
+        let input: ItemImpl = parse2(quote! {
+            impl PartialEq for Cat {
+                fn eq(&self, other: &Cat) -> bool {
+                    true
+                }
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            methods: Some(Override::Inherit),
+            ..Default::default()
+        };
+
+        assert!(transform(input, &attr).to_string().contains(
+            &quote! {
+                #[cfg(debug_assertions)]
+                pub const MRY_PARTIAL_EQ_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                    name: "eq",
+                    arity: 1usize,
+                    arg_types: &["&Cat"],
+                    return_type: "bool",
+                }];
+            }
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn methods_table_with_name() {
+        let input: ItemImpl = parse2(quote! {
+            impl Cat {
+                fn meow(&self) {}
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            methods: Some(Override::Explicit("MRY_MEOW_METHODS".to_string())),
+            ..Default::default()
+        };
+
+        assert!(transform(input, &attr)
+            .to_string()
+            .contains("pub const MRY_MEOW_METHODS"));
+    }
+
+    #[test]
+    fn no_methods_table_by_default() {
+        let input: ItemImpl = parse2(quote! {
+            impl Cat {
+                fn meow(&self) {}
+            }
+        })
+        .unwrap();
+
+        assert!(!transform(input, &MryAttr::default())
+            .to_string()
+            .contains("MethodMeta"));
+    }
+
+    #[test]
+    fn explicit() {
+        let input: ItemImpl = parse2(quote! {
+            impl Cat {
+                #[mry::mock]
+                fn meow(&self) -> String {
+                    "meow".into()
+                }
+
+                fn purr(&self) -> String {
+                    "purr".into()
+                }
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            explicit: darling::util::Flag::present(),
+            ..Default::default()
+        };
+        let output = transform(input, &attr).to_string();
+
+        assert!(output.contains("pub fn mock_meow"));
+        assert!(!output.contains("mock_purr"));
+        assert!(!output.contains("mry :: mock"));
+        assert!(output.contains(
+            &quote! {
+                fn purr(&self) -> String {
+                    "purr".into()
+                }
+            }
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn shared() {
+        let input: ItemImpl = parse2(quote! {
+            impl Cat<A> {
+                fn meow(&self) -> String {
+                    "meow".into()
+                }
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            shared: darling::util::Flag::present(),
+            ..Default::default()
+        };
+        let output = transform(input, &attr).to_string();
+
+        assert!(output.contains(
+            &quote![::mry::shared_mry("Cat<A>").record_call_and_find_mock_output].to_string()
+        ));
+        assert!(output.contains(&quote![::mry::shared_mocks("Cat<A>")].to_string()));
+        assert!(!output.contains("self . mry"));
+    }
+
+    #[test]
+    fn skip() {
+        let input: ItemImpl = parse2(quote! {
+            impl Cat {
+                #[mry(skip)]
+                fn meow(&self) -> String {
+                    "meow".into()
+                }
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input, &MryAttr::default()).to_string(),
+            quote! {
+                impl Cat {
+                    fn meow(&self) -> String {
+                        "meow".into()
+                    }
+                }
+
+                impl Cat {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_meow(&mut self, ) -> ::mry::MockLocator<(), String, ::mry::Behavior0<(), String> >
+                    where for<'mry> __mry_skipped_Cat_meow::meow: ::mry::MockableMethod
+                    {
+                        ::mry::MockLocator::new(
+                            self.mry.mocks(),
+                            ::core::any::Any::type_id(&<Cat>::meow),
+                            "Cat::meow",
+                            &[],
+                            ::core::convert::Into::into(()),
+                        )
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                #[doc(hidden)]
+                #[allow(non_snake_case, non_camel_case_types)]
+                pub mod __mry_skipped_Cat_meow {
+                    pub struct meow {}
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn callback() {
+        let input: ItemImpl = parse2(quote! {
+            impl Store {
+                fn update(&self, f: impl FnOnce(&mut Config)) {
+                    f(&mut self.config())
+                }
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input, &MryAttr::default()).to_string(),
+            quote! {
+                impl Store {
+                    #[allow(unused_mut)]
+                    fn update(&self, f: impl FnOnce(&mut Config)) {
+                        #[cfg(debug_assertions)]
+                        let mut f = ::core::option::Option::Some(f);
+                        #[cfg(debug_assertions)]
+                        if let ::core::option::Option::Some(out) = ::mry::Callback::lend(&mut |__mry_value: &mut Config| ::mry::Callback::once(&mut f, __mry_value), |f| ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, ()>(::core::any::TypeId::of::<__mry_callback_Store_update::update>(), "Store::update", (f,)))) {
+                            return out;
+                        }
+                        #[cfg(debug_assertions)]
+                        #[allow(unused_variables)]
+                        let f = ::mry::returned_callback(f, "Store::update");
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Store::update");
+                        f(&mut self.config())
+                    }
+                }
+
+                impl Store {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_update(&mut self, f: impl ::core::convert::Into<::mry::ArgMatcher<::mry::Callback<Config> >>) -> ::mry::MockLocator<(::mry::Callback<Config>,), (), ::mry::Behavior1<(::mry::Callback<Config>,), ()> > {
+                        ::mry::MockLocator::new(
+                            self.mry.mocks(),
+                            ::core::any::TypeId::of::<__mry_callback_Store_update::update>(),
+                            "Store::update",
+                            &["f"],
+                            ::core::convert::Into::into((::core::convert::Into::into(f),)),
+                        )
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                #[doc(hidden)]
+                #[allow(non_snake_case, non_camel_case_types)]
+                pub mod __mry_callback_Store_update {
+                    pub struct update {}
+                }
+            }
+            .to_string()
+        );
+    }
 }
@@ -4,7 +4,14 @@ use quote::quote;
 use quote::ToTokens;
 use syn::ItemStruct;
 
-pub(crate) fn transform(input: ItemStruct) -> TokenStream {
+use crate::MryAttr;
+
+pub(crate) fn transform(input: ItemStruct, attr: &MryAttr) -> TokenStream {
+    // The mocks of the struct are shared on the thread instead of kept in a field
+    if attr.shared.is_present() {
+        return input.into_token_stream();
+    }
+    let krate = attr.krate();
     let vis = &input.vis;
     let struct_name = &input.ident;
 
@@ -63,7 +70,7 @@ pub(crate) fn transform(input: ItemStruct) -> TokenStream {
         #vis struct #struct_name #generics {
             #(#struct_fields),*#comma_for_fields
             #serde_skip_or_blank
-            pub mry: mry::Mry,
+            pub mry: #krate::Mry,
         }
     }
 }
@@ -85,11 +92,11 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 struct Cat {
                     name: String,
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
             }
             .to_string()
@@ -108,13 +115,39 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 #[derive(Clone, Default)]
                 struct Cat {
                     #[name]
                     name: String,
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn shared_keeps_fields() {
+        let input: ItemStruct = parse2(quote! {
+            #[derive(Clone)]
+            struct Cat {
+                name: String,
+            }
+        })
+        .unwrap();
+        let attr = MryAttr {
+            shared: darling::util::Flag::present(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(input, &attr).to_string(),
+            quote! {
+                #[derive(Clone)]
+                struct Cat {
+                    name: String,
                 }
             }
             .to_string()
@@ -131,11 +164,11 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 pub struct Cat {
                     pub name: String,
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
             }
             .to_string()
@@ -152,11 +185,11 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 pub struct Cat<'a, A> {
                     pub name: &'a A,
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
             }
             .to_string()
@@ -172,10 +205,10 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 struct Cat {
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
             }
             .to_string()
@@ -193,13 +226,13 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 #[derive(Debug, Clone, PartialEq, Serialize)]
                 struct Cat {
                     pub name: String,
                     #[serde(skip)]
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
             }
             .to_string()
@@ -217,13 +250,13 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 #[derive(Debug, Clone, PartialEq, serde::Deserialize)]
                 struct Cat {
                     pub name: String,
                     #[serde(skip)]
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
             }
             .to_string()
@@ -1,17 +1,19 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{Ident, ItemTrait};
+use syn::{parse2, parse_quote, FnArg, Ident, ImplItemFn, ItemTrait};
 
-use crate::method;
+use crate::{method, MryAttr};
 
-pub(crate) fn transform(input: ItemTrait) -> TokenStream {
+pub(crate) fn transform(input: ItemTrait, attr: &MryAttr) -> TokenStream {
+    let krate = attr.krate();
+    let cfg = attr.cfg();
     let async_trait_or_blank = if input.attrs.iter().any(|attr| {
         attr.path()
             .segments
             .iter()
             .any(|segment| segment.ident == "async_trait")
     }) {
-        quote!(#[async_trait::async_trait])
+        quote!(#[::async_trait::async_trait])
     } else {
         TokenStream::default()
     };
@@ -21,28 +23,130 @@ pub(crate) fn transform(input: ItemTrait) -> TokenStream {
     let mry_ident = Ident::new(&format!("Mock{}", &input.ident), Span::call_site());
     let vis = &input.vis;
     let panic_message = format!("mock not found for {}", trait_ident);
-    let (items, impl_items): (Vec<_>, Vec<_>) = input
+    let (mut items, impl_items): (Vec<_>, Vec<_>) = input
         .items
         .iter()
         .map(|item| match item {
-            syn::TraitItem::Fn(method) => method::transform(
-                quote![self.mry.mocks()],
-                quote![<#mry_ident as #trait_ident>::],
-                &(trait_ident.to_string() + "::"),
-                quote![self.mry.record_call_and_find_mock_output],
-                None,
-                &method.attrs,
-                &method.sig,
-                &method
-                    .default
-                    .as_ref()
-                    .map(|default| default.to_token_stream())
-                    .unwrap_or(quote![panic!(#panic_message)]),
-            ),
+            syn::TraitItem::Fn(method) => {
+                let (mocks, record_call_and_find_mock_output) =
+                    if let Some(FnArg::Receiver(_)) = method.sig.inputs.first() {
+                        (
+                            quote![self.mry.mocks()],
+                            quote![self.mry.record_call_and_find_mock_output],
+                        )
+                    } else {
+                        (
+                            quote![#krate::get_static_mocks()],
+                            quote![#krate::static_record_call_and_find_mock_output],
+                        )
+                    };
+                method::transform(
+                    attr,
+                    mocks,
+                    quote![<#mry_ident as #trait_ident>::],
+                    &(trait_ident.to_string() + "::"),
+                    record_call_and_find_mock_output,
+                    true,
+                    None,
+                    &method.attrs,
+                    &method.sig,
+                    &method
+                        .default
+                        .as_ref()
+                        .map(|default| default.to_token_stream())
+                        .unwrap_or(quote![::core::panic!(#panic_message)]),
+                )
+            }
             _item => todo!(),
         })
         .unzip();
 
+    let stubbed_names = input.items.iter().filter_map(|item| match item {
+        syn::TraitItem::Fn(method)
+            if matches!(method.sig.inputs.first(), Some(FnArg::Receiver(_))) =>
+        {
+            Some(format!("{}::{}", trait_ident, method.sig.ident))
+        }
+        _ => None,
+    });
+
+    let metas = input.items.iter().filter_map(|item| match item {
+        syn::TraitItem::Fn(method) => Some(method::meta(&krate, &method.attrs, &method.sig)),
+        _ => None,
+    });
+
+    let callback_markers = input.items.iter().filter_map(|item| match item {
+        syn::TraitItem::Fn(method) => Some(method::callback_marker(
+            attr,
+            &(trait_ident.to_string() + "::"),
+            &method.attrs,
+            &method.sig,
+        )),
+        _ => None,
+    });
+
+    let cfgs = method::cfg_attrs(&input.attrs).collect::<Vec<_>>();
+    let mut input = input.clone();
+    for item in input.items.iter_mut() {
+        if let syn::TraitItem::Fn(method) = item {
+            method::strip_param_attrs(&mut method.sig);
+        }
+    }
+
+    let dyn_mocks = if attr.dyn_mocks.is_present() {
+        // Trait objects reach their mock through this hidden method, as they cannot be downcast
+        input.items.push(parse_quote! {
+            #[cfg(#cfg)]
+            #[doc(hidden)]
+            fn __mry_mock(&mut self) -> ::core::option::Option<&mut #mry_ident> {
+                ::core::option::Option::None
+            }
+        });
+        items.push(quote! {
+            fn __mry_mock(&mut self) -> ::core::option::Option<&mut #mry_ident> {
+                ::core::option::Option::Some(self)
+            }
+        });
+        let message = format!("the trait object of {} is not {}", trait_ident, mry_ident);
+        let forwarders: Vec<_> = impl_items
+            .iter()
+            .filter_map(|item| parse2::<ImplItemFn>(item.clone()).ok())
+            .filter(|mock| matches!(mock.sig.inputs.first(), Some(FnArg::Receiver(_))))
+            .map(|mut mock| {
+                let ident = &mock.sig.ident;
+                let args = mock.sig.inputs.iter().filter_map(|input| match input {
+                    FnArg::Typed(typed_arg) => Some(&typed_arg.pat),
+                    FnArg::Receiver(_) => None,
+                });
+                mock.block = parse_quote!({
+                    self.__mry_mock().expect(#message).#ident(#(#args),*)
+                });
+                mock
+            })
+            .collect();
+        quote! {
+            #[cfg(#cfg)]
+            #(#cfgs)*
+            impl dyn #trait_ident {
+                #(#forwarders)*
+            }
+
+            #[cfg(#cfg)]
+            #(#cfgs)*
+            impl dyn #trait_ident + ::core::marker::Send {
+                #(#forwarders)*
+            }
+
+            #[cfg(#cfg)]
+            #(#cfgs)*
+            impl dyn #trait_ident + ::core::marker::Send + ::core::marker::Sync {
+                #(#forwarders)*
+            }
+        }
+    } else {
+        TokenStream::default()
+    };
+
     quote! {
         #input
 
@@ -50,21 +154,37 @@ pub(crate) fn transform(input: ItemTrait) -> TokenStream {
         // trait is not supported yet in rustc. It is problem with using
         // `trait_variant::make` macro that desugars `async fn`.
         // See https://github.com/rust-lang/rust/issues/35121
-        #[cfg(debug_assertions)]
-        #[derive(Default, Clone, Debug)]
+        #[cfg(#cfg)]
+        #(#cfgs)*
+        #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
         #vis struct #mry_ident {
-            pub mry: mry::Mry,
+            pub mry: #krate::Mry,
         }
-        #[cfg(debug_assertions)]
+        #[cfg(#cfg)]
+        #(#cfgs)*
         #async_trait_or_blank
         impl #generics #trait_ident for #mry_ident {
             #(#items)*
         }
 
-        #[cfg(debug_assertions)]
+        #[cfg(#cfg)]
+        #(#cfgs)*
         impl #mry_ident {
             #(#impl_items)*
+
+            pub const MRY_METHODS: &'static [#krate::MethodMeta] = &[#(#metas),*];
+
+            /// Asserts every method of the trait has a rule, for strict tests not to reach the
+            /// default implementations or the panics of methods without rules.
+            /// Panics listing the methods without rules. Associated functions are not checked
+            pub fn assert_fully_stubbed(&self) {
+                self.mry.assert_stubbed(&[#(#stubbed_names),*]);
+            }
         }
+
+        #(#callback_markers)*
+
+        #dyn_mocks
     }
 }
 
@@ -85,26 +205,28 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 trait Cat {
                     fn meow(&self, count: usize) -> String;
                 }
 
                 #[cfg(debug_assertions)]
-                #[derive(Default, Clone, Debug)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
                 struct MockCat {
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
 
                 #[cfg(debug_assertions)]
                 impl Cat for MockCat {
                     fn meow(&self, count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
                             return out;
                         }
-                        panic!("mock not found for Cat")
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
+                        ::core::panic!("mock not found for Cat")
                     }
                 }
 
@@ -112,14 +234,29 @@ mod test {
                 impl MockCat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<MockCat as Cat>::meow),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::meow),
                             "Cat::meow",
-                            (count.into(),).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "meow",
+                            arity: 1usize,
+                            arg_types: &["usize"],
+                            return_type: "String",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&["Cat::meow"]);
+                    }
                 }
             }
             .to_string()
@@ -136,26 +273,28 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 pub trait Cat {
                     fn meow(&self, count: usize) -> String;
                 }
 
                 #[cfg(debug_assertions)]
-                #[derive(Default, Clone, Debug)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
                 pub struct MockCat {
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
 
                 #[cfg(debug_assertions)]
                 impl Cat for MockCat {
                     fn meow(&self, count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
                             return out;
                         }
-                        panic!("mock not found for Cat")
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
+                        ::core::panic!("mock not found for Cat")
                     }
                 }
 
@@ -163,14 +302,29 @@ mod test {
                 impl MockCat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<MockCat as Cat>::meow),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::meow),
                             "Cat::meow",
-                            (count.into(),).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "meow",
+                            arity: 1usize,
+                            arg_types: &["usize"],
+                            return_type: "String",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&["Cat::meow"]);
+                    }
                 }
             }
             .to_string()
@@ -188,7 +342,7 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 #[async_trait::async_trait]
                 trait Cat {
@@ -196,20 +350,23 @@ mod test {
                 }
 
                 #[cfg(debug_assertions)]
-                #[derive(Default, Clone, Debug)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
                 struct MockCat {
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
 
                 #[cfg(debug_assertions)]
-                #[async_trait::async_trait]
+                #[::async_trait::async_trait]
                 impl Cat for MockCat {
                     async fn meow(&self, count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = (match ::mry::__find_async_output::<_, String>(|| ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),)))) {
+                            ::core::result::Result::Ok(found) => found,
+                            ::core::result::Result::Err(future) => return future.await,
+                        }) {
                             return out;
                         }
-                        panic!("mock not found for Cat")
+                        ::core::panic!("mock not found for Cat")
                     }
                 }
 
@@ -217,14 +374,29 @@ mod test {
                 impl MockCat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<MockCat as Cat>::meow),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::meow),
                             "Cat::meow",
-                            (count.into(),).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "meow",
+                            arity: 1usize,
+                            arg_types: &["usize"],
+                            return_type: "String",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&["Cat::meow"]);
+                    }
                 }
             }
             .to_string()
@@ -241,26 +413,28 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 trait Cat {
                     fn _meow(&self, count: usize) -> String;
                 }
 
                 #[cfg(debug_assertions)]
-                #[derive(Default, Clone, Debug)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
                 struct MockCat {
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
 
                 #[cfg(debug_assertions)]
                 impl Cat for MockCat {
                     fn _meow(&self, count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<MockCat as Cat>::_meow), "Cat::_meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<MockCat as Cat>::_meow), "Cat::_meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
                             return out;
                         }
-                        panic!("mock not found for Cat")
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::_meow");
+                        ::core::panic!("mock not found for Cat")
                     }
                 }
 
@@ -269,14 +443,29 @@ mod test {
                     #[cfg(debug_assertions)]
                     #[allow(non_snake_case)]
                     #[must_use]
-                    pub fn mock__meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock__meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<MockCat as Cat>::_meow),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::_meow),
                             "Cat::_meow",
-                            (count.into(),).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "_meow",
+                            arity: 1usize,
+                            arg_types: &["usize"],
+                            return_type: "String",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&["Cat::_meow"]);
+                    }
                 }
             }
             .to_string()
@@ -293,26 +482,97 @@ mod test {
         .unwrap();
 
         assert_eq!(
-            transform(input).to_string(),
+            transform(input, &MryAttr::default()).to_string(),
             quote! {
                 trait Cat {
                     async fn meow(&self, count: usize) -> String;
                 }
 
                 #[cfg(debug_assertions)]
-                #[derive(Default, Clone, Debug)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
                 struct MockCat {
-                    pub mry : mry::Mry,
+                    pub mry : ::mry::Mry,
                 }
 
                 #[cfg(debug_assertions)]
                 impl Cat for MockCat {
                     async fn meow(&self, count: usize) -> String {
                         #[cfg(debug_assertions)]
-                        if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                        if let ::core::option::Option::Some(out) = (match ::mry::__find_async_output::<_, String>(|| ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),)))) {
+                            ::core::result::Result::Ok(found) => found,
+                            ::core::result::Result::Err(future) => return future.await,
+                        }) {
+                            return out;
+                        }
+                        ::core::panic!("mock not found for Cat")
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl MockCat {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
+                            self.mry.mocks(),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::meow),
+                            "Cat::meow",
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
+                        )
+                    }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "meow",
+                            arity: 1usize,
+                            arg_types: &["usize"],
+                            return_type: "String",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&["Cat::meow"]);
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn associated_function() {
+        let input: ItemTrait = parse2(quote! {
+            trait Cat {
+                fn create() -> Self;
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input, &MryAttr::default()).to_string(),
+            quote! {
+                trait Cat {
+                    fn create() -> Self;
+                }
+
+                #[cfg(debug_assertions)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
+                struct MockCat {
+                    pub mry : ::mry::Mry,
+                }
+
+                #[cfg(debug_assertions)]
+                impl Cat for MockCat {
+                    fn create() -> Self {
+                        #[cfg(debug_assertions)]
+                        if let ::core::option::Option::Some(out) = ::mry::static_record_call_and_find_mock_output::<_, Self>(::core::any::Any::type_id(&<MockCat as Cat>::create), "Cat::create", ()) {
                             return out;
                         }
-                        panic!("mock not found for Cat")
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::create");
+                        ::core::panic!("mock not found for Cat")
                     }
                 }
 
@@ -320,14 +580,141 @@ mod test {
                 impl MockCat {
                     #[cfg(debug_assertions)]
                     #[must_use]
-                    pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                        mry::MockLocator::new(
+                    pub fn mock_create() -> ::mry::MockLocator<(), Self, ::mry::Behavior0<(), Self> > {
+                        ::mry::MockLocator::new(
+                            ::mry::get_static_mocks(),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::create),
+                            "Cat::create",
+                            &[],
+                            ::core::convert::Into::into(()),
+                        )
+                    }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "create",
+                            arity: 0usize,
+                            arg_types: &[],
+                            return_type: "Self",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&[]);
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn dyn_mocks() {
+        let input: ItemTrait = parse2(quote! {
+            trait Cat {
+                fn meow(&self, count: usize) -> String;
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(
+                input,
+                &MryAttr {
+                    dyn_mocks: darling::util::Flag::present(),
+                    ..Default::default()
+                }
+            )
+            .to_string(),
+            quote! {
+                trait Cat {
+                    fn meow(&self, count: usize) -> String;
+
+                    #[cfg(debug_assertions)]
+                    #[doc(hidden)]
+                    fn __mry_mock(&mut self) -> ::core::option::Option<&mut MockCat> {
+                        ::core::option::Option::None
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                #[derive(::core::default::Default, ::core::clone::Clone, ::core::fmt::Debug)]
+                struct MockCat {
+                    pub mry : ::mry::Mry,
+                }
+
+                #[cfg(debug_assertions)]
+                impl Cat for MockCat {
+                    fn meow(&self, count: usize) -> String {
+                        #[cfg(debug_assertions)]
+                        if let ::core::option::Option::Some(out) = ::mry::with_receiver(&*self, || self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&<MockCat as Cat>::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
+                            return out;
+                        }
+                        #[cfg(debug_assertions)]
+                        let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
+                        ::core::panic!("mock not found for Cat")
+                    }
+
+                    fn __mry_mock(&mut self) -> ::core::option::Option<&mut MockCat> {
+                        ::core::option::Option::Some(self)
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl MockCat {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        ::mry::MockLocator::new(
                             self.mry.mocks(),
-                            std::any::Any::type_id(&<MockCat as Cat>::meow),
+                            ::core::any::Any::type_id(&<MockCat as Cat>::meow),
                             "Cat::meow",
-                            (count.into(),).into(),
+                            &["count"],
+                            ::core::convert::Into::into((::core::convert::Into::into(count),)),
                         )
                     }
+
+                    pub const MRY_METHODS: &'static [::mry::MethodMeta] = &[::mry::MethodMeta {
+                            name: "meow",
+                            arity: 1usize,
+                            arg_types: &["usize"],
+                            return_type: "String",
+                        }];
+
+                    /// Asserts every method of the trait has a rule, for strict tests not to reach the
+                    /// default implementations or the panics of methods without rules.
+                    /// Panics listing the methods without rules. Associated functions are not checked
+                    pub fn assert_fully_stubbed(&self) {
+                        self.mry.assert_stubbed(&["Cat::meow"]);
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl dyn Cat {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize> >) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        self.__mry_mock().expect("the trait object of Cat is not MockCat").mock_meow(count)
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl dyn Cat + ::core::marker::Send {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize> >) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        self.__mry_mock().expect("the trait object of Cat is not MockCat").mock_meow(count)
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl dyn Cat + ::core::marker::Send + ::core::marker::Sync {
+                    #[cfg(debug_assertions)]
+                    #[must_use]
+                    pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize> >) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                        self.__mry_mock().expect("the trait object of Cat is not MockCat").mock_meow(count)
+                    }
                 }
             }
             .to_string()
@@ -1,20 +1,30 @@
+mod after;
+mod cfg_select;
 mod create_behaviors;
 mod create_matchers;
+mod deny_mocks;
 mod item_fn;
 mod item_impl;
 mod item_struct;
 mod item_trait;
 mod lock;
 mod method;
+mod mry_match;
+mod mry_test;
 mod new;
+mod sequence;
+mod stub;
 use darling::ast::NestedMeta;
 use darling::FromMeta;
 use lock::LockPaths;
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::visit_mut::VisitMut;
 mod alphabets;
-use syn::{parse, parse2, parse_macro_input, ExprStruct, ItemFn, ItemImpl, ItemStruct, ItemTrait};
+use syn::{
+    parse, parse2, parse_macro_input, parse_quote, ExprStruct, ItemFn, ItemImpl, ItemStruct,
+    ItemTrait,
+};
 
 enum TargetItem {
     Struct(ItemStruct),
@@ -23,9 +33,54 @@ enum TargetItem {
     Fn(ItemFn),
 }
 
-#[derive(FromMeta)]
+#[derive(FromMeta, Default)]
 struct MryAttr {
     debug: darling::util::Flag,
+    /// Emits the `MRY_METHODS` table for impl blocks, optionally with another name
+    methods: Option<darling::util::Override<String>>,
+    /// Path to the mry crate in case it is renamed or re-exported
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+    /// Suffix of the `mock_*` functions, to tell apart impls of the same trait such as `From<T>`
+    suffix: Option<String>,
+    /// Records raw pointers, `NonNull` and `MaybeUninit` arguments by address
+    unsafe_args: darling::util::Flag,
+    /// Extra cfg predicate of the generated mocks in addition to `debug_assertions`
+    cfg: Option<syn::Expr>,
+    /// Copies `#[deprecated]` of methods onto their `mock_*` functions instead of allowing it there
+    deprecated_mocks: darling::util::Flag,
+    /// Generates the `mock_*` functions of a trait mock on its trait objects, such as
+    /// `<dyn Cat>::mock_meow`
+    dyn_mocks: darling::util::Flag,
+    /// Mocks only the methods with `#[mry::mock]` in an impl block, keeping the others real
+    explicit: darling::util::Flag,
+    /// Shares the mocks of a struct among its instances on the thread instead of adding the `mry`
+    /// field, so struct literals compile unchanged
+    shared: darling::util::Flag,
+    /// Attribute macros on the item to expand before mocking, such as ones generating methods
+    #[darling(default)]
+    after: darling::util::PathList,
+    /// Whether the `bench` feature is enabled, set by the entry point rather than parsed
+    #[darling(skip)]
+    bench: bool,
+}
+
+impl MryAttr {
+    fn krate(&self) -> syn::Path {
+        self.krate.clone().unwrap_or_else(|| parse_quote!(::mry))
+    }
+
+    /// Predicate of `#[cfg(...)]` on the generated mocks.
+    /// Mocks are gated by `debug_assertions` because the mry runtime mocks nothing without it,
+    /// unless the `bench` feature makes the runtime mock in release builds too.
+    fn cfg(&self) -> TokenStream {
+        match (&self.cfg, self.bench) {
+            (Some(cfg), false) => quote!(all(debug_assertions, #cfg)),
+            (None, false) => quote!(debug_assertions),
+            (Some(cfg), true) => quote!(#cfg),
+            (None, true) => quote!(all()),
+        }
+    }
 }
 
 #[proc_macro_attribute]
@@ -33,7 +88,17 @@ pub fn mry(
     attr: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let attr = MryAttr::from_list(&NestedMeta::parse_meta_list(attr.into()).unwrap()).unwrap();
+    let args: TokenStream = attr.into();
+    let mut attr = MryAttr::from_list(&NestedMeta::parse_meta_list(args.clone()).unwrap()).unwrap();
+    attr.bench = cfg!(feature = "bench");
+    if !attr.after.is_empty() {
+        if let Some(deferred) = parse(input.clone())
+            .ok()
+            .and_then(|item| after::defer(&attr.krate(), &args, &attr.after, item))
+        {
+            return deferred.into();
+        }
+    }
     match parse(input.clone())
         .map(TargetItem::Struct)
         .or_else(|_| parse(input.clone()).map(TargetItem::Impl))
@@ -42,10 +107,10 @@ pub fn mry(
     {
         Ok(target) => {
             let token_stream = match target {
-                TargetItem::Struct(target) => item_struct::transform(target),
-                TargetItem::Impl(target) => item_impl::transform(target),
-                TargetItem::Trait(target) => item_trait::transform(target),
-                TargetItem::Fn(target) => item_fn::transform(target),
+                TargetItem::Struct(target) => item_struct::transform(target, &attr),
+                TargetItem::Impl(target) => item_impl::transform(target, &attr),
+                TargetItem::Trait(target) => item_trait::transform(target, &attr),
+                TargetItem::Fn(target) => item_fn::transform(target, &attr),
             };
             if attr.debug.is_present() {
                 println!("{}", token_stream);
@@ -61,6 +126,28 @@ pub fn new(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     new::transform(parse_macro_input!(input as ExprStruct)).into()
 }
 
+#[proc_macro]
+pub fn sequence(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    sequence::transform(parse_macro_input!(input as sequence::SequenceSteps)).into()
+}
+
+#[proc_macro]
+pub fn stub(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    stub::transform(parse_macro_input!(input as stub::Stubs)).into()
+}
+
+#[proc_macro]
+pub fn cfg_select(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    cfg_select::transform(parse_macro_input!(input as cfg_select::CfgSelect)).into()
+}
+
+#[proc_macro]
+pub fn deny_mocks(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut attr = MryAttr::from_list(&NestedMeta::parse_meta_list(input.into()).unwrap()).unwrap();
+    attr.bench = cfg!(feature = "bench");
+    deny_mocks::transform(&attr).into()
+}
+
 #[proc_macro]
 pub fn create_behaviors(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
     create_behaviors::create().into()
@@ -83,17 +170,43 @@ pub fn lock(
     .into()
 }
 
+#[proc_macro_attribute]
+pub fn test(
+    attribute: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    mry_test::transform(
+        parse_macro_input!(attribute as mry_test::TestAttr),
+        parse_macro_input!(input as ItemFn),
+    )
+    .into()
+}
+
+#[proc_macro]
+pub fn locked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    lock::locked(parse_macro_input!(input as lock::Locked)).into()
+}
+
+#[proc_macro_derive(MryMatch, attributes(mry))]
+pub fn mry_match(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    mry_match::derive(
+        parse_macro_input!(input as syn::DeriveInput),
+        cfg!(feature = "bench"),
+    )
+    .into()
+}
+
 struct M(TokenStream);
 
 impl VisitMut for M {
     fn visit_item_trait_mut(&mut self, i: &mut ItemTrait) {
-        item_trait::transform(i.clone()).to_tokens(&mut self.0)
+        item_trait::transform(i.clone(), &MryAttr::default()).to_tokens(&mut self.0)
     }
     fn visit_item_struct_mut(&mut self, i: &mut ItemStruct) {
-        item_struct::transform(i.clone()).to_tokens(&mut self.0)
+        item_struct::transform(i.clone(), &MryAttr::default()).to_tokens(&mut self.0)
     }
     fn visit_item_impl_mut(&mut self, i: &mut ItemImpl) {
-        item_impl::transform(i.clone()).to_tokens(&mut self.0)
+        item_impl::transform(i.clone(), &MryAttr::default()).to_tokens(&mut self.0)
     }
 }
 
@@ -103,3 +216,58 @@ pub fn m(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     m.visit_file_mut(&mut parse2(input.into()).unwrap());
     m.0.into()
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use quote::quote;
+    use syn::parse_quote;
+
+    use super::MryAttr;
+
+    #[test]
+    fn cfg_debug_assertions_by_default() {
+        assert_eq!(
+            MryAttr::default().cfg().to_string(),
+            quote!(debug_assertions).to_string()
+        );
+    }
+
+    #[test]
+    fn cfg_all_with_bench() {
+        let attr = MryAttr {
+            bench: true,
+            ..Default::default()
+        };
+
+        assert_eq!(attr.cfg().to_string(), quote!(all()).to_string());
+    }
+
+    #[test]
+    fn cfg_override_with_debug_assertions() {
+        let attr = MryAttr {
+            cfg: Some(parse_quote!(feature = "mocks")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            attr.cfg().to_string(),
+            quote!(all(debug_assertions, feature = "mocks")).to_string()
+        );
+    }
+
+    #[test]
+    fn cfg_override_with_bench() {
+        let attr = MryAttr {
+            cfg: Some(parse_quote!(feature = "mocks")),
+            bench: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            attr.cfg().to_string(),
+            quote!(feature = "mocks").to_string()
+        );
+    }
+}
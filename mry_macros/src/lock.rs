@@ -1,21 +1,65 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse_quote, ItemFn};
+use syn::{parse_quote, ItemFn, Signature};
 
-pub struct LockPaths(Vec<syn::Type>);
+pub struct LockPaths {
+    krate: Option<syn::Path>,
+    paths: Vec<syn::Type>,
+}
+
+/// Parses the leading `crate = "path",` of `lock` and `sequence!` if any
+pub(crate) fn parse_crate(input: syn::parse::ParseStream) -> syn::Result<Option<syn::Path>> {
+    if !(input.peek(syn::Token![crate]) && input.peek2(syn::Token![=])) {
+        return Ok(None);
+    }
+    input.parse::<syn::Token![crate]>()?;
+    input.parse::<syn::Token![=]>()?;
+    let krate = input.parse::<syn::LitStr>()?.parse()?;
+    if !input.is_empty() {
+        input.parse::<syn::Token![,]>()?;
+    }
+    Ok(Some(krate))
+}
+
+/// Input of `mry::locked!(hello, Cat::meow => expr)`
+pub struct Locked {
+    paths: LockPaths,
+    body: syn::Expr,
+}
+
+impl syn::parse::Parse for Locked {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = parse_crate(input)?;
+        let mut paths = Vec::new();
+        while !input.peek(syn::Token![=>]) {
+            paths.push(input.parse()?);
+            if !input.peek(syn::Token![=>]) {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        input.parse::<syn::Token![=>]>()?;
+        Ok(Self {
+            paths: LockPaths { krate, paths },
+            body: input.parse()?,
+        })
+    }
+}
 
 impl syn::parse::Parse for LockPaths {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Ok(Self(
-            input
+        Ok(Self {
+            krate: parse_crate(input)?,
+            paths: input
                 .parse_terminated(syn::Type::parse, syn::Token![,])?
                 .into_iter()
                 .collect(),
-        ))
+        })
     }
 }
 
 pub(crate) fn transform(args: LockPaths, mut input: ItemFn) -> TokenStream {
+    let krate = args.krate.clone().unwrap_or_else(|| parse_quote!(::mry));
+    let crate_name = &krate.segments.last().unwrap().ident;
     if let Some((attr, paths)) = input
         .attrs
         .iter_mut()
@@ -25,7 +69,7 @@ pub(crate) fn transform(args: LockPaths, mut input: ItemFn) -> TokenStream {
                 return true;
             }
             if path.segments.len() == 2
-                && path.segments[0].ident == "mry"
+                && (path.segments[0].ident == "mry" || path.segments[0].ident == *crate_name)
                 && path.segments[1].ident == "lock"
             {
                 return true;
@@ -38,32 +82,33 @@ pub(crate) fn transform(args: LockPaths, mut input: ItemFn) -> TokenStream {
                 .map(|paths| (attr, paths))
         })
     {
-        let paths = args.0.iter().chain(paths.0.iter());
-        *attr = parse_quote!(#[mry::lock(#(#paths),*)]);
+        let krate = args.krate.or(paths.krate);
+        let paths = args.paths.iter().chain(paths.paths.iter());
+        *attr = match krate {
+            Some(krate) => {
+                let name = krate.to_token_stream().to_string().replace(' ', "");
+                parse_quote!(#[#krate::lock(crate = #name, #(#paths),*)])
+            }
+            None => parse_quote!(#[::mry::lock(#(#paths),*)]),
+        };
         return input.into_token_stream();
     }
-    let args = args.0.into_iter().map(|arg| {
-        let name = arg
-            .to_token_stream()
-            .to_string()
-            .replace(" :: ", "::")
-            .replace("< ", "<")
-            .replace(" >", ">");
-        quote![(std::any::Any::type_id(&#arg), #name.to_string())]
-    });
+    if let Some(error) = async_feature_error(&input.sig, cfg!(feature = "async")) {
+        return quote![#error #input];
+    }
     let block = input.block.clone();
     input.block.stmts.clear();
-    let mutexes = quote![mry::__mutexes(vec![#(#args,)*])];
+    let mutexes = mutexes(&krate, args.paths);
     input.block.stmts.insert(
         0,
         syn::Stmt::Expr(
             if input.sig.asyncness.is_some() {
                 parse_quote! {
-                    mry::__async_lock_and_run(#mutexes, move || Box::pin(async #block)).await
+                    #krate::__async_lock_and_run(#mutexes, move || ::std::boxed::Box::pin(async #block)).await
                 }
             } else {
                 parse_quote! {
-                    mry::__lock_and_run(#mutexes, move || #block)
+                    #krate::__lock_and_run(#mutexes, move || #block)
                 }
             },
             None,
@@ -72,6 +117,40 @@ pub(crate) fn transform(args: LockPaths, mut input: ItemFn) -> TokenStream {
     input.into_token_stream()
 }
 
+/// Locks the static mocks of the paths while evaluating the body, for places `#[mry::lock]` is not
+/// available such as doc tests.
+pub(crate) fn locked(input: Locked) -> TokenStream {
+    let krate = input.paths.krate.unwrap_or_else(|| parse_quote!(::mry));
+    let mutexes = mutexes(&krate, input.paths.paths);
+    let body = input.body;
+    quote![#krate::__lock_and_run(#mutexes, || #body)]
+}
+
+fn mutexes(krate: &syn::Path, paths: Vec<syn::Type>) -> TokenStream {
+    let args = paths.into_iter().map(|arg| {
+        let name = arg
+            .to_token_stream()
+            .to_string()
+            .replace(" :: ", "::")
+            .replace("< ", "<")
+            .replace(" >", ">");
+        quote![(::core::any::Any::type_id(&#arg), ::std::string::ToString::to_string(#name))]
+    });
+    quote![#krate::__mutexes(::std::vec![#(#args,)*])]
+}
+
+/// Error naming the `async` feature of mry, which `#[mry::lock]` on async functions needs
+fn async_feature_error(sig: &Signature, async_feature: bool) -> Option<TokenStream> {
+    let asyncness = sig.asyncness.filter(|_| !async_feature)?;
+    Some(
+        syn::Error::new_spanned(
+            asyncness,
+            "`#[mry::lock]` on async functions requires the `async` feature of mry. Enable it by `mry = { version = \"0.8\", features = [\"async\"] }`",
+        )
+        .to_compile_error(),
+    )
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -81,11 +160,14 @@ mod test {
 
     #[test]
     fn lock() {
-        let args = LockPaths(vec![
-            parse_str("<A as B>::a").unwrap(),
-            parse_str("a::a").unwrap(),
-            parse_str("b::b").unwrap(),
-        ]);
+        let args = LockPaths {
+            krate: None,
+            paths: vec![
+                parse_str("<A as B>::a").unwrap(),
+                parse_str("a::a").unwrap(),
+                parse_str("b::b").unwrap(),
+            ],
+        };
         let input: ItemFn = parse2(quote! {
             #[test]
             fn test_meow() {
@@ -99,10 +181,10 @@ mod test {
             quote! {
                 #[test]
                 fn test_meow() {
-                    mry::__lock_and_run(mry::__mutexes(vec![
-                        (std::any::Any::type_id(&<A as B> :: a), "<A as B>::a".to_string()),
-                        (std::any::Any::type_id(&a :: a), "a::a".to_string()),
-                        (std::any::Any::type_id(&b :: b), "b::b".to_string()),
+                    ::mry::__lock_and_run(::mry::__mutexes(::std::vec![
+                        (::core::any::Any::type_id(&<A as B> :: a), ::std::string::ToString::to_string("<A as B>::a")),
+                        (::core::any::Any::type_id(&a :: a), ::std::string::ToString::to_string("a::a")),
+                        (::core::any::Any::type_id(&b :: b), ::std::string::ToString::to_string("b::b")),
                     ]), move | | {
                         assert!(true);
                     })
@@ -114,7 +196,10 @@ mod test {
 
     #[test]
     fn concats_multiple_locks() {
-        let args = LockPaths(vec![parse_str("a::a").unwrap(), parse_str("b::b").unwrap()]);
+        let args = LockPaths {
+            krate: None,
+            paths: vec![parse_str("a::a").unwrap(), parse_str("b::b").unwrap()],
+        };
         let input: ItemFn = parse2(quote! {
             #[mry::lock(c::c)]
             #[test]
@@ -127,7 +212,7 @@ mod test {
         assert_eq!(
             transform(args, input).to_string(),
             quote! {
-                #[mry::lock(a::a, b::b, c::c)]
+                #[::mry::lock(a::a, b::b, c::c)]
                 #[test]
                 fn test_meow() {
                     assert!(true);
@@ -136,4 +221,103 @@ mod test {
             .to_string()
         );
     }
+
+    #[test]
+    fn renamed_crate() {
+        let args: LockPaths = parse2(quote!(crate = "my_mry", a::a)).unwrap();
+        let input: ItemFn = parse2(quote! {
+            #[test]
+            fn test_meow() {
+                assert!(true);
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(args, input).to_string(),
+            quote! {
+                #[test]
+                fn test_meow() {
+                    my_mry::__lock_and_run(my_mry::__mutexes(::std::vec![
+                        (::core::any::Any::type_id(&a :: a), ::std::string::ToString::to_string("a::a")),
+                    ]), move | | {
+                        assert!(true);
+                    })
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn concats_multiple_locks_of_renamed_crate() {
+        let args: LockPaths = parse2(quote!(crate = "my_mry", a::a)).unwrap();
+        let input: ItemFn = parse2(quote! {
+            #[my_mry::lock(b::b)]
+            #[test]
+            fn test_meow() {
+                assert!(true);
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(args, input).to_string(),
+            quote! {
+                #[my_mry::lock(crate = "my_mry", a::a, b::b)]
+                #[test]
+                fn test_meow() {
+                    assert!(true);
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn locked() {
+        let input: Locked = parse2(quote! {
+            hello, Cat::meow => {
+                assert!(true);
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            super::locked(input).to_string(),
+            quote! {
+                ::mry::__lock_and_run(::mry::__mutexes(::std::vec![
+                    (::core::any::Any::type_id(&hello), ::std::string::ToString::to_string("hello")),
+                    (::core::any::Any::type_id(&Cat :: meow), ::std::string::ToString::to_string("Cat::meow")),
+                ]), || {
+                    assert!(true);
+                })
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn async_requires_feature() {
+        let input: ItemFn = parse2(quote! {
+            async fn test_meow() {}
+        })
+        .unwrap();
+
+        assert!(async_feature_error(&input.sig, false)
+            .unwrap()
+            .to_string()
+            .contains("requires the `async` feature of mry"));
+        assert!(async_feature_error(&input.sig, true).is_none());
+    }
+
+    #[test]
+    fn sync_needs_no_feature() {
+        let input: ItemFn = parse2(quote! {
+            fn test_meow() {}
+        })
+        .unwrap();
+
+        assert!(async_feature_error(&input.sig, false).is_none());
+    }
 }
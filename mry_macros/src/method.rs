@@ -1,42 +1,73 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{format_ident, quote};
+use syn::visit::Visit;
 use syn::{
-    parse_quote, punctuated::Punctuated, Attribute, FnArg, Ident, Pat, PatIdent, ReturnType,
-    Signature, Type, Visibility,
+    parse_quote, punctuated::Punctuated, Attribute, Expr, ExprClosure, ExprLit, FnArg, Ident, Lit,
+    MetaNameValue, Pat, PatIdent, PatType, Path, ReturnType, Signature, Type, Visibility,
 };
 
+use crate::MryAttr;
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn transform(
+    attr: &MryAttr,
     mocks_tokens: TokenStream,  // `MOCKS.lock()`
     method_prefix: TokenStream, // `Self::`
     method_debug_prefix: &str,  // "Cat::"
     record_call_and_find_mock_output: TokenStream,
+    lends_receiver: bool, // whether the receiver is `'static` to be lent to `returns_with_self`
     vis: Option<&Visibility>,
     attrs: &[Attribute],
     sig: &Signature,
     body: &TokenStream,
 ) -> (TokenStream, TokenStream) {
+    let krate = attr.krate();
+    let cfg = attr.cfg();
+    let skipped = attrs.iter().any(is_skip);
+    let logs_moved_args = !attrs.iter().any(is_unlogged_move_args);
+    let moves_args = !logs_moved_args || attrs.iter().any(is_move_args);
+    let attrs: Vec<_> = attrs
+        .iter()
+        .filter(|attr| !is_skip(attr) && !is_move_args(attr) && !is_unlogged_move_args(attr))
+        .cloned()
+        .collect();
+    let mut original_sig = sig.clone();
+    strip_param_attrs(&mut original_sig);
     // Split into receiver and other inputs
     let mut receiver = None;
     let mut mock_receiver = None;
+    let mut lent_receiver = None;
     let mut inputs = sig.inputs.iter().peekable();
     // If receiver exists
     if let Some(FnArg::Receiver(rec)) = inputs.peek() {
         receiver = Some(FnArg::Receiver(rec.clone()));
         mock_receiver = Some(quote![&mut self,]);
+        if lends_receiver {
+            let by_value = rec.reference.is_none()
+                && (rec.colon_token.is_none()
+                    || matches!(&*rec.ty, Type::Path(path) if path.path.is_ident("Self")));
+            lent_receiver = Some(if by_value {
+                quote![&self]
+            } else {
+                quote![&*self]
+            });
+        }
         // Skip the receiver
         inputs.next();
     }
     let inputs_without_receiver: Vec<_> = inputs
         .map(|input| {
             if let FnArg::Typed(typed_arg) = input {
-                typed_arg.clone()
+                let mut typed_arg = typed_arg.clone();
+                typed_arg.attrs.retain(|attr| !attr.path().is_ident("mry"));
+                typed_arg
             } else {
                 panic!("multiple receiver?");
             }
         })
         .collect();
     let mut bindings = Vec::new();
+    let param_names = param_names(&inputs_without_receiver);
 
     let args_without_receiver: Vec<_> = inputs_without_receiver
         .iter()
@@ -46,7 +77,7 @@ pub(crate) fn transform(
                 input.clone()
             } else {
                 let pat = input.pat.clone();
-                let arg_name = Ident::new(&format!("arg{}", i), Span::call_site());
+                let arg_name = param_names[i].clone();
                 bindings.push((pat, arg_name.clone()));
                 let ident = Pat::Ident(PatIdent {
                     attrs: Default::default(),
@@ -74,7 +105,10 @@ pub(crate) fn transform(
         }
     };
     let ident = sig.ident.clone();
-    let mock_ident = Ident::new(&format!("mock_{}", ident), Span::call_site());
+    let mock_ident = match &attr.suffix {
+        Some(suffix) => format_ident!("mock_{}_{}", ident, suffix),
+        None => format_ident!("mock_{}", ident),
+    };
     let name = format!("{}{}", method_debug_prefix, ident);
     let bindings = bindings.iter().map(|(pat, arg)| quote![let #pat = #arg;]);
     let behavior_name = Ident::new(
@@ -86,67 +120,243 @@ pub(crate) fn transform(
         owned_ty: Option<Type>,
         to_owned: TokenStream,
         name: Ident,
+        /// Type matched by the pattern if it is not the recorded one, such as `T` of `Redacted<T>`,
+        /// with the conversion of the pattern
+        matched: Option<(Type, TokenStream)>,
+        /// Lender of the argument to the recorded value for the call, such as `Projected::lend`,
+        /// with the arguments it takes before the closure
+        lent: Option<(TokenStream, TokenStream)>,
+        /// Whether the argument is taken by value and recorded as it is, which
+        /// `#[mry(move_args)]` moves instead of cloning
+        movable: bool,
     }
     impl Arg {
         fn ty(&self) -> &Type {
             self.owned_ty.as_ref().unwrap_or(&self.org_ty)
         }
     }
-    let args: Vec<Arg> = inputs_without_receiver
+    let args: Vec<Arg> = sig
+        .inputs
         .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(typed_arg) => Some(typed_arg),
+            FnArg::Receiver(_) => None,
+        })
         .enumerate()
         .map(|(index, input)| {
             let org_ty = input.ty.as_ref().clone();
-            let name = if let Pat::Ident(ident) = &*input.pat {
-                ident.ident.clone()
+            let name = param_names[index].clone();
+            if let Some(projection) = log_with(&input.attrs) {
+                let (closure, ty) = match projection {
+                    Ok(projection) => projection,
+                    Err(err) => {
+                        return Arg {
+                            owned_ty: Some(parse_quote!(())),
+                            to_owned: err.to_compile_error(),
+                            org_ty,
+                            name,
+                            matched: None,
+                            lent: None,
+                            movable: false,
+                        }
+                    }
+                };
+                // Patterns match the whole argument, which unsized ones match as their owned type
+                let (full, value) = match &org_ty {
+                    Type::Reference(reference) => (reference.elem.as_ref().clone(), quote![#name]),
+                    _ => (org_ty.clone(), quote![&#name]),
+                };
+                let (matched_ty, matcher) = match &full {
+                    Type::Slice(_) => (
+                        parse_quote!(<#full as ::std::borrow::ToOwned>::Owned),
+                        quote![owned_matcher],
+                    ),
+                    _ if is_str(&org_ty) => (parse_quote!(::std::string::String), quote![owned_matcher]),
+                    _ => (full.clone(), quote![matcher]),
+                };
+                return Arg {
+                    owned_ty: Some(parse_quote!(#krate::Projected<#full, #ty>)),
+                    to_owned: quote![#name],
+                    org_ty,
+                    name: name.clone(),
+                    matched: Some((
+                        matched_ty,
+                        quote![#krate::Projected::<#full, #ty>::#matcher(::core::convert::Into::into(#name), #closure)],
+                    )),
+                    lent: Some((
+                        quote![#krate::Projected::lend],
+                        quote![#value, #closure],
+                    )),
+                    movable: false,
+                };
+            }
+            let derefs = input.attrs.iter().any(is_deref);
+            let (owned_ty, to_owned) = if derefs {
+                match deref_owned_type(&name, &org_ty) {
+                    Ok((owned_ty, to_owned)) => (Some(owned_ty), to_owned),
+                    Err(err) => (Some(parse_quote!(())), err.to_compile_error()),
+                }
             } else {
-                format_ident!("arg{}", index)
+                make_owned_type(attr, &name, &org_ty)
+            };
+            if input.attrs.iter().any(is_redact) {
+                let inner = owned_ty.unwrap_or_else(|| org_ty.clone());
+                return Arg {
+                    owned_ty: Some(parse_quote!(#krate::Redacted<#inner>)),
+                    to_owned: quote![#krate::Redacted(#to_owned)],
+                    org_ty,
+                    matched: Some((
+                        inner,
+                        quote![#krate::Redacted::matcher(::core::convert::Into::into(#name))],
+                    )),
+                    name,
+                    lent: None,
+                    movable: false,
+                };
+            }
+            // Borrowed arguments are matched in place and copied only into the log
+            if let Type::Reference(reference) = &org_ty {
+                if moves_args && !derefs {
+                    let target = reference.elem.as_ref().clone();
+                    let (matched_ty, matcher) = match &target {
+                        Type::Slice(_) => (
+                            parse_quote!(<#target as ::std::borrow::ToOwned>::Owned),
+                            quote![owned_matcher],
+                        ),
+                        _ if is_str(&org_ty) => (parse_quote!(::std::string::String), quote![owned_matcher]),
+                        _ => (target.clone(), quote![matcher]),
+                    };
+                    return Arg {
+                        owned_ty: Some(parse_quote!(#krate::Borrowed<#target>)),
+                        to_owned: quote![#name],
+                        matched: Some((
+                            matched_ty,
+                            quote![#krate::Borrowed::<#target>::#matcher(::core::convert::Into::into(#name))],
+                        )),
+                        lent: Some((quote![#krate::Borrowed::lend], quote![&*#name])),
+                        org_ty,
+                        name,
+                        movable: false,
+                    };
+                }
+            }
+            let movable = match org_ty {
+                _ if derefs => false,
+                Type::Reference(_) | Type::ImplTrait(_) => false,
+                _ => !(attr.unsafe_args.is_present() && is_unsafe_arg(&org_ty)),
             };
-            let (owned_ty, to_owned) = make_owned_type(&name, &org_ty);
             Arg {
                 org_ty,
                 owned_ty,
                 to_owned,
                 name,
+                matched: None,
+                lent: None,
+                movable,
             }
         })
         .collect();
     let mock_args = args.iter().map(|arg| {
         let name = &arg.name;
-        let ty = arg.ty().clone();
+        let ty = arg.matched.as_ref().map_or_else(|| arg.ty(), |(ty, _)| ty);
         quote! {
-            #name: impl Into<mry::ArgMatcher<#ty>>
+            #name: impl ::core::convert::Into<#krate::ArgMatcher<#ty>>
         }
     });
     let into_matchers = args.iter().map(|arg| {
         let name = &arg.name;
-        quote! {
-            #name.into()
+        if let Some((_, matcher)) = &arg.matched {
+            matcher.clone()
+        } else {
+            quote! {
+                ::core::convert::Into::into(#name)
+            }
         }
     });
     let input_types = args.iter().map(|arg| arg.ty()).collect::<Vec<_>>();
+    let arg_names = args.iter().map(|arg| arg.name.to_string());
+    // Callbacks are kept while the mock looks for the output, which borrows them for the call
+    // only, and are given back to the real implementation
+    let callbacks: Vec<_> = args
+        .iter()
+        .zip(&args_without_receiver)
+        .filter_map(|(arg, input)| {
+            let (trait_name, target) = callback_target(&arg.org_ty)?;
+            let mutability = match &*input.pat {
+                Pat::Ident(pat) => pat.mutability,
+                _ => None,
+            };
+            Some((&arg.name, trait_name, target, mutability))
+        })
+        .collect();
+    let keep_callbacks = callbacks.iter().map(|(name, ..)| {
+        quote! {
+            #[cfg(#cfg)]
+            let mut #name = ::core::option::Option::Some(#name);
+        }
+    });
+    let give_back_callbacks = callbacks.iter().map(|(arg_name, _, _, mutability)| {
+        quote! {
+            #[cfg(#cfg)]
+            #[allow(unused_variables)]
+            let #mutability #arg_name = #krate::returned_callback(#arg_name, #name);
+        }
+    });
+    let keep_callbacks = quote![#(#keep_callbacks)*];
+    let give_back_callbacks = quote![#(#give_back_callbacks)*];
+    let allow_unused_mut_or_blank = if callbacks.is_empty() {
+        TokenStream::default()
+    } else {
+        quote!(#[allow(unused_mut)])
+    };
     let owned_args = args.iter().map(|arg| {
         let name = &arg.name;
         let to_owned = &arg.to_owned;
-        if arg.owned_ty.is_some() {
+        if moves_args && arg.movable {
+            quote![#name]
+        } else if arg.owned_ty.is_some() {
             quote![#to_owned]
         } else {
             quote![#name]
         }
     });
-    let behavior_type = quote![mry::#behavior_name<(#(#input_types,)*), #static_output_type>];
+    let behavior_type = quote![#krate::#behavior_name<(#(#input_types,)*), #static_output_type>];
     let allow_non_snake_case_or_blank = if ident.to_string().starts_with('_') {
         quote!(#[allow(non_snake_case)])
     } else {
         TokenStream::default()
     };
-    let key = quote![std::any::Any::type_id(&#method_prefix #ident)];
+    // The default threshold of the lint, which methods with many parameters allow themselves
+    let allow_too_many_arguments_or_blank = if args.len() + usize::from(mock_receiver.is_some()) > 7
+    {
+        quote!(#[allow(clippy::too_many_arguments)])
+    } else {
+        TokenStream::default()
+    };
+    let key = if keyed_by_callback_marker(sig) {
+        let marker = callback_marker_path(method_debug_prefix, &ident);
+        quote![::core::any::TypeId::of::<#marker>()]
+    } else {
+        quote![::core::any::Any::type_id(&#method_prefix #ident)]
+    };
     let mut sig = sig.clone();
     sig.inputs = Punctuated::from_iter(
         receiver
             .into_iter()
             .chain(args_without_receiver.iter().cloned().map(FnArg::Typed)),
     );
+    let platform_cfgs = cfg_attrs(&attrs);
+    let deprecations: Vec<_> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("deprecated"))
+        .collect();
+    let deprecation = if deprecations.is_empty() {
+        TokenStream::default()
+    } else if attr.deprecated_mocks.is_present() {
+        quote!(#(#deprecations)*)
+    } else {
+        quote!(#[allow(deprecated)])
+    };
     let return_out = if is_impl_future {
         quote! {
             return async move { out };
@@ -156,46 +366,602 @@ pub(crate) fn transform(
             return out;
         }
     };
-    (
+    // The bound is higher-ranked not to be checked until `mock_*` is called
+    let not_mockable_or_blank = if skipped {
+        let marker = skipped_marker_path(method_debug_prefix, &ident);
+        quote![where for<'mry> #marker: #krate::MockableMethod]
+    } else {
+        TokenStream::default()
+    };
+    let record_call_and_find_mock_output = if moves_args {
+        or_input(record_call_and_find_mock_output)
+    } else {
+        record_call_and_find_mock_output
+    };
+    let clone_into_log_or_blank = match (moves_args, logs_moved_args) {
+        (false, _) => TokenStream::default(),
+        (true, true) => quote![, ::core::option::Option::Some(::core::clone::Clone::clone)],
+        (true, false) => quote![, ::core::option::Option::None],
+    };
+    let find_mock_output = quote![#record_call_and_find_mock_output::<_, #static_output_type>(#key, #name, (#(#owned_args,)*) #clone_into_log_or_blank)];
+    let find_mock_output = match lent_receiver {
+        Some(receiver) => quote![#krate::with_receiver(#receiver, || #find_mock_output)],
+        None => find_mock_output,
+    };
+    let find_mock_output = args
+        .iter()
+        .rev()
+        .fold(find_mock_output, |find_mock_output, arg| {
+            let name = &arg.name;
+            match &arg.lent {
+                Some((lend, args)) => quote![#lend(#args, |#name| #find_mock_output)],
+                None => find_mock_output,
+            }
+        });
+    let find_mock_output = callbacks.iter().rev().fold(
+        find_mock_output,
+        |find_mock_output, (name, trait_name, target, _)| {
+            let run = if *trait_name == "FnOnce" {
+                quote![once]
+            } else {
+                quote![each]
+            };
+            quote![#krate::Callback::lend(&mut |__mry_value: &mut #target| #krate::Callback::#run(&mut #name, __mry_value), |#name| #find_mock_output)]
+        },
+    );
+    // Futures of `returns_with_async` are awaited here, after the lookup releases the mock
+    let find_mock_output = if sig.asyncness.is_some() {
+        quote![(match #krate::__find_async_output::<_, #static_output_type>(|| #find_mock_output) {
+            ::core::result::Result::Ok(found) => found,
+            ::core::result::Result::Err(future) => return future.await,
+        })]
+    } else {
+        find_mock_output
+    };
+    // Async bodies may resume on other threads, which the guard counting on this thread can't follow
+    let guard_recursion = if sig.asyncness.is_none() {
+        quote! {
+            #[cfg(#cfg)]
+            let _mry_real_impl = #krate::__enter_real_impl(#name);
+        }
+    } else {
+        TokenStream::default()
+    };
+    let not_send_errors: Vec<_> = if skipped {
+        Vec::new()
+    } else {
+        // Outputs needn't be `Send`, which behaviors produce on each call or keep behind `Send`
+        args.iter()
+            .filter_map(|arg| not_send_error(arg.ty(), &arg.name))
+            .collect()
+    };
+    let member = if skipped {
+        quote! {
+            #(#attrs)*
+            #vis #original_sig {
+                #body
+            }
+        }
+    } else if moves_args {
+        // Arguments moved into the call are given back for the real implementation
+        let given_back = args.iter().zip(&args_without_receiver).map(|(arg, input)| {
+            let name = &arg.name;
+            match &*input.pat {
+                Pat::Ident(pat) if arg.movable => {
+                    let mutability = &pat.mutability;
+                    quote![#mutability #name]
+                }
+                _ if arg.movable => quote![#name],
+                _ => quote![_],
+            }
+        });
         quote! {
             #(#attrs)*
+            #[allow(unused_mut)]
             #vis #sig {
-                #[cfg(debug_assertions)]
-                if let Some(out) = #record_call_and_find_mock_output::<_, #static_output_type>(#key, #name, (#(#owned_args,)*)) {
+                #keep_callbacks
+                #[cfg(#cfg)]
+                let (#(#given_back,)*) = match #find_mock_output {
+                    ::core::result::Result::Ok(out) => {
+                        #return_out
+                    }
+                    ::core::result::Result::Err(input) => input,
+                };
+                #give_back_callbacks
+                #guard_recursion
+                #(#bindings)*
+                #body
+            }
+        }
+    } else {
+        quote! {
+            #(#attrs)*
+            #allow_unused_mut_or_blank
+            #vis #sig {
+                #keep_callbacks
+                #[cfg(#cfg)]
+                if let ::core::option::Option::Some(out) = #find_mock_output {
                     #return_out
                 }
+                #give_back_callbacks
+                #guard_recursion
                 #(#bindings)*
                 #body
             }
-        },
+        }
+    };
+    let member = quote! {
+        #(
+            #[cfg(#cfg)]
+            #not_send_errors
+        )*
+        #member
+    };
+    (
+        member,
         quote! {
-            #[cfg(debug_assertions)]
+            #[cfg(#cfg)]
+            #(#platform_cfgs)*
             #allow_non_snake_case_or_blank
+            #allow_too_many_arguments_or_blank
+            #deprecation
             #[must_use]
-            pub fn #mock_ident (#mock_receiver #(#mock_args),*) -> mry::MockLocator<(#(#input_types,)*), #static_output_type, #behavior_type> {
-                mry::MockLocator::new(
+            pub fn #mock_ident (#mock_receiver #(#mock_args),*) -> #krate::MockLocator<(#(#input_types,)*), #static_output_type, #behavior_type>
+            #not_mockable_or_blank
+            {
+                #krate::MockLocator::new(
                     #mocks_tokens,
                     #key,
                     #name,
-                    (#(#into_matchers,)*).into(),
+                    &[#(#arg_names),*],
+                    ::core::convert::Into::into((#(#into_matchers,)*)),
                 )
             }
         },
     )
 }
 
-pub fn make_owned_type(name: &Ident, ty: &Type) -> (Option<Type>, TokenStream) {
+/// Well-known types which are not `Send`, found by the last segment of their paths
+const NOT_SEND: &[&str] = &[
+    "Rc",
+    "NonNull",
+    "MutexGuard",
+    "RwLockReadGuard",
+    "RwLockWriteGuard",
+];
+
+/// Compile error at a well-known type which is not `Send` in the type, found best-effort, since
+/// the error of the `Send` bound points at the attribute without naming the argument
+fn not_send_error(ty: &Type, name: &Ident) -> Option<TokenStream> {
+    struct FindNotSend<'a>(Option<&'a syn::TypePath>);
+
+    impl<'a> Visit<'a> for FindNotSend<'a> {
+        fn visit_type_path(&mut self, ty: &'a syn::TypePath) {
+            if self.0.is_none()
+                && ty
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| NOT_SEND.iter().any(|name| segment.ident == name))
+            {
+                self.0 = Some(ty);
+            }
+            syn::visit::visit_type_path(self, ty);
+        }
+    }
+
+    let mut find = FindNotSend(None);
+    find.visit_type(ty);
+    let not_send = find.0?;
+    let rendered = quote!(#not_send).to_string().replace(' ', "");
+    Some(
+        syn::Error::new_spanned(
+            not_send,
+            format!(
+                "`{}` in the argument `{}` is not `Send`, which mocks need to record the calls. Use a `Send` type such as `Arc` instead, or skip the method by `#[mry(skip)]`",
+                rendered, name
+            ),
+        )
+        .to_compile_error(),
+    )
+}
+
+/// `#[mry(redact)]` on a parameter, which records the argument as `mry::Redacted`
+pub(crate) fn is_redact(attr: &Attribute) -> bool {
+    is_mry_flag(attr, "redact")
+}
+
+/// Projection and its type of `#[mry(log_with = |user| -> u32 { user.id })]` on a parameter,
+/// which records the projection instead of the argument. The closure may also be a string literal
+fn log_with(attrs: &[Attribute]) -> Option<syn::Result<(Expr, Type)>> {
+    let meta = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("mry"))
+        .filter_map(|attr| attr.parse_args::<MetaNameValue>().ok())
+        .find(|meta| meta.path.is_ident("log_with"))?;
+    let closure = match meta.value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => match lit.parse::<Expr>() {
+            Ok(closure) => closure,
+            Err(err) => return Some(Err(err)),
+        },
+        closure => closure,
+    };
+    Some(match &closure {
+        Expr::Closure(ExprClosure {
+            output: ReturnType::Type(_, ty),
+            ..
+        }) => Ok((closure.clone(), ty.as_ref().clone())),
+        _ => Err(syn::Error::new_spanned(
+            &closure,
+            "`log_with` needs a closure with the type of the projection, such as `|user| -> u32 { user.id }`",
+        )),
+    })
+}
+
+/// Names of the parameters, which are the bound names of patterns such as `name` of `A { name }: A`
+/// or `arg0` if the pattern binds nothing or the name is taken
+fn param_names(inputs: &[PatType]) -> Vec<Ident> {
+    let idents: Vec<&Ident> = inputs
+        .iter()
+        .filter_map(|input| match &*input.pat {
+            Pat::Ident(pat) => Some(&pat.ident),
+            _ => None,
+        })
+        .collect();
+    let mut names: Vec<Ident> = Vec::new();
+    for (index, input) in inputs.iter().enumerate() {
+        if let Pat::Ident(pat) = &*input.pat {
+            names.push(pat.ident.clone());
+            continue;
+        }
+        let mut bound = Vec::new();
+        bound_idents(&input.pat, &mut bound);
+        let joined = bound
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("_");
+        let taken = |name: &str| {
+            idents.iter().any(|ident| *ident == name) || names.iter().any(|ident| ident == name)
+        };
+        names.push(if joined.is_empty() || taken(&joined) {
+            format_ident!("arg{}", index)
+        } else {
+            Ident::new(&joined, Span::call_site())
+        });
+    }
+    names
+}
+
+fn bound_idents<'a>(pat: &'a Pat, idents: &mut Vec<&'a Ident>) {
+    match pat {
+        Pat::Ident(pat) => {
+            idents.push(&pat.ident);
+            if let Some((_, subpat)) = &pat.subpat {
+                bound_idents(subpat, idents);
+            }
+        }
+        Pat::Or(pat) => {
+            // Every case binds the same names
+            if let Some(case) = pat.cases.first() {
+                bound_idents(case, idents);
+            }
+        }
+        Pat::Paren(pat) => bound_idents(&pat.pat, idents),
+        Pat::Reference(pat) => bound_idents(&pat.pat, idents),
+        Pat::Slice(pat) => pat.elems.iter().for_each(|elem| bound_idents(elem, idents)),
+        Pat::Struct(pat) => pat
+            .fields
+            .iter()
+            .for_each(|field| bound_idents(&field.pat, idents)),
+        Pat::Tuple(pat) => pat.elems.iter().for_each(|elem| bound_idents(elem, idents)),
+        Pat::TupleStruct(pat) => pat.elems.iter().for_each(|elem| bound_idents(elem, idents)),
+        Pat::Type(pat) => bound_idents(&pat.pat, idents),
+        _ => {}
+    }
+}
+
+/// `#[mry(move_args)]` on a method, which moves the arguments taken by value into the call record
+/// instead of cloning them
+fn is_move_args(attr: &Attribute) -> bool {
+    is_mry_flag(attr, "move_args")
+}
+
+/// Path of the recorder giving the input back for the real implementation, such as
+/// `self.mry.record_call_and_find_mock_output_or_input`
+/// `#[mry(move_args(unlogged))]` on a method, which moves the arguments without cloning them into
+/// the log for the real implementation, so the calls falling through to it are only counted
+fn is_unlogged_move_args(attr: &Attribute) -> bool {
+    attr.path().is_ident("mry")
+        && attr
+            .parse_args::<syn::MetaList>()
+            .map(|list| {
+                list.path.is_ident("move_args")
+                    && list
+                        .parse_args::<Ident>()
+                        .map(|arg| arg == "unlogged")
+                        .unwrap_or(false)
+            })
+            .unwrap_or(false)
+}
+
+fn or_input(record_call_and_find_mock_output: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = record_call_and_find_mock_output.into_iter().collect();
+    if let Some(TokenTree::Ident(last)) = tokens.last_mut() {
+        *last = format_ident!("{}_or_input", last);
+    }
+    tokens.into_iter().collect()
+}
+
+/// `#[mry(deref)]` on a parameter of `Arc<T>`, `Box<T>` or `Rc<T>`, which records the `T` it
+/// points to
+fn is_deref(attr: &Attribute) -> bool {
+    is_mry_flag(attr, "deref")
+}
+
+/// Owned type of `T` pointed by an `Arc<T>`, `Box<T>` or `Rc<T>` parameter or a reference to it,
+/// and the clone of it. Pointers to `str` are recorded as `String`
+fn deref_owned_type(name: &Ident, ty: &Type) -> syn::Result<(Type, TokenStream)> {
+    let (pointer, deref) = match ty {
+        Type::Reference(reference) => (reference.elem.as_ref(), quote![**#name]),
+        ty => (ty, quote![*#name]),
+    };
+    let target = smart_pointer_target(pointer).ok_or_else(|| {
+        syn::Error::new_spanned(
+            ty,
+            "`deref` needs a parameter of `Arc<T>`, `Box<T>` or `Rc<T>`",
+        )
+    })?;
+    match target {
+        Type::Path(path) if path.path.is_ident("str") => Ok((
+            parse_quote!(::std::string::String),
+            quote![::std::string::ToString::to_string(&#deref)],
+        )),
+        Type::TraitObject(_) | Type::ImplTrait(_) | Type::Slice(_) => Err(syn::Error::new_spanned(
+            target,
+            "`deref` cannot record an unsized value, which is not clonable",
+        )),
+        target => Ok((
+            target.clone(),
+            quote![<#target as ::core::clone::Clone>::clone(&#deref)],
+        )),
+    }
+}
+
+/// `T` of `Arc<T>`, `Box<T>` or `Rc<T>`, with or without the path such as `std::sync::Arc<T>`
+fn smart_pointer_target(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if !["Arc", "Box", "Rc"].iter().any(|name| last.ident == name) {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(target) if args.args.len() == 1 => Some(target),
+        _ => None,
+    }
+}
+
+/// `#[mry(skip)]` on a method, which keeps it real and makes its `mock_*` a compile error
+pub(crate) fn is_skip(attr: &Attribute) -> bool {
+    is_mry_flag(attr, "skip")
+}
+
+fn is_mry_flag(attr: &Attribute, flag: &str) -> bool {
+    attr.path().is_ident("mry")
+        && attr
+            .parse_args::<Ident>()
+            .map(|arg| arg == flag)
+            .unwrap_or(false)
+}
+
+/// Module of a marker named after a method, unique to the kind, the type and the method
+fn marker_module(kind: &str, method_debug_prefix: &str, ident: &Ident) -> Ident {
+    let type_name = method_debug_prefix
+        .split(|char: char| !char.is_alphanumeric() && char != '_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    format_ident!("__mry_{}_{}_{}", kind, type_name, ident)
+}
+
+fn skipped_marker_path(method_debug_prefix: &str, ident: &Ident) -> TokenStream {
+    let module = marker_module("skipped", method_debug_prefix, ident);
+    quote![#module::#ident]
+}
+
+fn callback_marker_path(method_debug_prefix: &str, ident: &Ident) -> TokenStream {
+    let module = marker_module("callback", method_debug_prefix, ident);
+    quote![#module::#ident]
+}
+
+/// Methods with a callback parameter are keyed by a marker, since their function items cannot be
+/// named without the types of the callbacks. Static functions are not, as `#[mry::lock]` names them
+fn keyed_by_callback_marker(sig: &Signature) -> bool {
+    matches!(sig.inputs.first(), Some(FnArg::Receiver(_)))
+        && sig.inputs.iter().any(|input| match input {
+            FnArg::Typed(typed_arg) => callback_target(&typed_arg.ty).is_some(),
+            FnArg::Receiver(_) => false,
+        })
+}
+
+/// Marker of a method skipped by `#[mry(skip)]`, which names the method in the compile error of
+/// its `mock_*`
+pub(crate) fn skipped_marker(
+    attr: &MryAttr,
+    method_debug_prefix: &str,
+    attrs: &[Attribute],
+    sig: &Signature,
+) -> TokenStream {
+    if !attrs.iter().any(is_skip) {
+        return TokenStream::default();
+    }
+    let cfg = attr.cfg();
+    let platform_cfgs = cfg_attrs(attrs);
+    let ident = &sig.ident;
+    let module = marker_module("skipped", method_debug_prefix, ident);
+    quote! {
+        #[cfg(#cfg)]
+        #(#platform_cfgs)*
+        #[doc(hidden)]
+        #[allow(non_snake_case, non_camel_case_types)]
+        pub mod #module {
+            pub struct #ident {}
+        }
+    }
+}
+
+/// Removes `#[mry(redact)]` and `#[mry(log_with = ..)]` which are only meaningful to mry from the
+/// parameters
+pub(crate) fn strip_param_attrs(sig: &mut Signature) {
+    for input in sig.inputs.iter_mut() {
+        if let FnArg::Typed(typed_arg) = input {
+            typed_arg.attrs.retain(|attr| !attr.path().is_ident("mry"));
+        }
+    }
+}
+
+/// Marker keying the mock of a method with a callback parameter
+pub(crate) fn callback_marker(
+    attr: &MryAttr,
+    method_debug_prefix: &str,
+    attrs: &[Attribute],
+    sig: &Signature,
+) -> TokenStream {
+    if !keyed_by_callback_marker(sig) {
+        return TokenStream::default();
+    }
+    let cfg = attr.cfg();
+    let platform_cfgs = cfg_attrs(attrs);
+    let ident = &sig.ident;
+    let module = marker_module("callback", method_debug_prefix, ident);
+    quote! {
+        #[cfg(#cfg)]
+        #(#platform_cfgs)*
+        #[doc(hidden)]
+        #[allow(non_snake_case, non_camel_case_types)]
+        pub mod #module {
+            pub struct #ident {}
+        }
+    }
+}
+
+/// `#[cfg(...)]` of the original item, which the generated companions must follow
+pub(crate) fn cfg_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("cfg"))
+}
+
+/// `mry::MethodMeta` of the signature
+/// Entry of the method in `MRY_METHODS`, under the cfg attributes of the method so methods defined
+/// once per platform are listed once
+pub(crate) fn meta(krate: &Path, attrs: &[Attribute], sig: &Signature) -> TokenStream {
+    let platform_cfgs = cfg_attrs(attrs);
+    let name = sig.ident.to_string();
+    let arg_types: Vec<_> = sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(typed_arg) => Some(type_name(&typed_arg.ty)),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let arity = arg_types.len();
+    let return_type = match &sig.output {
+        ReturnType::Default => "()".to_string(),
+        ReturnType::Type(_, ty) => type_name(ty),
+    };
+    quote! {
+        #(#platform_cfgs)*
+        #krate::MethodMeta {
+            name: #name,
+            arity: #arity,
+            arg_types: &[#(#arg_types),*],
+            return_type: #return_type,
+        }
+    }
+}
+
+/// Pretty printed type
+pub(crate) fn type_name(ty: &Type) -> String {
+    quote!(#ty)
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" ,", ",")
+        .replace("< ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace("& ", "&")
+}
+
+pub fn make_owned_type(attr: &MryAttr, name: &Ident, ty: &Type) -> (Option<Type>, TokenStream) {
+    if attr.unsafe_args.is_present() && is_unsafe_arg(ty) {
+        let krate = attr.krate();
+        return (
+            Some(parse_quote!(#krate::Address)),
+            quote![#krate::Address::of(&#name)],
+        );
+    }
+    if let Some((_, target)) = callback_target(ty) {
+        let krate = attr.krate();
+        return (Some(parse_quote!(#krate::Callback<#target>)), quote![#name]);
+    }
     if is_str(ty) {
-        return (Some(parse_quote!(String)), quote![#name.to_string()]);
+        return (
+            Some(parse_quote!(::std::string::String)),
+            quote![::std::string::ToString::to_string(#name)],
+        );
     }
     let owned = match ty {
         Type::Reference(ty) => Some(ty.elem.as_ref().clone()),
         ty => Some(ty.clone()),
     };
-    let cloned = quote![<#owned>::clone(&#name)];
+    let cloned = quote![<#owned as ::core::clone::Clone>::clone(&#name)];
     (owned, cloned)
 }
 
+/// `Fn*` trait and `T` of a callback parameter `impl FnOnce(&mut T)`, `impl FnMut(&mut T)` or
+/// `impl Fn(&mut T)`, which is recorded as `mry::Callback<T>`
+pub fn callback_target(ty: &Type) -> Option<(&Ident, &Type)> {
+    let syn::Type::ImplTrait(impl_trait) = ty else {
+        return None;
+    };
+    let syn::TypeParamBound::Trait(bound) = impl_trait.bounds.first()? else {
+        return None;
+    };
+    let last = bound.path.segments.last()?;
+    if !["FnOnce", "FnMut", "Fn"]
+        .iter()
+        .any(|name| last.ident == name)
+    {
+        return None;
+    }
+    let syn::PathArguments::Parenthesized(args) = &last.arguments else {
+        return None;
+    };
+    if args.inputs.len() != 1 {
+        return None;
+    }
+    match &args.output {
+        ReturnType::Default => {}
+        ReturnType::Type(_, output) => match output.as_ref() {
+            Type::Tuple(tuple) if tuple.elems.is_empty() => {}
+            _ => return None,
+        },
+    }
+    match &args.inputs[0] {
+        Type::Reference(reference) if reference.mutability.is_some() => {
+            Some((&last.ident, &reference.elem))
+        }
+        _ => None,
+    }
+}
+
 pub fn impl_future(ty: &Type) -> Option<&Type> {
     let syn::Type::ImplTrait(impl_trait) = ty else {
         return None;
@@ -227,12 +993,25 @@ pub fn make_static_type(ty: &Type) -> TokenStream {
         }
         Type::ImplTrait(impl_trait) => {
             let bounds = &impl_trait.bounds;
-            quote!(Box<dyn #bounds>)
+            quote!(::std::boxed::Box<dyn #bounds>)
         }
         ty => quote!(#ty),
     }
 }
 
+/// Raw pointers, `NonNull` and `MaybeUninit`, which are recorded by address with `unsafe_args`
+pub fn is_unsafe_arg(ty: &Type) -> bool {
+    match ty {
+        Type::Ptr(_) => true,
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "NonNull" || segment.ident == "MaybeUninit"),
+        _ => false,
+    }
+}
+
 pub fn is_str(ty: &Type) -> bool {
     match ty {
         Type::Reference(ty) => {
@@ -269,10 +1048,12 @@ mod test {
 
     fn t(method: &ImplItemFn) -> (TokenStream, TokenStream) {
         transform(
+            &MryAttr::default(),
             quote![self.mry.mocks()],
             quote![Self::],
             "Cat::",
             quote![self.mry.record_call_and_find_mock_output],
+            false,
             Some(&method.vis),
             &method.attrs,
             &method.sig,
@@ -301,20 +1082,23 @@ mod test {
             quote! {
                 fn meow(&self, count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     "meow".repeat(count)
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -336,21 +1120,24 @@ mod test {
             quote! {
                 fn _meow(&self, count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::_meow), "Cat::_meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::_meow), "Cat::_meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::_meow");
                     "meow".repeat(count)
                 }
 
                 #[cfg(debug_assertions)]
                 #[allow(non_snake_case)]
                 #[must_use]
-                pub fn mock__meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock__meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::_meow),
+                        ::core::any::Any::type_id(&Self::_meow),
                         "Cat::_meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -372,20 +1159,23 @@ mod test {
             quote! {
                 fn meow(&self) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", ()) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", ()) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     "meow".into()
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self,) -> mry::MockLocator<(), String, mry::Behavior0<(), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self,) -> ::mry::MockLocator<(), String, ::mry::Behavior0<(), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        ().into(),
+                        &[],
+                        ::core::convert::Into::into(()),
                     )
                 }
             }
@@ -407,20 +1197,23 @@ mod test {
             quote! {
                 fn meow(&self, base: String, count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<String>::clone(&base), <usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<String as ::core::clone::Clone>::clone(&base), <usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     base.repeat(count)
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, base: impl Into<mry::ArgMatcher<String>>, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(String, usize,), String, mry::Behavior2<(String, usize,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, base: impl ::core::convert::Into<::mry::ArgMatcher<String>>, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(String, usize,), String, ::mry::Behavior2<(String, usize,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (base.into(), count.into(),).into(),
+                        &["base", "count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(base), ::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -442,21 +1235,24 @@ mod test {
             quote! {
                 fn meow(&self, out: &'static mut String, base: &str, count: &usize) {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, ()>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<String>::clone(&out), base.to_string(), <usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, ()>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<String as ::core::clone::Clone>::clone(&out), ::std::string::ToString::to_string(base), <usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     *out = base.repeat(count);
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, out: impl Into <mry::ArgMatcher<String>>, base: impl Into<mry::ArgMatcher<String>>, count: impl Into<mry::ArgMatcher<usize>>)
-                    -> mry::MockLocator<(String, String, usize,), (), mry::Behavior3<(String, String, usize,), ()> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, out: impl ::core::convert::Into<::mry::ArgMatcher<String>>, base: impl ::core::convert::Into<::mry::ArgMatcher<::std::string::String>>, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>)
+                    -> ::mry::MockLocator<(String, ::std::string::String, usize,), (), ::mry::Behavior3<(String, ::std::string::String, usize,), ()> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (out.into(), base.into(), count.into(),).into(),
+                        &["out", "base", "count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(out), ::core::convert::Into::into(base), ::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -478,7 +1274,10 @@ mod test {
             quote! {
                 async fn meow(&self, count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = (match ::mry::__find_async_output::<_, String>(|| self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
+                        ::core::result::Result::Ok(found) => found,
+                        ::core::result::Result::Err(future) => return future.await,
+                    }) {
                         return out;
                     }
                     base().await.repeat(count);
@@ -486,12 +1285,13 @@ mod test {
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -511,24 +1311,27 @@ mod test {
         assert_eq!(
             t(&input).to_string(),
             quote! {
-                fn meow(&self, arg0: A, count: usize, arg2: String) -> String {
+                fn meow(&self, name: A, count: usize, arg2: String) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<A>::clone(&arg0), <usize>::clone(&count), <String>::clone(&arg2),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<A as ::core::clone::Clone>::clone(&name), <usize as ::core::clone::Clone>::clone(&count), <String as ::core::clone::Clone>::clone(&arg2),)) {
                         return out;
                     }
-                    let A { name } = arg0;
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
+                    let A { name } = name;
                     let _ = arg2;
                     name.repeat(count)
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, arg0: impl Into<mry::ArgMatcher<A>>, count: impl Into<mry::ArgMatcher<usize>>, arg2: impl Into<mry::ArgMatcher<String>>) -> mry::MockLocator<(A, usize, String,), String, mry::Behavior3<(A, usize, String,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, name: impl ::core::convert::Into<::mry::ArgMatcher<A>>, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>, arg2: impl ::core::convert::Into<::mry::ArgMatcher<String>>) -> ::mry::MockLocator<(A, usize, String,), String, ::mry::Behavior3<(A, usize, String,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (arg0.into(), count.into(), arg2.into(),).into(),
+                        &["name", "count", "arg2"],
+                        ::core::convert::Into::into((::core::convert::Into::into(name), ::core::convert::Into::into(count), ::core::convert::Into::into(arg2),)),
                     )
                 }
             }
@@ -550,9 +1353,11 @@ mod test {
             quote! {
                 pub fn meow(&self, count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     "meow".repeat(count)
                 }
             }
@@ -575,21 +1380,24 @@ mod test {
             quote! {
                 fn increment(&self, mut count: usize) -> usize {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, usize>(std::any::Any::type_id(&Self::increment), "Cat::increment", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, usize>(::core::any::Any::type_id(&Self::increment), "Cat::increment", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::increment");
                     count += 1;
                     count
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_increment(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), usize, mry::Behavior1<(usize,), usize> > {
-                    mry::MockLocator::new(
+                pub fn mock_increment(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), usize, ::mry::Behavior1<(usize,), usize> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::increment),
+                        ::core::any::Any::type_id(&Self::increment),
                         "Cat::increment",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -611,20 +1419,23 @@ mod test {
             quote! {
                 fn meow<'a, T: Display, const A: usize>(&self, a: usize) -> &'a String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, &'static String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<usize>::clone(&a),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, &'static String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&a),)) {
                         return out;
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     todo!()
                 }
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, a: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), &'static String, mry::Behavior1<(usize,), &'static String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, a: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), &'static String, ::mry::Behavior1<(usize,), &'static String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (a.into(),).into(),
+                        &["a"],
+                        ::core::convert::Into::into((::core::convert::Into::into(a),)),
                     )
                 }
             }
@@ -646,7 +1457,10 @@ mod test {
             quote! {
                 async fn meow(&self, count: usize) -> String {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = (match ::mry::__find_async_output::<_, String>(|| self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),))) {
+                        ::core::result::Result::Ok(found) => found,
+                        ::core::result::Result::Err(future) => return future.await,
+                    }) {
                         return out;
                     }
                     "meow".repeat(count)
@@ -654,12 +1468,13 @@ mod test {
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }
@@ -667,6 +1482,49 @@ mod test {
         );
     }
 
+    #[test]
+    fn meta() {
+        let input: ImplItemFn = parse_quote! {
+            fn meow(&self, base: &str, counts: Vec<usize>) {}
+        };
+
+        assert_eq!(
+            super::meta(&parse_quote!(::mry), &input.attrs, &input.sig).to_string(),
+            quote! {
+                ::mry::MethodMeta {
+                    name: "meow",
+                    arity: 2usize,
+                    arg_types: &["&str", "Vec<usize>"],
+                    return_type: "()",
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn meta_with_cfg() {
+        let input: ImplItemFn = parse_quote! {
+            #[cfg(unix)]
+            #[inline]
+            fn meow(&self) {}
+        };
+
+        assert_eq!(
+            super::meta(&parse_quote!(::mry), &input.attrs, &input.sig).to_string(),
+            quote! {
+                #[cfg(unix)]
+                ::mry::MethodMeta {
+                    name: "meow",
+                    arity: 0usize,
+                    arg_types: &[],
+                    return_type: "()",
+                }
+            }
+            .to_string()
+        );
+    }
+
     #[test]
     fn return_position_impl_future() {
         let input: ImplItemFn = parse_quote! {
@@ -682,9 +1540,11 @@ mod test {
                 quote! {
                 fn meow(&self, count: usize) -> impl std::future::Future<Output = String> + Send {
                     #[cfg(debug_assertions)]
-                    if let Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(std::any::Any::type_id(&Self::meow), "Cat::meow", (<usize>::clone(&count),)) {
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, String>(::core::any::Any::type_id(&Self::meow), "Cat::meow", (<usize as ::core::clone::Clone>::clone(&count),)) {
                         return async move { out };
                     }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Cat::meow");
                     async move {
                         "meow".repeat(count)
                     }
@@ -692,15 +1552,344 @@ mod test {
 
                 #[cfg(debug_assertions)]
                 #[must_use]
-                pub fn mock_meow(&mut self, count: impl Into<mry::ArgMatcher<usize>>) -> mry::MockLocator<(usize,), String, mry::Behavior1<(usize,), String> > {
-                    mry::MockLocator::new(
+                pub fn mock_meow(&mut self, count: impl ::core::convert::Into<::mry::ArgMatcher<usize>>) -> ::mry::MockLocator<(usize,), String, ::mry::Behavior1<(usize,), String> > {
+                    ::mry::MockLocator::new(
                         self.mry.mocks(),
-                        std::any::Any::type_id(&Self::meow),
+                        ::core::any::Any::type_id(&Self::meow),
                         "Cat::meow",
-                        (count.into(),).into(),
+                        &["count"],
+                        ::core::convert::Into::into((::core::convert::Into::into(count),)),
                     )
                 }
             }.to_string()
         );
     }
+
+    #[test]
+    fn unsafe_args() {
+        let input: ImplItemFn = parse2(quote! {
+            fn write(&self, ptr: *mut u8, len: usize) {}
+        })
+        .unwrap();
+        let attr = MryAttr {
+            unsafe_args: darling::util::Flag::present(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            transform(
+                &attr,
+                quote![self.mry.mocks()],
+                quote![Self::],
+                "Buf::",
+                quote![self.mry.record_call_and_find_mock_output],
+                false,
+                Some(&input.vis),
+                &input.attrs,
+                &input.sig,
+                &TokenStream::default(),
+            )
+            .to_string(),
+            quote! {
+                fn write(&self, ptr: *mut u8, len: usize) {
+                    #[cfg(debug_assertions)]
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, ()>(::core::any::Any::type_id(&Self::write), "Buf::write", (::mry::Address::of(&ptr), <usize as ::core::clone::Clone>::clone(&len),)) {
+                        return out;
+                    }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Buf::write");
+                }
+
+                #[cfg(debug_assertions)]
+                #[must_use]
+                pub fn mock_write(&mut self, ptr: impl ::core::convert::Into<::mry::ArgMatcher<::mry::Address>>, len: impl ::core::convert::Into<::mry::ArgMatcher<usize>>)
+                    -> ::mry::MockLocator<(::mry::Address, usize,), (), ::mry::Behavior2<(::mry::Address, usize,), ()> > {
+                    ::mry::MockLocator::new(
+                        self.mry.mocks(),
+                        ::core::any::Any::type_id(&Self::write),
+                        "Buf::write",
+                        &["ptr", "len"],
+                        ::core::convert::Into::into((::core::convert::Into::into(ptr), ::core::convert::Into::into(len),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn redact() {
+        let input: ImplItemFn = parse2(quote! {
+            fn login(&self, user: &str, #[mry(redact)] password: &str) -> bool {}
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(
+                &MryAttr::default(),
+                quote![self.mry.mocks()],
+                quote![Self::],
+                "Auth::",
+                quote![self.mry.record_call_and_find_mock_output],
+                false,
+                Some(&input.vis),
+                &input.attrs,
+                &input.sig,
+                &TokenStream::default(),
+            )
+            .to_string(),
+            quote! {
+                fn login(&self, user: &str, password: &str) -> bool {
+                    #[cfg(debug_assertions)]
+                    if let ::core::option::Option::Some(out) = self.mry.record_call_and_find_mock_output::<_, bool>(::core::any::Any::type_id(&Self::login), "Auth::login", (::std::string::ToString::to_string(user), ::mry::Redacted(::std::string::ToString::to_string(password)),)) {
+                        return out;
+                    }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Auth::login");
+                }
+
+                #[cfg(debug_assertions)]
+                #[must_use]
+                pub fn mock_login(&mut self, user: impl ::core::convert::Into<::mry::ArgMatcher<::std::string::String>>, password: impl ::core::convert::Into<::mry::ArgMatcher<::std::string::String>>)
+                    -> ::mry::MockLocator<(::std::string::String, ::mry::Redacted<::std::string::String>,), bool, ::mry::Behavior2<(::std::string::String, ::mry::Redacted<::std::string::String>,), bool> > {
+                    ::mry::MockLocator::new(
+                        self.mry.mocks(),
+                        ::core::any::Any::type_id(&Self::login),
+                        "Auth::login",
+                        &["user", "password"],
+                        ::core::convert::Into::into((::core::convert::Into::into(user), ::mry::Redacted::matcher(::core::convert::Into::into(password)),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn log_with() {
+        let input: ImplItemFn = parse2(quote! {
+            fn store(&self, #[mry(log_with = |report| -> u32 { report.id })] report: &Report) -> bool {}
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(
+                &MryAttr::default(),
+                quote![self.mry.mocks()],
+                quote![Self::],
+                "Archive::",
+                quote![self.mry.record_call_and_find_mock_output],
+                false,
+                Some(&input.vis),
+                &input.attrs,
+                &input.sig,
+                &TokenStream::default(),
+            )
+            .to_string(),
+            quote! {
+                fn store(&self, report: &Report) -> bool {
+                    #[cfg(debug_assertions)]
+                    if let ::core::option::Option::Some(out) = ::mry::Projected::lend(report, |report| -> u32 { report.id }, |report| self.mry.record_call_and_find_mock_output::<_, bool>(::core::any::Any::type_id(&Self::store), "Archive::store", (report,))) {
+                        return out;
+                    }
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Archive::store");
+                }
+
+                #[cfg(debug_assertions)]
+                #[must_use]
+                pub fn mock_store(&mut self, report: impl ::core::convert::Into<::mry::ArgMatcher<Report>>)
+                    -> ::mry::MockLocator<(::mry::Projected<Report, u32>,), bool, ::mry::Behavior1<(::mry::Projected<Report, u32>,), bool> > {
+                    ::mry::MockLocator::new(
+                        self.mry.mocks(),
+                        ::core::any::Any::type_id(&Self::store),
+                        "Archive::store",
+                        &["report"],
+                        ::core::convert::Into::into((::mry::Projected::<Report, u32>::matcher(::core::convert::Into::into(report), |report| -> u32 { report.id }),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn log_with_needs_type_of_projection() {
+        let input: ImplItemFn = parse2(quote! {
+            fn store(&self, #[mry(log_with = |report| report.id)] report: &Report) {}
+        })
+        .unwrap();
+
+        let (member, _) = transform(
+            &MryAttr::default(),
+            quote![self.mry.mocks()],
+            quote![Self::],
+            "Archive::",
+            quote![self.mry.record_call_and_find_mock_output],
+            false,
+            Some(&input.vis),
+            &input.attrs,
+            &input.sig,
+            &TokenStream::default(),
+        );
+        assert!(member
+            .to_string()
+            .contains("`log_with` needs a closure with the type of the projection"));
+    }
+
+    #[test]
+    fn move_args() {
+        let input: ImplItemFn = parse2(quote! {
+            #[mry(move_args)]
+            fn upload(&self, bucket: &str, mut blob: Blob) -> usize {}
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(
+                &MryAttr::default(),
+                quote![self.mry.mocks()],
+                quote![Self::],
+                "Uploader::",
+                quote![self.mry.record_call_and_find_mock_output],
+                false,
+                Some(&input.vis),
+                &input.attrs,
+                &input.sig,
+                &TokenStream::default(),
+            )
+            .to_string(),
+            quote! {
+                #[allow(unused_mut)]
+                fn upload(&self, bucket: &str, mut blob: Blob) -> usize {
+                    #[cfg(debug_assertions)]
+                    let (_, mut blob,) = match ::mry::Borrowed::lend(&*bucket, |bucket| self.mry.record_call_and_find_mock_output_or_input::<_, usize>(::core::any::Any::type_id(&Self::upload), "Uploader::upload", (bucket, blob,), ::core::option::Option::Some(::core::clone::Clone::clone))) {
+                        ::core::result::Result::Ok(out) => {
+                            return out;
+                        }
+                        ::core::result::Result::Err(input) => input,
+                    };
+                    #[cfg(debug_assertions)]
+                    let _mry_real_impl = ::mry::__enter_real_impl("Uploader::upload");
+                }
+
+                #[cfg(debug_assertions)]
+                #[must_use]
+                pub fn mock_upload(&mut self, bucket: impl ::core::convert::Into<::mry::ArgMatcher<::std::string::String>>, blob: impl ::core::convert::Into<::mry::ArgMatcher<Blob>>)
+                    -> ::mry::MockLocator<(::mry::Borrowed<str>, Blob,), usize, ::mry::Behavior2<(::mry::Borrowed<str>, Blob,), usize> > {
+                    ::mry::MockLocator::new(
+                        self.mry.mocks(),
+                        ::core::any::Any::type_id(&Self::upload),
+                        "Uploader::upload",
+                        &["bucket", "blob"],
+                        ::core::convert::Into::into((::mry::Borrowed::<str>::owned_matcher(::core::convert::Into::into(bucket)), ::core::convert::Into::into(blob),)),
+                    )
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn move_args_unlogged() {
+        let input: ImplItemFn = parse2(quote! {
+            #[mry(move_args(unlogged))]
+            fn archive(&self, stream: Stream) -> usize {}
+        })
+        .unwrap();
+
+        let (member, _) = transform(
+            &MryAttr::default(),
+            quote![self.mry.mocks()],
+            quote![Self::],
+            "Uploader::",
+            quote![self.mry.record_call_and_find_mock_output],
+            false,
+            Some(&input.vis),
+            &input.attrs,
+            &input.sig,
+            &TokenStream::default(),
+        );
+
+        assert!(member
+            .to_string()
+            .contains(&quote![(stream,), ::core::option::Option::None].to_string()));
+        assert!(!member.to_string().contains("move_args"));
+    }
+
+    #[test]
+    fn names_params_after_patterns() {
+        let sig: Signature = parse2(quote! {
+            fn transfer((from, to): (u8, u8), amount: u32, Pair(amount, _): Pair, _: String)
+        })
+        .unwrap();
+        let inputs: Vec<PatType> = sig
+            .inputs
+            .iter()
+            .map(|input| match input {
+                FnArg::Typed(input) => input.clone(),
+                FnArg::Receiver(_) => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(
+            param_names(&inputs)
+                .iter()
+                .map(std::string::ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["from_to", "amount", "arg2", "arg3"]
+        );
+    }
+    #[test]
+    fn deref_needs_smart_pointer() {
+        let input: ImplItemFn = parse2(quote! {
+            fn process(&self, #[mry(deref)] config: Config) {}
+        })
+        .unwrap();
+
+        let (member, _) = transform(
+            &MryAttr::default(),
+            quote![self.mry.mocks()],
+            quote![Self::],
+            "Worker::",
+            quote![self.mry.record_call_and_find_mock_output],
+            false,
+            Some(&input.vis),
+            &input.attrs,
+            &input.sig,
+            &TokenStream::default(),
+        );
+        assert!(member
+            .to_string()
+            .contains("`deref` needs a parameter of `Arc<T>`, `Box<T>` or `Rc<T>`"));
+    }
+
+    #[test]
+    fn names_types_not_send() {
+        let input: ImplItemFn = parse2(quote! {
+            fn share(&self, #[mry(deref)] config: Rc<Config>, cache: Vec<Rc<Entry>>) -> Rc<Report> {
+                todo!()
+            }
+        })
+        .unwrap();
+
+        let (member, _) = transform(
+            &MryAttr::default(),
+            quote![self.mry.mocks()],
+            quote![Self::],
+            "Worker::",
+            quote![self.mry.record_call_and_find_mock_output],
+            false,
+            Some(&input.vis),
+            &input.attrs,
+            &input.sig,
+            &TokenStream::default(),
+        );
+        let member = member.to_string();
+        assert!(!member.contains("in the argument `config`"));
+        assert!(member.contains(
+            "`Rc<Entry>` in the argument `cache` is not `Send`, which mocks need to record the calls"
+        ));
+        assert!(!member.contains("`Rc<Report>`"));
+    }
 }
@@ -0,0 +1,374 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Fields, FieldsNamed, Generics, Ident, Path};
+
+use crate::item_impl::upper_snake_case;
+use crate::MryAttr;
+
+#[derive(FromDeriveInput, Default)]
+#[darling(attributes(mry))]
+struct MatchAttr {
+    #[darling(rename = "crate")]
+    krate: Option<Path>,
+    /// Extra cfg predicate of the matchers, as `cfg` of `#[mry::mry]`
+    cfg: Option<syn::Expr>,
+}
+
+/// Name of the constructor of a variant, made raw if it is a keyword such as `type` for `Type`
+fn variant_constructor(variant: &Ident) -> Ident {
+    let name = upper_snake_case(&variant.to_string()).to_lowercase();
+    if syn::parse_str::<Ident>(&name).is_ok() {
+        format_ident!("{}", name)
+    } else if matches!(name.as_str(), "self" | "super" | "crate") {
+        // These cannot be raw identifiers
+        format_ident!("{}_", name)
+    } else {
+        format_ident!("r#{}", name)
+    }
+}
+
+/// Constructor of a matcher which matches the fields one by one
+fn constructor(
+    krate: &Path,
+    input: &DeriveInput,
+    name: &Ident,
+    path: TokenStream,
+    fields: &Fields,
+    exhaustive: bool,
+    generic: bool,
+) -> TokenStream {
+    let names: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            field
+                .ident
+                .clone()
+                .unwrap_or_else(|| format_ident!("arg{}", index))
+        })
+        .collect();
+    let types = fields.iter().map(|field| &field.ty);
+    let bindings: Vec<_> = names
+        .iter()
+        .map(|name| format_ident!("__mry_{}", name))
+        .collect();
+    let pattern = match fields {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|field| &field.ident);
+            quote![#path { #(#members: #bindings),* }]
+        }
+        Fields::Unnamed(_) => quote![#path(#(#bindings),*)],
+        Fields::Unit => quote![#path],
+    };
+    let condition = if names.is_empty() {
+        quote![true]
+    } else {
+        quote![#(#names.matches(#bindings))&&*]
+    };
+    let ident = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let ty = quote![#ident #ty_generics];
+    let params = &input.generics.params;
+    let params = (generic && !params.is_empty()).then(|| quote![<#params>]);
+    let generics = bounded_generics(input);
+    let where_clause = generic.then_some(generics.where_clause).flatten();
+    let otherwise = if exhaustive {
+        TokenStream::default()
+    } else {
+        quote![_ => false,]
+    };
+    quote! {
+        pub fn #name #params(#(#names: impl ::core::convert::Into<#krate::ArgMatcher<#types>>),*) -> #krate::ArgMatcher<#ty> #where_clause {
+            #(let #names: #krate::ArgMatcher<_> = ::core::convert::Into::into(#names);)*
+            #krate::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &#ty| match __mry_input {
+                #pattern => #condition,
+                #otherwise
+            }))
+        }
+    }
+}
+
+/// Generics of the input with `Send + 'static` bounds on the type parameters, as matchers are
+/// stored in mocks shared across threads
+fn bounded_generics(input: &DeriveInput) -> Generics {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for param in input.generics.type_params() {
+        let param = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::core::marker::Send + 'static));
+    }
+    if where_clause.predicates.is_empty() {
+        generics.where_clause = None;
+    }
+    generics
+}
+
+/// Matcher of a struct with named fields, which is a struct of a matcher per field. Fields left
+/// by `..Default::default()` match anything
+fn fields_matcher(
+    krate: &Path,
+    cfg: &TokenStream,
+    input: &DeriveInput,
+    matcher: &Ident,
+    fields: &FieldsNamed,
+) -> TokenStream {
+    let ty = &input.ident;
+    let vis = &input.vis;
+    let generics = bounded_generics(input);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let members: Vec<_> = fields.named.iter().map(|field| &field.ident).collect();
+    let visibilities = fields.named.iter().map(|field| &field.vis);
+    let types = fields.named.iter().map(|field| &field.ty);
+    let condition = if members.is_empty() {
+        quote![true]
+    } else {
+        quote![#(__mry_matcher.#members.matches(&__mry_input.#members))&&*]
+    };
+    let constructor = constructor(
+        krate,
+        input,
+        &format_ident!("new"),
+        quote![#ty],
+        &Fields::Named(fields.clone()),
+        true,
+        false,
+    );
+    quote! {
+        #[cfg(#cfg)]
+        #vis struct #matcher #impl_generics #where_clause {
+            #(#visibilities #members: #krate::ArgMatcher<#types>,)*
+        }
+
+        #[cfg(#cfg)]
+        impl #impl_generics ::core::default::Default for #matcher #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#members: #krate::ArgMatcher::Any,)*
+                }
+            }
+        }
+
+        #[cfg(#cfg)]
+        impl #impl_generics ::core::convert::From<#matcher #ty_generics> for #krate::ArgMatcher<#ty #ty_generics> #where_clause {
+            fn from(__mry_matcher: #matcher #ty_generics) -> Self {
+                #krate::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &#ty #ty_generics| #condition))
+            }
+        }
+
+        #[cfg(#cfg)]
+        impl #impl_generics #matcher #ty_generics #where_clause {
+            #constructor
+        }
+    }
+}
+
+/// Derives the matcher, gated as the mocks are, which is on release builds too if `bench` is set
+pub(crate) fn derive(input: DeriveInput, bench: bool) -> TokenStream {
+    let attr = match MatchAttr::from_derive_input(&input) {
+        Ok(attr) => attr,
+        Err(err) => return err.write_errors(),
+    };
+    let cfg = MryAttr {
+        cfg: attr.cfg,
+        bench,
+        ..Default::default()
+    }
+    .cfg();
+    let krate = attr.krate.unwrap_or_else(|| parse_quote!(::mry));
+    let ty = &input.ident;
+    let vis = &input.vis;
+    let matcher = format_ident!("{}Matcher", ty);
+    let constructors = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => return fields_matcher(&krate, &cfg, &input, &matcher, fields),
+        Data::Struct(data) => vec![constructor(
+            &krate,
+            &input,
+            &format_ident!("new"),
+            quote![#ty],
+            &data.fields,
+            true,
+            true,
+        )],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                constructor(
+                    &krate,
+                    &input,
+                    &variant_constructor(variant_ident),
+                    quote![#ty::#variant_ident],
+                    &variant.fields,
+                    data.variants.len() == 1,
+                    true,
+                )
+            })
+            .collect(),
+        Data::Union(_) => {
+            return syn::Error::new_spanned(ty, "MryMatch does not support unions")
+                .to_compile_error()
+        }
+    };
+    quote! {
+        #[cfg(#cfg)]
+        #vis struct #matcher;
+
+        #[cfg(#cfg)]
+        impl #matcher {
+            #(#constructors)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn enum_variants() {
+        let input: DeriveInput = parse2(quote! {
+            pub enum Command {
+                Insert { key: String, value: u32 },
+                Delete(String),
+                Clear,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            derive(input, false).to_string(),
+            quote! {
+                #[cfg(debug_assertions)]
+                pub struct CommandMatcher;
+
+                #[cfg(debug_assertions)]
+                impl CommandMatcher {
+                    pub fn insert(key: impl ::core::convert::Into<::mry::ArgMatcher<String>>, value: impl ::core::convert::Into<::mry::ArgMatcher<u32>>) -> ::mry::ArgMatcher<Command> {
+                        let key: ::mry::ArgMatcher<_> = ::core::convert::Into::into(key);
+                        let value: ::mry::ArgMatcher<_> = ::core::convert::Into::into(value);
+                        ::mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &Command| match __mry_input {
+                            Command::Insert { key: __mry_key, value: __mry_value } => key.matches(__mry_key) && value.matches(__mry_value),
+                            _ => false,
+                        }))
+                    }
+                    pub fn delete(arg0: impl ::core::convert::Into<::mry::ArgMatcher<String>>) -> ::mry::ArgMatcher<Command> {
+                        let arg0: ::mry::ArgMatcher<_> = ::core::convert::Into::into(arg0);
+                        ::mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &Command| match __mry_input {
+                            Command::Delete(__mry_arg0) => arg0.matches(__mry_arg0),
+                            _ => false,
+                        }))
+                    }
+                    pub fn clear() -> ::mry::ArgMatcher<Command> {
+                        ::mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &Command| match __mry_input {
+                            Command::Clear => true,
+                            _ => false,
+                        }))
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn struct_fields() {
+        let input: DeriveInput = parse2(quote! {
+            #[mry(crate = "my_mry")]
+            struct User {
+                id: u32,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            derive(input, false).to_string(),
+            quote! {
+                #[cfg(debug_assertions)]
+                struct UserMatcher {
+                    id: my_mry::ArgMatcher<u32>,
+                }
+
+                #[cfg(debug_assertions)]
+                impl ::core::default::Default for UserMatcher {
+                    fn default() -> Self {
+                        Self {
+                            id: my_mry::ArgMatcher::Any,
+                        }
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl ::core::convert::From<UserMatcher> for my_mry::ArgMatcher<User> {
+                    fn from(__mry_matcher: UserMatcher) -> Self {
+                        my_mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &User| __mry_matcher.id.matches(&__mry_input.id)))
+                    }
+                }
+
+                #[cfg(debug_assertions)]
+                impl UserMatcher {
+                    pub fn new(id: impl ::core::convert::Into<my_mry::ArgMatcher<u32>>) -> my_mry::ArgMatcher<User> {
+                        let id: my_mry::ArgMatcher<_> = ::core::convert::Into::into(id);
+                        my_mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &User| match __mry_input {
+                            User { id: __mry_id } => id.matches(__mry_id),
+                        }))
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn keyword_variants() {
+        let input: DeriveInput = parse2(quote! {
+            #[mry(cfg = test)]
+            enum Token {
+                Type,
+                SelfValue,
+                Crate,
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            derive(input, true).to_string(),
+            quote! {
+                #[cfg(test)]
+                struct TokenMatcher;
+
+                #[cfg(test)]
+                impl TokenMatcher {
+                    pub fn r#type() -> ::mry::ArgMatcher<Token> {
+                        ::mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &Token| match __mry_input {
+                            Token::Type => true,
+                            _ => false,
+                        }))
+                    }
+                    pub fn self_value() -> ::mry::ArgMatcher<Token> {
+                        ::mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &Token| match __mry_input {
+                            Token::SelfValue => true,
+                            _ => false,
+                        }))
+                    }
+                    pub fn crate_() -> ::mry::ArgMatcher<Token> {
+                        ::mry::ArgMatcher::Fn(::std::boxed::Box::new(move |__mry_input: &Token| match __mry_input {
+                            Token::Crate => true,
+                            _ => false,
+                        }))
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+}
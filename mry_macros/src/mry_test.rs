@@ -0,0 +1,103 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_quote, ItemFn};
+
+/// Input of `#[mry::test(crate = "path")]`
+pub struct TestAttr {
+    krate: Option<syn::Path>,
+}
+
+impl syn::parse::Parse for TestAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            krate: crate::lock::parse_crate(input)?,
+        })
+    }
+}
+
+/// Runs the test with a token of its own, adding `#[test]` unless another test attribute such as
+/// `#[tokio::test]` is there
+pub(crate) fn transform(args: TestAttr, mut input: ItemFn) -> TokenStream {
+    let krate = args.krate.unwrap_or_else(|| parse_quote!(::mry));
+    let has_test_attr = input.attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "test")
+    });
+    if !has_test_attr {
+        input.attrs.insert(0, parse_quote!(#[test]));
+    }
+    let name = input.sig.ident.to_string();
+    let name = quote![::core::concat!(::core::module_path!(), "::", #name)];
+    let block = input.block.clone();
+    input.block.stmts.clear();
+    input.block.stmts.push(syn::Stmt::Expr(
+        if input.sig.asyncness.is_some() {
+            parse_quote! {
+                #krate::__run_async_test(#name, ::std::boxed::Box::pin(async move #block)).await
+            }
+        } else {
+            parse_quote! {
+                #krate::__run_test(#name, move || #block)
+            }
+        },
+        None,
+    ));
+    input.into_token_stream()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn adds_test_attribute() {
+        let input: ItemFn = parse2(quote! {
+            fn meows() {
+                assert!(true);
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(TestAttr { krate: None }, input).to_string(),
+            quote! {
+                #[test]
+                fn meows() {
+                    ::mry::__run_test(::core::concat!(::core::module_path!(), "::", "meows"), move | | {
+                        assert!(true);
+                    })
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn async_test() {
+        let input: ItemFn = parse2(quote! {
+            #[tokio::test]
+            async fn meows() {
+                assert!(true);
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(TestAttr { krate: None }, input).to_string(),
+            quote! {
+                #[tokio::test]
+                async fn meows() {
+                    ::mry::__run_async_test(::core::concat!(::core::module_path!(), "::", "meows"), ::std::boxed::Box::pin(async move {
+                        assert!(true);
+                    })).await
+                }
+            }
+            .to_string()
+        );
+    }
+}
@@ -19,7 +19,7 @@ pub(crate) fn transform(input: ExprStruct) -> TokenStream {
         })
         .collect();
     fields.push(quote! {
-        mry: Default::default(),
+        mry: ::core::default::Default::default(),
     });
     quote! {
         #ident {
@@ -49,7 +49,7 @@ mod test {
             quote! {
                 Cat {
                     name: "aaa",
-                    mry: Default::default(),
+                    mry: ::core::default::Default::default(),
                 }
             }
             .to_string()
@@ -70,7 +70,7 @@ mod test {
             quote! {
                 Cat::<A> {
                     name: "aaa",
-                    mry: Default::default(),
+                    mry: ::core::default::Default::default(),
                 }
             }
             .to_string()
@@ -0,0 +1,175 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse::Parse, parse_quote, punctuated::Punctuated, Expr, Path, Token};
+
+use crate::lock::parse_crate;
+
+pub struct SequenceSteps {
+    krate: Option<Path>,
+    steps: Vec<Expr>,
+}
+
+impl Parse for SequenceSteps {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            krate: parse_crate(input)?,
+            steps: Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect(),
+        })
+    }
+}
+
+/// Methods of `MockLocator` returning the locator, which may be chained to the call of a step.
+/// The `returns*` family is matched by its prefix
+const LOCATOR_METHODS: &[&str] = &[
+    "calls_real_impl",
+    "echoes",
+    "echoes_field",
+    "group",
+    "identity",
+    "in_sequence",
+    "inspect",
+    "log_store",
+    "measure_durations",
+    "memoizes",
+    "on_failpoint",
+    "otherwise_real",
+    "panics_with",
+];
+
+/// Whether the method is chained to the `mock_*` locator, such as `returns`
+fn is_behavior(method: &syn::Ident) -> bool {
+    let method = method.to_string();
+    method.starts_with("returns") || LOCATOR_METHODS.contains(&method.as_str())
+}
+
+/// Replaces the call configured by the behaviors chained to it with its `mock_*` counterpart.
+/// `cat.meow(Any).returns(x)` becomes `cat.mock_meow(Any).returns(x)`, while
+/// `svc.client().fetch(Any)` becomes `svc.client().mock_fetch(Any)`.
+fn to_mock_chain(expr: &mut Expr, macro_name: &str) -> syn::Result<()> {
+    match expr {
+        Expr::MethodCall(call) if is_behavior(&call.method) => {
+            to_mock_chain(&mut call.receiver, macro_name)
+        }
+        expr => to_mock(expr, macro_name),
+    }
+}
+
+/// Replaces the outermost call with its `mock_*` counterpart.
+/// `svc.client().fetch(Any)` becomes `svc.client().mock_fetch(Any)`.
+pub(crate) fn to_mock(expr: &mut Expr, macro_name: &str) -> syn::Result<()> {
+    match expr {
+        Expr::MethodCall(call) => {
+            call.method = format_ident!("mock_{}", call.method);
+            Ok(())
+        }
+        Expr::Call(call) => match call.func.as_mut() {
+            Expr::Path(path) => {
+                let last = path.path.segments.last_mut().unwrap();
+                last.ident = format_ident!("mock_{}", last.ident);
+                Ok(())
+            }
+            func => Err(syn::Error::new_spanned(
+                func,
+                format!("{} expects a method or function call", macro_name),
+            )),
+        },
+        expr => Err(syn::Error::new_spanned(
+            expr,
+            format!("{} expects a method or function call", macro_name),
+        )),
+    }
+}
+
+pub(crate) fn transform(input: SequenceSteps) -> TokenStream {
+    let krate = input.krate.unwrap_or_else(|| parse_quote!(::mry));
+    let steps =
+        input.steps.into_iter().map(
+            |mut step| match to_mock_chain(&mut step, "mry::sequence!") {
+                Ok(()) => quote![#step.in_sequence(&__mry_sequence);],
+                Err(err) => err.to_compile_error(),
+            },
+        );
+    quote! {
+        {
+            let __mry_sequence = <#krate::Sequence as ::core::default::Default>::default();
+            #(#steps)*
+            __mry_sequence
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn replaces_calls_with_mocks() {
+        let input: SequenceSteps = parse2(quote! {
+            cat.open("a"),
+            cat.read(Any).returns(1),
+            Cat::new(Any).returns_once(cat),
+            svc.client().fetch(Any).returns(2),
+            svc.client().close(),
+            hello(),
+            cat.meow(Any).identity().otherwise_real(),
+            cat.purr().group("g").on_failpoint("f", h).measure_durations(),
+            cat.sleep().log_store(store).inspect(f).memoizes(g),
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input).to_string(),
+            quote! {
+                {
+                    let __mry_sequence = <::mry::Sequence as ::core::default::Default>::default();
+                    cat.mock_open("a").in_sequence(&__mry_sequence);
+                    cat.mock_read(Any).returns(1).in_sequence(&__mry_sequence);
+                    Cat::mock_new(Any).returns_once(cat).in_sequence(&__mry_sequence);
+                    svc.client().mock_fetch(Any).returns(2).in_sequence(&__mry_sequence);
+                    svc.client().mock_close().in_sequence(&__mry_sequence);
+                    mock_hello().in_sequence(&__mry_sequence);
+                    cat.mock_meow(Any).identity().otherwise_real().in_sequence(&__mry_sequence);
+                    cat.mock_purr().group("g").on_failpoint("f", h).measure_durations().in_sequence(&__mry_sequence);
+                    cat.mock_sleep().log_store(store).inspect(f).memoizes(g).in_sequence(&__mry_sequence);
+                    __mry_sequence
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn renamed_crate() {
+        let input: SequenceSteps = parse2(quote! {
+            crate = "my_mry",
+            hello(),
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input).to_string(),
+            quote! {
+                {
+                    let __mry_sequence = <my_mry::Sequence as ::core::default::Default>::default();
+                    mock_hello().in_sequence(&__mry_sequence);
+                    __mry_sequence
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_non_call() {
+        let input: SequenceSteps = parse2(quote!(cat)).unwrap();
+
+        assert!(transform(input)
+            .to_string()
+            .contains("mry::sequence! expects a method or function call"));
+    }
+}
@@ -0,0 +1,141 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Parse, parse_quote, punctuated::Punctuated, Expr, Path, Token};
+
+use crate::{lock::parse_crate, sequence::to_mock};
+
+/// `cat.meow(3) => "m"`
+struct Stub {
+    call: Expr,
+    output: Expr,
+}
+
+impl Parse for Stub {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let call = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        Ok(Self {
+            call,
+            output: input.parse()?,
+        })
+    }
+}
+
+pub struct Stubs {
+    krate: Option<Path>,
+    stubs: Vec<Stub>,
+}
+
+impl Parse for Stubs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            krate: parse_crate(input)?,
+            stubs: Punctuated::<Stub, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect(),
+        })
+    }
+}
+
+/// Replaces `_` in the arguments of the call with `Any`
+fn replace_wildcards(call: &mut Expr, krate: &Path) {
+    let args = match call {
+        Expr::MethodCall(call) => &mut call.args,
+        Expr::Call(call) => &mut call.args,
+        _ => return,
+    };
+    for arg in args.iter_mut() {
+        if let Expr::Infer(_) = arg {
+            *arg = parse_quote!(#krate::Any);
+        }
+    }
+}
+
+fn behavior(output: &Expr) -> TokenStream {
+    match output {
+        Expr::Path(path) if path.path.is_ident("calls_real") => quote![.calls_real_impl()],
+        output => quote![.returns(::core::convert::Into::into(#output))],
+    }
+}
+
+pub(crate) fn transform(input: Stubs) -> TokenStream {
+    let krate = input.krate.unwrap_or_else(|| parse_quote!(::mry));
+    let stubs = input.stubs.into_iter().map(|mut stub| {
+        replace_wildcards(&mut stub.call, &krate);
+        match to_mock(&mut stub.call, "mry::stub!") {
+            Ok(()) => {
+                let call = &stub.call;
+                let behavior = behavior(&stub.output);
+                quote![#call #behavior;]
+            }
+            Err(err) => err.to_compile_error(),
+        }
+    });
+    quote! {
+        {
+            #(#stubs)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn expands_into_locators() {
+        let input: Stubs = parse2(quote! {
+            cat.meow(3) => "m",
+            cat.purr(_) => (),
+            Cat::new(_, 1) => cat,
+            hello() => calls_real,
+            svc.client(1).fetch(_) => x,
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input).to_string(),
+            quote! {
+                {
+                    cat.mock_meow(3).returns(::core::convert::Into::into("m"));
+                    cat.mock_purr(::mry::Any).returns(::core::convert::Into::into(()));
+                    Cat::mock_new(::mry::Any, 1).returns(::core::convert::Into::into(cat));
+                    mock_hello().calls_real_impl();
+                    svc.client(1).mock_fetch(::mry::Any).returns(::core::convert::Into::into(x));
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn renamed_crate() {
+        let input: Stubs = parse2(quote! {
+            crate = "my_mry",
+            hello(_) => 1,
+        })
+        .unwrap();
+
+        assert_eq!(
+            transform(input).to_string(),
+            quote! {
+                {
+                    mock_hello(my_mry::Any).returns(::core::convert::Into::into(1));
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_non_call() {
+        let input: Stubs = parse2(quote!(cat => 1)).unwrap();
+
+        assert!(transform(input)
+            .to_string()
+            .contains("mry::stub! expects a method or function call"));
+    }
+}
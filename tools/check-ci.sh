@@ -6,3 +6,4 @@ set -e
 cargo check
 cargo check --release
 cargo test
+cargo test --all-features